@@ -1,9 +1,13 @@
 use anyhow::{Context, Result, bail};
 use serde::{Deserialize, Serialize};
+
+use crate::keymap::KeyMap;
+use ratatui::style::Color;
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     fs,
     path::{Path, PathBuf},
+    time::{Duration, SystemTime},
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -15,13 +19,117 @@ pub struct AppConfig {
     #[serde(default)]
     pub show_all_tab: Option<bool>, // deprecated; ignored
     #[serde(default)]
-    pub all_tab_position: Option<AllTabPosition>, // deprecated; ignored
+    pub all_tab_position: Option<AllTabPosition>,
+    /// Home table columns in display order; empty means the default set.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub columns: Vec<String>,
+    /// Start the home list in dense mode (Name + Workspace only); toggled with `v`.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub dense: bool,
+    /// Wrap around at the ends of the list and tab bar; `false` stops at the edges.
+    #[serde(default = "default_true", skip_serializing_if = "is_true")]
+    pub wrap_navigation: bool,
+    /// `[keys]` remaps character keys by action name (see `crate::keymap`).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub keys: BTreeMap<String, String>,
+    /// Show the local time on the right of the home status line.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub show_clock: bool,
+    /// Seconds before a status-line message clears itself; `0` keeps messages until replaced.
+    #[serde(
+        default = "default_message_timeout_secs",
+        skip_serializing_if = "is_default_message_timeout_secs"
+    )]
+    pub message_timeout_secs: u64,
+    /// Editor split-ratio change per key press, toolbar click or wheel notch (default 0.1).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ratio_step: Option<f32>,
+    /// Marker drawn before the selected row (default `"> "`, at most 4 cells wide).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub highlight_symbol: Option<String>,
+    /// Selected-row text color: a name like `black`, an index like `16`, or `#rrggbb`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selection_fg: Option<String>,
+    /// Selected-row background color, in the same forms as `selection_fg`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selection_bg: Option<String>,
+    /// Extra cells on each side of an editor split bar that still grab it (default 1, max 3).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub split_hit_slop: Option<u16>,
+    /// Layouts with more slots than this ask before launching from the TUI (default 6; 0 never asks).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slot_warning_threshold: Option<usize>,
+    /// Ask before deleting a tab with no worksets too (by default empty tabs go at once).
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub confirm_empty_tab_delete: bool,
+    /// Append the live window count of each workset's workspace to the Workspace column.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub show_window_counts: bool,
+    /// Start processes hyprsets spawns itself (`--capture` commands, `xdg-open`) in their own
+    /// process group so they outlive a Ctrl-C or a closed terminal.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub detach_commands: bool,
+    /// Whether the home list leads with workset names (default) or ids.
+    #[serde(default, skip_serializing_if = "PrimaryLabel::is_default")]
+    pub primary_label: PrimaryLabel,
+    /// Drop tab entries naming worksets that no longer exist when the config is loaded, and
+    /// write the cleaned config back.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub repair_on_load: bool,
+    /// Experimental: switch to the selected workset's workspace once the home list selection
+    /// settles, and back to the starting workspace when the list closes.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub preview_workspace_on_select: bool,
+    /// Answer a bare Enter gives the launch confirmation; unset means only `y`/`n` answer.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub run_confirm_default: Option<ConfirmDefault>,
+    /// Answer a bare Enter gives the workset delete confirmation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delete_confirm_default: Option<ConfirmDefault>,
+    /// Mark special workspaces with a glyph in the home list's Workspace column.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub workspace_icons: bool,
     #[serde(rename = "tab", default)]
     pub tabs: Vec<TabConfig>,
     #[serde(rename = "workset")]
     pub worksets: Vec<Workset>,
 }
 
+impl Default for AppConfig {
+    /// The values a config without any settings parses to, and no worksets.
+    fn default() -> Self {
+        Self {
+            version: default_version(),
+            default_tab: None,
+            show_all_tab: None,
+            all_tab_position: None,
+            columns: Vec::new(),
+            dense: false,
+            wrap_navigation: true,
+            keys: BTreeMap::new(),
+            show_clock: false,
+            message_timeout_secs: DEFAULT_MESSAGE_TIMEOUT_SECS,
+            ratio_step: None,
+            highlight_symbol: None,
+            selection_fg: None,
+            selection_bg: None,
+            split_hit_slop: None,
+            slot_warning_threshold: None,
+            confirm_empty_tab_delete: false,
+            show_window_counts: false,
+            detach_commands: false,
+            primary_label: PrimaryLabel::default(),
+            repair_on_load: false,
+            preview_workspace_on_select: false,
+            run_confirm_default: None,
+            delete_confirm_default: None,
+            workspace_icons: false,
+            tabs: Vec::new(),
+            worksets: Vec::new(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TabConfig {
     pub id: String,
@@ -30,6 +138,64 @@ pub struct TabConfig {
     pub worksets: Vec<String>,
     #[serde(default)]
     pub include_unassigned: bool,
+    /// Pinned tabs are listed before unpinned ones, keeping their relative order.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub pinned: bool,
+    /// Cleanup mode for worksets in this tab that don't set their own `cleanup`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_cleanup: Option<CleanupMode>,
+    /// Order of the worksets pulled in by `include_unassigned`.
+    #[serde(default, skip_serializing_if = "UnassignedOrder::is_default")]
+    pub unassigned_order: UnassignedOrder,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+#[allow(clippy::enum_variant_names)]
+pub enum UnassignedOrder {
+    /// Alphabetical by workset id.
+    #[default]
+    #[serde(rename = "name")]
+    ByName,
+    /// The order worksets appear in the config file.
+    #[serde(rename = "config")]
+    ByConfigOrder,
+    /// Most recently modified first; worksets never modified since go last, by id.
+    #[serde(rename = "modified")]
+    ByModified,
+    /// Most recently launched first, from the state file; never-launched worksets go last, by id.
+    #[serde(rename = "last_run")]
+    ByLastRun,
+}
+
+impl UnassignedOrder {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Which label leads in the home list's Name column and the launch prompt.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PrimaryLabel {
+    #[default]
+    Name,
+    /// The id leads and the name follows it, dimmed in the list.
+    Id,
+}
+
+impl PrimaryLabel {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// `ws` labelled this way round: the name alone, or `id (name)`.
+    pub fn describe(self, ws: &Workset) -> String {
+        match self {
+            Self::Name => ws.name.clone(),
+            Self::Id => format!("{} ({})", ws.id, ws.name),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
@@ -39,6 +205,84 @@ pub enum AllTabPosition {
     Last,
 }
 
+/// Answer picked by Enter in a yes/no confirmation.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfirmDefault {
+    Yes,
+    No,
+}
+
+/// What to do with windows already on the target workspace before a launch.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CleanupMode {
+    /// Prompt before closing existing windows.
+    #[default]
+    Ask,
+    /// Close existing windows without prompting.
+    Close,
+    /// Leave existing windows open and launch alongside them.
+    Keep,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListColumn {
+    No,
+    /// A dot on worksets marked `startup`.
+    Startup,
+    Name,
+    Description,
+    Tab,
+    Workspace,
+    /// How long ago the workset was last changed, e.g. `3d ago`.
+    Modified,
+}
+
+pub const DEFAULT_LIST_COLUMNS: [ListColumn; 6] = [
+    ListColumn::No,
+    ListColumn::Startup,
+    ListColumn::Name,
+    ListColumn::Description,
+    ListColumn::Tab,
+    ListColumn::Workspace,
+];
+
+impl ListColumn {
+    /// Every column, in the order the unknown-column warning lists them.
+    pub const ALL: [ListColumn; 7] = [
+        ListColumn::No,
+        ListColumn::Startup,
+        ListColumn::Name,
+        ListColumn::Description,
+        ListColumn::Tab,
+        ListColumn::Workspace,
+        ListColumn::Modified,
+    ];
+
+    /// The name a `columns` entry uses for this column.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::No => "no",
+            Self::Startup => "startup",
+            Self::Name => "name",
+            Self::Description => "description",
+            Self::Tab => "tab",
+            Self::Workspace => "workspace",
+            Self::Modified => "modified",
+        }
+    }
+
+    /// Accepts any column's `name` in any case, and `desc` for `description`.
+    pub fn from_name(raw: &str) -> Option<Self> {
+        let raw = raw.trim().to_ascii_lowercase();
+        if raw == "desc" {
+            return Some(Self::Description);
+        }
+        Self::ALL.into_iter().find(|column| column.name() == raw)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Workset {
     pub id: String,
@@ -46,14 +290,165 @@ pub struct Workset {
     pub desc: String,
     #[serde(default)]
     pub workspace: Option<String>,
+    #[serde(default)]
     pub commands: Vec<String>,
+    /// File whose lines become `commands` on load (blank lines and `#` comments are skipped).
+    /// Relative paths start at the config directory; never combined with inline `commands`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commands_file: Option<PathBuf>,
     #[serde(default)]
     pub cwd: Option<PathBuf>,
     #[serde(default)]
     pub env: HashMap<String, String>,
+    /// Disable Hyprland animations while the layout is being launched.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub suppress_animations: bool,
+    /// Toggle the (special) target workspace instead of relaunching when it is already populated.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub scratchpad: bool,
+    /// Overrides the owning tab's `default_cleanup`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cleanup: Option<CleanupMode>,
+    /// When false, focus returns to the previously active workspace once the launch is done.
+    #[serde(default = "default_true", skip_serializing_if = "is_true")]
+    pub switch_focus: bool,
+    /// Offered as a starting point in the New dialog.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub template: bool,
+    /// Fire all `commands` at once without the delay between them. Ignored with a layout.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub parallel: bool,
+    /// Always ask before launching from the home list, even with nothing to close.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub confirm: bool,
+    /// Launched by `hyprsets startup`, e.g. from Hyprland's `exec-once`.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub startup: bool,
+    /// Start each command in its own transient `systemd-run --user --scope`.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub scope: bool,
+    /// Slice the scopes go into (`--slice`); setting it implies `scope`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slice: Option<String>,
+    /// Abort the launch once this many milliseconds have passed, checked between slots.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_timeout_ms: Option<u64>,
+    /// When the workset was last changed from hyprsets (stored as Unix seconds). Launching
+    /// does not count. Absent in configs written before this was tracked.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "unix_seconds"
+    )]
+    pub modified: Option<SystemTime>,
     pub layout: Option<LayoutNode>,
 }
 
+impl Default for Workset {
+    /// The values an entry with only `id`, `name` and `desc` parses to.
+    fn default() -> Self {
+        Self {
+            id: String::new(),
+            name: String::new(),
+            desc: String::new(),
+            workspace: None,
+            commands: vec![],
+            cwd: None,
+            env: HashMap::new(),
+            suppress_animations: false,
+            scratchpad: false,
+            cleanup: None,
+            switch_focus: true,
+            template: false,
+            parallel: false,
+            confirm: false,
+            startup: false,
+            commands_file: None,
+            total_timeout_ms: None,
+            modified: None,
+            scope: false,
+            slice: None,
+            layout: None,
+        }
+    }
+}
+
+impl Workset {
+    /// Records that the workset was just changed.
+    pub fn touch(&mut self) {
+        self.modified = Some(SystemTime::now());
+    }
+
+    /// A workset with no commands or layout and every option at its default.
+    pub fn blank(id: &str, name: &str, desc: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            desc: desc.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// `total_timeout_ms` as a duration; unset or `0` means no overall limit.
+    pub fn total_timeout(&self) -> Option<Duration> {
+        self.total_timeout_ms
+            .filter(|ms| *ms > 0)
+            .map(Duration::from_millis)
+    }
+
+    /// Commands this workset launches: `commands` when set, otherwise the layout's leaf
+    /// commands. Hand-edited configs often carry a layout with an empty `commands` list.
+    pub fn effective_commands(&self) -> Vec<String> {
+        if !self.commands.is_empty() {
+            return self.commands.clone();
+        }
+        let mut commands = Vec::new();
+        if let Some(layout) = &self.layout {
+            layout.collect_commands(&mut commands);
+        }
+        commands
+    }
+
+    /// True when both `commands` and a layout are set but hold different commands. The
+    /// launcher only uses the layout, so stale `commands` are misleading; the editor rewrites
+    /// them from the layout on save.
+    pub fn commands_out_of_sync(&self) -> bool {
+        let Some(layout) = &self.layout else {
+            return false;
+        };
+        if self.commands.is_empty() {
+            return false;
+        }
+        let mut from_layout = Vec::new();
+        layout.collect_commands(&mut from_layout);
+        let mut listed: Vec<&str> = self.commands.iter().map(String::as_str).collect();
+        let mut from_layout: Vec<&str> = from_layout.iter().map(String::as_str).collect();
+        listed.sort_unstable();
+        from_layout.sort_unstable();
+        listed != from_layout
+    }
+
+    /// A new workset that copies the template's commands, layout, env, cwd, workspace,
+    /// parallel flag and systemd scope settings.
+    /// The copy is not itself a template and shares nothing with the original.
+    pub fn from_template(template: &Workset, id: &str, name: &str, desc: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            desc: desc.to_string(),
+            workspace: template.workspace.clone(),
+            commands: template.commands.clone(),
+            cwd: template.cwd.clone(),
+            env: template.env.clone(),
+            parallel: template.parallel,
+            scope: template.scope,
+            slice: template.slice.clone(),
+            layout: template.layout.clone(),
+            ..Self::default()
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "lowercase")]
 pub enum LayoutNode {
@@ -61,6 +456,81 @@ pub enum LayoutNode {
     Split(SplitNode),
 }
 
+impl LayoutNode {
+    /// Slot commands in launch order (left before right).
+    pub fn collect_commands(&self, commands: &mut Vec<String>) {
+        match self {
+            LayoutNode::Leaf(slot) => commands.push(slot.command.clone()),
+            LayoutNode::Split(split) => {
+                split.left.collect_commands(commands);
+                split.right.collect_commands(commands);
+            }
+        }
+    }
+
+    /// Slot ids in launch order, duplicates included.
+    pub fn collect_slot_ids_list(&self, ids: &mut Vec<u32>) {
+        match self {
+            LayoutNode::Leaf(slot) => ids.push(slot.slot_id),
+            LayoutNode::Split(split) => {
+                split.left.collect_slot_ids_list(ids);
+                split.right.collect_slot_ids_list(ids);
+            }
+        }
+    }
+
+    /// Slot ids used by more than one slot, ascending. The launcher tracks slots by id, so
+    /// only the first slot with a given id would be launched.
+    pub fn duplicate_slot_ids(&self) -> Vec<u32> {
+        let mut ids = Vec::new();
+        self.collect_slot_ids_list(&mut ids);
+        ids.sort_unstable();
+        let mut duplicates: Vec<u32> = ids
+            .windows(2)
+            .filter(|w| w[0] == w[1])
+            .map(|w| w[0])
+            .collect();
+        duplicates.dedup();
+        duplicates
+    }
+
+    /// Gives the slots ids 1, 2, … in launch order.
+    pub fn renumber_slots(&mut self) {
+        fn walk(node: &mut LayoutNode, next: &mut u32) {
+            match node {
+                LayoutNode::Leaf(slot) => {
+                    slot.slot_id = *next;
+                    *next += 1;
+                }
+                LayoutNode::Split(split) => {
+                    walk(&mut split.left, next);
+                    walk(&mut split.right, next);
+                }
+            }
+        }
+        walk(self, &mut 1);
+    }
+
+    /// Whether every split runs in the same direction, i.e. the slots just sit in one row or
+    /// column and the layout reads as a plain list in launch order. A single slot counts.
+    pub fn is_linear(&self) -> bool {
+        fn same_direction(node: &LayoutNode, direction: SplitDirection) -> bool {
+            match node {
+                LayoutNode::Leaf(_) => true,
+                LayoutNode::Split(split) => {
+                    split.direction == direction
+                        && same_direction(&split.left, direction)
+                        && same_direction(&split.right, direction)
+                }
+            }
+        }
+        match self {
+            LayoutNode::Leaf(_) => true,
+            LayoutNode::Split(split) => same_direction(self, split.direction),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WindowSlot {
     pub slot_id: u32,
@@ -71,6 +541,46 @@ pub struct WindowSlot {
     pub env: HashMap<String, String>,
     #[serde(default)]
     pub wait_after_ms: Option<u64>,
+    /// Window class this slot is expected to open; `run --fill` uses it to spot slots
+    /// whose window is already there.
+    #[serde(default)]
+    pub class: Option<String>,
+    /// Launch this slot on another workspace than the workset's (same forms as `workspace`).
+    #[serde(default)]
+    pub workspace: Option<String>,
+    /// Application the slot is meant to open, for documentation and the editor. Also stands in
+    /// for `class` when matching windows if no class is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub app: Option<String>,
+    /// Disabled slots keep their place in the layout but are not launched.
+    #[serde(default = "default_true", skip_serializing_if = "is_true")]
+    pub enabled: bool,
+}
+
+impl WindowSlot {
+    /// An enabled slot running `command`, with every optional setting left unset.
+    pub fn new(slot_id: u32, command: impl Into<String>) -> Self {
+        Self {
+            slot_id,
+            command: command.into(),
+            cwd: None,
+            env: HashMap::new(),
+            wait_after_ms: None,
+            class: None,
+            workspace: None,
+            app: None,
+            enabled: true,
+        }
+    }
+
+    /// Window class to match this slot's window by: `class`, else `app`.
+    pub fn class_hint(&self) -> Option<&str> {
+        [self.class.as_deref(), self.app.as_deref()]
+            .into_iter()
+            .flatten()
+            .map(str::trim)
+            .find(|value| !value.is_empty())
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -81,7 +591,7 @@ pub struct SplitNode {
     pub right: Box<LayoutNode>,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum SplitDirection {
     Horizontal,
@@ -90,26 +600,70 @@ pub enum SplitDirection {
 
 impl AppConfig {
     /// Load config from path. If the file is missing or empty, write and return a default template.
+    /// With `repair_on_load`, dangling tab references are removed and the file rewritten.
     pub fn load_or_init(path: &Path) -> Result<Self> {
-        if path.exists() {
-            let raw = fs::read_to_string(path)
-                .with_context(|| format!("failed to read config file: {}", path.display()))?;
-            if raw.trim().is_empty() {
-                return Self::write_default(path);
+        match Self::load(path)? {
+            Some(mut cfg) => {
+                if cfg.repair_on_load {
+                    let removed = cfg.remove_dangling_tab_refs();
+                    for (tab, id) in &removed {
+                        eprintln!("repaired: removed missing workset '{id}' from tab '{tab}'");
+                    }
+                    if !removed.is_empty() {
+                        cfg.save(path)?;
+                    }
+                }
+                Ok(cfg)
             }
-            let cfg: Self = toml::from_str(&raw)
-                .with_context(|| format!("failed to parse config file: {}", path.display()))?;
-            cfg.validate_tabs()?;
-            cfg.warn_tab_version();
-            return Ok(cfg);
+            None => Self::write_default(path),
+        }
+    }
+
+    /// Removes tab entries whose workset id doesn't exist, keeping the rest in order.
+    /// Returns the removed `(tab id, workset id)` pairs.
+    pub fn remove_dangling_tab_refs(&mut self) -> Vec<(String, String)> {
+        let known: HashSet<&str> = self.worksets.iter().map(|ws| ws.id.as_str()).collect();
+        let mut removed = Vec::new();
+        for tab in &mut self.tabs {
+            tab.worksets.retain(|id| {
+                let keep = known.contains(id.as_str());
+                if !keep {
+                    removed.push((tab.id.clone(), id.clone()));
+                }
+                keep
+            });
         }
+        removed
+    }
 
-        Self::write_default(path)
+    /// Like [`AppConfig::load_or_init`] but never writes: a missing or empty file is `Ok(None)`.
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let raw = fs::read_to_string(path)
+            .with_context(|| format!("failed to read config file: {}", path.display()))?;
+        if raw.trim().is_empty() {
+            return Ok(None);
+        }
+        let mut cfg: Self = toml::from_str(&raw)
+            .with_context(|| format!("failed to parse config file: {}", path.display()))?;
+        cfg.read_commands_files(path.parent().unwrap_or(Path::new("")))?;
+        cfg.validate_worksets()?;
+        cfg.validate_tabs()?;
+        cfg.warn_config();
+        Ok(Some(cfg))
     }
 
     pub fn save(&self, path: &Path) -> Result<()> {
         self.ensure_parent(path)?;
-        let serialized = toml::to_string_pretty(self).context("failed to serialize config")?;
+        let mut on_disk = self.clone();
+        for ws in &mut on_disk.worksets {
+            if ws.commands_file.is_some() {
+                ws.commands.clear();
+            }
+        }
+        let serialized = toml::to_string_pretty(&on_disk).context("failed to serialize config")?;
         fs::write(path, serialized)
             .with_context(|| format!("failed to write config file: {}", path.display()))
     }
@@ -132,22 +686,14 @@ impl AppConfig {
         let layout = LayoutNode::Split(SplitNode {
             direction: SplitDirection::Horizontal,
             ratio: 1.2,
-            left: Box::new(LayoutNode::Leaf(WindowSlot {
-                slot_id: 1,
-                command: String::from(r#"code -n "$HOME/ws/demo""#),
-                cwd: None,
-                env: HashMap::new(),
-                wait_after_ms: None,
-            })),
-            right: Box::new(LayoutNode::Leaf(WindowSlot {
-                slot_id: 2,
-                command: String::from(
-                    r#"omarchy-launch-browser --new-window "https://example.com""#,
-                ),
-                cwd: None,
-                env: HashMap::new(),
-                wait_after_ms: None,
-            })),
+            left: Box::new(LayoutNode::Leaf(WindowSlot::new(
+                1,
+                r#"code -n "$HOME/ws/demo""#,
+            ))),
+            right: Box::new(LayoutNode::Leaf(WindowSlot::new(
+                2,
+                r#"omarchy-launch-browser --new-window "https://example.com""#,
+            ))),
         });
 
         let workset = Workset {
@@ -161,23 +707,244 @@ impl AppConfig {
                 String::from(r#"hyprctl dispatch movefocus l"#),
                 String::from(r#"hyprctl dispatch splitratio exact 1.2"#),
             ],
-            cwd: None,
-            env: HashMap::new(),
             layout: Some(layout),
+            ..Workset::default()
         };
 
         Self {
-            version: 1,
-            default_tab: None,
-            show_all_tab: None,
-            all_tab_position: None,
-            tabs: Vec::new(),
             worksets: vec![workset],
+            ..Self::default()
         }
     }
 }
 
 impl AppConfig {
+    /// Every `[[tab]]` resolved in config order, as indices into `worksets`, plus a warning
+    /// for each listed id that is missing or already claimed by an earlier tab. Worksets no
+    /// earlier tab claimed go to the first `include_unassigned` tab, in its `unassigned_order`;
+    /// `last_run` is the run history `"last_run"` sorts by.
+    pub fn resolve_tabs(
+        &self,
+        last_run: &BTreeMap<String, SystemTime>,
+    ) -> (Vec<Vec<usize>>, Vec<String>) {
+        let mut warnings = Vec::new();
+        let index_map: HashMap<&str, usize> = self
+            .worksets
+            .iter()
+            .enumerate()
+            .map(|(idx, ws)| (ws.id.as_str(), idx))
+            .collect();
+
+        // Indexed like `worksets`; one flag per workset instead of a set of cloned ids.
+        let mut assigned = vec![false; self.worksets.len()];
+        let mut tabs = Vec::with_capacity(self.tabs.len());
+
+        for tab in &self.tabs {
+            let mut indices = Vec::new();
+            for id in &tab.worksets {
+                if let Some(&idx) = index_map.get(id.as_str()) {
+                    if assigned[idx] {
+                        warnings.push(format!(
+                            "workset '{}' already assigned to another tab; skipped in '{}'",
+                            id, tab.id
+                        ));
+                        continue;
+                    }
+                    indices.push(idx);
+                    assigned[idx] = true;
+                } else {
+                    warnings.push(format!(
+                        "tab '{}' references missing workset '{}'",
+                        tab.id, id
+                    ));
+                }
+            }
+            if tab.include_unassigned {
+                // With duplicate ids only the last one is reachable by id, as in `index_map`.
+                let mut extras: Vec<(&str, usize)> = self
+                    .worksets
+                    .iter()
+                    .enumerate()
+                    .filter(|(idx, ws)| {
+                        !assigned[*idx] && index_map.get(ws.id.as_str()) == Some(idx)
+                    })
+                    .map(|(idx, ws)| (ws.id.as_str(), idx))
+                    .collect();
+                match tab.unassigned_order {
+                    UnassignedOrder::ByName => extras.sort_by(|a, b| a.0.cmp(b.0)),
+                    // `extras` is collected in config order already.
+                    UnassignedOrder::ByConfigOrder => {}
+                    UnassignedOrder::ByModified => {
+                        let modified = |idx: usize| self.worksets[idx].modified;
+                        // `None` sorts first, so reversing puts never-modified worksets last.
+                        extras.sort_by(|a, b| {
+                            modified(b.1).cmp(&modified(a.1)).then_with(|| a.0.cmp(b.0))
+                        });
+                    }
+                    UnassignedOrder::ByLastRun => {
+                        let last_run = |id: &str| last_run.get(id);
+                        extras.sort_by(|a, b| {
+                            last_run(b.0).cmp(&last_run(a.0)).then_with(|| a.0.cmp(b.0))
+                        });
+                    }
+                }
+                for (_, idx) in extras {
+                    indices.push(idx);
+                    assigned[idx] = true;
+                }
+            }
+            tabs.push(indices);
+        }
+        (tabs, warnings)
+    }
+
+    /// Worksets shown in the given tab, resolved like the home screen's tab bar.
+    pub fn worksets_in_tab(
+        &self,
+        tab_id: &str,
+        last_run: &BTreeMap<String, SystemTime>,
+    ) -> Result<Vec<&Workset>> {
+        let Some(pos) = self.tabs.iter().position(|t| t.id == tab_id) else {
+            bail!("tab not found: {tab_id}");
+        };
+        let (mut tabs, _) = self.resolve_tabs(last_run);
+        Ok(tabs
+            .swap_remove(pos)
+            .into_iter()
+            .map(|idx| &self.worksets[idx])
+            .collect())
+    }
+
+    /// Worksets marked `startup`, in config order.
+    pub fn startup_worksets(&self) -> Vec<&Workset> {
+        self.worksets.iter().filter(|ws| ws.startup).collect()
+    }
+
+    /// Tab a workset belongs to: the tab that shows it per [`AppConfig::resolve_tabs`].
+    pub fn owning_tab(&self, workset_id: &str) -> Option<&TabConfig> {
+        let idx = self.worksets.iter().rposition(|ws| ws.id == workset_id)?;
+        let (tabs, _) = self.resolve_tabs(&BTreeMap::new());
+        let pos = tabs.iter().position(|indices| indices.contains(&idx))?;
+        Some(&self.tabs[pos])
+    }
+
+    /// Cleanup precedence: the workset's own `cleanup`, then its tab's `default_cleanup`,
+    /// then [`CleanupMode::Ask`].
+    pub fn cleanup_mode_for(&self, ws: &Workset) -> CleanupMode {
+        ws.cleanup
+            .or_else(|| self.owning_tab(&ws.id).and_then(|t| t.default_cleanup))
+            .unwrap_or_default()
+    }
+
+    /// How long status messages stay up; `None` when `message_timeout_secs = 0`.
+    pub fn message_timeout(&self) -> Option<Duration> {
+        (self.message_timeout_secs > 0).then(|| Duration::from_secs(self.message_timeout_secs))
+    }
+
+    /// `ratio_step`, or the default when unset or not a positive number.
+    pub fn ratio_step(&self) -> f32 {
+        self.ratio_step
+            .filter(|step| step.is_finite() && *step > 0.0)
+            .unwrap_or(DEFAULT_RATIO_STEP)
+    }
+
+    /// `split_hit_slop` capped at the maximum, or the default when unset.
+    pub fn split_hit_slop(&self) -> u16 {
+        self.split_hit_slop
+            .map(|slop| slop.min(MAX_SPLIT_HIT_SLOP))
+            .unwrap_or(DEFAULT_SPLIT_HIT_SLOP)
+    }
+
+    /// Slot count above which a launch asks first; `None` when the warning is turned off.
+    pub fn slot_warning_threshold(&self) -> Option<usize> {
+        match self.slot_warning_threshold {
+            Some(0) => None,
+            Some(threshold) => Some(threshold),
+            None => Some(DEFAULT_SLOT_WARNING_THRESHOLD),
+        }
+    }
+
+    /// `highlight_symbol`, or the default when unset, empty, too wide or holding control
+    /// characters.
+    pub fn highlight_symbol(&self) -> &str {
+        self.highlight_symbol
+            .as_deref()
+            .filter(|symbol| valid_highlight_symbol(symbol))
+            .unwrap_or(DEFAULT_HIGHLIGHT_SYMBOL)
+    }
+
+    /// Selected-row `(foreground, background)`; unparsable colors fall back to black on cyan.
+    pub fn selection_colors(&self) -> (Color, Color) {
+        let parse = |value: &Option<String>, fallback| {
+            value
+                .as_deref()
+                .and_then(|v| v.trim().parse().ok())
+                .unwrap_or(fallback)
+        };
+        (
+            parse(&self.selection_fg, Color::Black),
+            parse(&self.selection_bg, Color::Cyan),
+        )
+    }
+
+    /// Columns to show in the home table. Unknown names are skipped (warned about on load);
+    /// an empty or fully invalid list falls back to the default set.
+    pub fn list_columns(&self) -> Vec<ListColumn> {
+        let mut columns: Vec<ListColumn> = Vec::new();
+        for column in self.columns.iter().filter_map(|c| ListColumn::from_name(c)) {
+            if !columns.contains(&column) {
+                columns.push(column);
+            }
+        }
+        if columns.is_empty() {
+            DEFAULT_LIST_COLUMNS.to_vec()
+        } else {
+            columns
+        }
+    }
+
+    /// Tabs and the home list index worksets by id, so a duplicate would make rows collide.
+    fn validate_worksets(&self) -> Result<()> {
+        let mut ids = HashSet::new();
+        for ws in &self.worksets {
+            if !ids.insert(ws.id.as_str()) {
+                bail!("duplicate workset id found: {}", ws.id);
+            }
+        }
+        Ok(())
+    }
+
+    /// Fills `commands` of worksets that keep them in a `commands_file`. Saving writes the
+    /// file reference back, not the commands read from it.
+    fn read_commands_files(&mut self, config_dir: &Path) -> Result<()> {
+        for ws in &mut self.worksets {
+            let Some(file) = &ws.commands_file else {
+                continue;
+            };
+            if !ws.commands.is_empty() {
+                bail!(
+                    "workset {}: commands_file and commands are mutually exclusive",
+                    ws.id
+                );
+            }
+            let file = config_dir.join(file);
+            let raw = fs::read_to_string(&file).with_context(|| {
+                format!(
+                    "failed to read commands_file of workset {}: {}",
+                    ws.id,
+                    file.display()
+                )
+            })?;
+            ws.commands = raw
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .map(str::to_string)
+                .collect();
+        }
+        Ok(())
+    }
+
     fn validate_tabs(&self) -> Result<()> {
         let mut ids = HashSet::new();
         for tab in &self.tabs {
@@ -194,28 +961,786 @@ impl AppConfig {
         Ok(())
     }
 
-    fn warn_tab_version(&self) {
+    fn warn_config(&self) {
         if self.version <= 1 && !self.tabs.is_empty() {
             eprintln!("warning: config version <=1 with [[tab]] entries; enabling tab feature");
         }
-        if matches!(self.show_all_tab, Some(false)) {
-            eprintln!("warning: show_all_tab is deprecated and ignored (All tab is always shown)");
+        for warning in KeyMap::from_config(&self.keys).1 {
+            eprintln!("warning: {warning}");
         }
-        if self.all_tab_position.is_some() {
+        for column in &self.columns {
+            if ListColumn::from_name(column).is_none() {
+                eprintln!(
+                    "warning: unknown column '{column}' ignored (expected: {})",
+                    ListColumn::ALL.map(ListColumn::name).join(", ")
+                );
+            }
+        }
+        if let Some(step) = self.ratio_step
+            && !(step.is_finite() && step > 0.0)
+        {
+            eprintln!("warning: ratio_step must be a positive number; using {DEFAULT_RATIO_STEP}");
+        }
+        if let Some(slop) = self.split_hit_slop
+            && slop > MAX_SPLIT_HIT_SLOP
+        {
+            eprintln!(
+                "warning: split_hit_slop is at most {MAX_SPLIT_HIT_SLOP}; using {MAX_SPLIT_HIT_SLOP}"
+            );
+        }
+        if let Some(symbol) = &self.highlight_symbol
+            && !valid_highlight_symbol(symbol)
+        {
             eprintln!(
-                "warning: all_tab_position is deprecated and ignored (All tab is always last)"
+                "warning: highlight_symbol must be 1-{MAX_HIGHLIGHT_SYMBOL_WIDTH} printable cells; using {DEFAULT_HIGHLIGHT_SYMBOL:?}"
             );
         }
+        for (key, value) in [
+            ("selection_fg", &self.selection_fg),
+            ("selection_bg", &self.selection_bg),
+        ] {
+            if let Some(value) = value
+                && value.trim().parse::<Color>().is_err()
+            {
+                eprintln!("warning: unknown color '{value}' for {key}; using the default");
+            }
+        }
+        for ws in &self.worksets {
+            let Some(layout) = &ws.layout else {
+                continue;
+            };
+            let duplicates = layout.duplicate_slot_ids();
+            if !duplicates.is_empty() {
+                let ids: Vec<String> = duplicates.iter().map(u32::to_string).collect();
+                eprintln!(
+                    "warning: workset '{}' layout reuses slot id(s) {}; only the first slot with each id launches (open it in the editor to renumber)",
+                    ws.id,
+                    ids.join(", ")
+                );
+            }
+        }
+        for ws in self.worksets.iter().filter(|ws| ws.commands_out_of_sync()) {
+            eprintln!(
+                "warning: workset '{}' lists commands that differ from its layout; only the layout is launched (save it in the editor to resync)",
+                ws.id
+            );
+        }
+        if matches!(self.show_all_tab, Some(false)) {
+            eprintln!("warning: show_all_tab is deprecated and ignored (All tab is always shown)");
+        }
+    }
+}
+
+/// Renames workset `old` to `new` and rewrites every tab entry that pointed at `old`, so no
+/// tab is left referencing a missing id. Fails without changing anything when `old` is
+/// missing or `new` is empty or already taken.
+pub fn rename_workset_id(cfg: &mut AppConfig, old: &str, new: &str) -> Result<()> {
+    if new.trim().is_empty() {
+        bail!("new workset id must not be empty");
+    }
+    let Some(idx) = cfg.worksets.iter().position(|ws| ws.id == old) else {
+        bail!("workset not found: {old}");
+    };
+    if old == new {
+        return Ok(());
+    }
+    if cfg.worksets.iter().any(|ws| ws.id == new) {
+        bail!("workset id already exists: {new}");
+    }
+    cfg.worksets[idx].id = new.to_string();
+    cfg.worksets[idx].touch();
+    for tab in &mut cfg.tabs {
+        for id in tab.worksets.iter_mut().filter(|id| id.as_str() == old) {
+            *id = new.to_string();
+        }
     }
+    Ok(())
+}
+
+/// Moves command `from` of workset `id` to position `to` (both 1-based), shifting the ones
+/// in between. Only plain `commands` lists can be reordered this way: layout slots belong to
+/// the editor and a `commands_file` is the file's to order.
+pub fn move_workset_command(cfg: &mut AppConfig, id: &str, from: usize, to: usize) -> Result<()> {
+    let Some(ws) = cfg.worksets.iter_mut().find(|ws| ws.id == id) else {
+        bail!("workset not found: {id}");
+    };
+    if ws.layout.is_some() {
+        bail!("workset '{id}' uses a layout; reorder its slots with `hyprsets edit {id}`");
+    }
+    if let Some(file) = &ws.commands_file {
+        bail!(
+            "workset '{id}' reads its commands from {}; reorder them there",
+            file.display()
+        );
+    }
+    let len = ws.commands.len();
+    for (name, index) in [("from", from), ("to", to)] {
+        if !(1..=len).contains(&index) {
+            bail!("{name} index {index} is out of range: workset '{id}' has {len} command(s)");
+        }
+    }
+    let command = ws.commands.remove(from - 1);
+    ws.commands.insert(to - 1, command);
+    ws.touch();
+    Ok(())
 }
 
 fn default_version() -> u32 {
     1
 }
 
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn is_true(value: &bool) -> bool {
+    *value
+}
+
+/// `Option<SystemTime>` as whole Unix seconds, which TOML can hold as a plain integer.
+pub(crate) mod unix_seconds {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    fn to_secs(time: &SystemTime) -> u64 {
+        time.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs())
+    }
+
+    fn from_secs(secs: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(secs)
+    }
+
+    pub fn serialize<S: Serializer>(time: &Option<SystemTime>, ser: S) -> Result<S::Ok, S::Error> {
+        match time {
+            Some(time) => ser.serialize_u64(to_secs(time)),
+            None => ser.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(de: D) -> Result<Option<SystemTime>, D::Error> {
+        Ok(Option::<u64>::deserialize(de)?.map(from_secs))
+    }
+
+    /// The same encoding for the values of a map keyed by workset id.
+    pub(crate) mod map {
+        use serde::{Deserialize, Deserializer, Serializer};
+        use std::{collections::BTreeMap, time::SystemTime};
+
+        pub fn serialize<S: Serializer>(
+            map: &BTreeMap<String, SystemTime>,
+            ser: S,
+        ) -> Result<S::Ok, S::Error> {
+            ser.collect_map(map.iter().map(|(id, time)| (id, super::to_secs(time))))
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(
+            de: D,
+        ) -> Result<BTreeMap<String, SystemTime>, D::Error> {
+            Ok(BTreeMap::<String, u64>::deserialize(de)?
+                .into_iter()
+                .map(|(id, secs)| (id, super::from_secs(secs)))
+                .collect())
+        }
+    }
+}
+
+pub const DEFAULT_MESSAGE_TIMEOUT_SECS: u64 = 4;
+pub const DEFAULT_RATIO_STEP: f32 = 0.1;
+pub const DEFAULT_SPLIT_HIT_SLOP: u16 = 1;
+const MAX_SPLIT_HIT_SLOP: u16 = 3;
+pub const DEFAULT_SLOT_WARNING_THRESHOLD: usize = 6;
+pub const DEFAULT_HIGHLIGHT_SYMBOL: &str = "> ";
+const MAX_HIGHLIGHT_SYMBOL_WIDTH: usize = 4;
+
+fn valid_highlight_symbol(symbol: &str) -> bool {
+    let width = unicode_width::UnicodeWidthStr::width(symbol);
+    !symbol.chars().any(char::is_control) && (1..=MAX_HIGHLIGHT_SYMBOL_WIDTH).contains(&width)
+}
+
+fn default_message_timeout_secs() -> u64 {
+    DEFAULT_MESSAGE_TIMEOUT_SECS
+}
+
+fn is_default_message_timeout_secs(value: &u64) -> bool {
+    *value == DEFAULT_MESSAGE_TIMEOUT_SECS
+}
+
 pub fn default_config_path() -> PathBuf {
     let home = std::env::var("HOME")
         .map(PathBuf::from)
         .unwrap_or_else(|_| PathBuf::from("."));
     home.join(".config").join("hyprsets").join("hyprsets.toml")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn workset(id: &str) -> Workset {
+        Workset {
+            id: id.to_string(),
+            name: id.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn tab(id: &str, worksets: &[&str], include_unassigned: bool) -> TabConfig {
+        TabConfig {
+            id: id.to_string(),
+            label: id.to_string(),
+            worksets: worksets.iter().map(|s| s.to_string()).collect(),
+            include_unassigned,
+            pinned: false,
+            default_cleanup: None,
+            unassigned_order: Default::default(),
+        }
+    }
+
+    #[test]
+    fn worksets_in_tab_follows_tab_order_and_unassigned() {
+        let cfg = AppConfig {
+            version: 2,
+            tabs: vec![tab("a", &["w3", "w1"], false), tab("b", &[], true)],
+            worksets: vec![workset("w1"), workset("w3"), workset("w2"), workset("w0")],
+            ..Default::default()
+        };
+
+        let ids = |tab_id: &str| -> Vec<String> {
+            cfg.worksets_in_tab(tab_id, &BTreeMap::new())
+                .unwrap()
+                .iter()
+                .map(|ws| ws.id.clone())
+                .collect()
+        };
+        assert_eq!(ids("a"), vec!["w3", "w1"]);
+        assert_eq!(ids("b"), vec!["w0", "w2"]);
+        assert!(cfg.worksets_in_tab("missing", &BTreeMap::new()).is_err());
+    }
+
+    #[test]
+    fn worksets_in_tab_matches_ownership_with_two_unassigned_tabs_and_a_duplicate_id() {
+        let mut second_w1 = workset("w1");
+        second_w1.name = "Second w1".into();
+        let cfg = AppConfig {
+            version: 2,
+            tabs: vec![
+                tab("a", &["w1", "w2"], false),
+                tab("rest", &[], true),
+                tab("more", &["w2", "w4"], true),
+            ],
+            worksets: vec![
+                workset("w1"),
+                workset("w2"),
+                workset("w3"),
+                second_w1,
+                workset("w4"),
+            ],
+            ..Default::default()
+        };
+
+        let ids = |tab_id: &str| -> Vec<&str> {
+            cfg.worksets_in_tab(tab_id, &BTreeMap::new())
+                .unwrap()
+                .iter()
+                .map(|ws| ws.name.as_str())
+                .collect()
+        };
+        // The later `w1` is the one reachable by id; the first copy and the already claimed
+        // `w2` stay out of every tab, and only the first absorbing tab takes the extras.
+        assert_eq!(ids("a"), vec!["Second w1", "w2"]);
+        assert_eq!(ids("rest"), vec!["w3", "w4"]);
+        assert!(ids("more").is_empty());
+        let (_, warnings) = cfg.resolve_tabs(&BTreeMap::new());
+        assert_eq!(
+            warnings,
+            vec![
+                "workset 'w2' already assigned to another tab; skipped in 'more'",
+                "workset 'w4' already assigned to another tab; skipped in 'more'",
+            ]
+        );
+
+        let owner = |id: &str| cfg.owning_tab(id).map(|tab| tab.id.as_str());
+        assert_eq!(owner("w1"), Some("a"));
+        assert_eq!(owner("w4"), Some("rest"));
+        assert_eq!(owner("missing"), None);
+    }
+
+    #[test]
+    fn suppress_animations_defaults_off_and_is_omitted_when_false() {
+        let mut ws = workset("w1");
+        let raw = toml::to_string(&ws).unwrap();
+        assert!(!raw.contains("suppress_animations"));
+
+        ws.suppress_animations = true;
+        let raw = toml::to_string(&ws).unwrap();
+        let parsed: Workset = toml::from_str(&raw).unwrap();
+        assert!(parsed.suppress_animations);
+    }
+
+    #[test]
+    fn list_columns_follow_config_order_and_fall_back_to_defaults() {
+        let mut cfg = AppConfig::default_template();
+        assert_eq!(cfg.list_columns(), DEFAULT_LIST_COLUMNS.to_vec());
+
+        cfg.columns = vec![
+            "Workspace".into(),
+            "bogus".into(),
+            "desc".into(),
+            "workspace".into(),
+        ];
+        assert_eq!(
+            cfg.list_columns(),
+            vec![ListColumn::Workspace, ListColumn::Description]
+        );
+
+        cfg.columns = vec!["bogus".into()];
+        assert_eq!(cfg.list_columns(), DEFAULT_LIST_COLUMNS.to_vec());
+
+        for column in ListColumn::ALL {
+            assert_eq!(
+                ListColumn::from_name(&column.name().to_uppercase()),
+                Some(column)
+            );
+        }
+    }
+
+    #[test]
+    fn primary_label_parses_and_rejects_unknown_values() {
+        let base = toml::to_string(&AppConfig::default_template()).unwrap();
+        let cfg: AppConfig = toml::from_str(&base).unwrap();
+        assert_eq!(cfg.primary_label, PrimaryLabel::Name);
+        assert!(!base.contains("primary_label"));
+
+        let cfg: AppConfig = toml::from_str(&format!("primary_label = \"id\"\n{base}")).unwrap();
+        assert_eq!(cfg.primary_label, PrimaryLabel::Id);
+        let ws = &cfg.worksets[0];
+        assert_eq!(
+            PrimaryLabel::Id.describe(ws),
+            format!("{} ({})", ws.id, ws.name)
+        );
+        assert_eq!(PrimaryLabel::Name.describe(ws), ws.name);
+
+        let err =
+            toml::from_str::<AppConfig>(&format!("primary_label = \"slug\"\n{base}")).unwrap_err();
+        assert!(err.to_string().contains("unknown variant"), "{err}");
+    }
+
+    #[test]
+    fn cleanup_mode_prefers_workset_then_tab_then_ask() {
+        let mut work = tab("work", &["w1", "w2"], false);
+        work.default_cleanup = Some(CleanupMode::Close);
+        let mut scratch = tab("scratch", &[], true);
+        scratch.default_cleanup = Some(CleanupMode::Keep);
+        let mut w2 = workset("w2");
+        w2.cleanup = Some(CleanupMode::Ask);
+        let cfg = AppConfig {
+            tabs: vec![work, scratch],
+            worksets: vec![workset("w1"), w2, workset("w3")],
+            ..AppConfig::default_template()
+        };
+
+        assert_eq!(cfg.cleanup_mode_for(&cfg.worksets[0]), CleanupMode::Close);
+        assert_eq!(cfg.cleanup_mode_for(&cfg.worksets[1]), CleanupMode::Ask);
+        assert_eq!(cfg.cleanup_mode_for(&cfg.worksets[2]), CleanupMode::Keep);
+
+        let untabbed = AppConfig {
+            tabs: Vec::new(),
+            ..cfg.clone()
+        };
+        assert_eq!(untabbed.cleanup_mode_for(&workset("w1")), CleanupMode::Ask);
+    }
+
+    #[test]
+    fn load_returns_none_without_creating_a_file() {
+        let dir = std::env::temp_dir().join(format!("hyprsets_test_load_{}", std::process::id()));
+        let path = dir.join("missing.toml");
+        assert!(AppConfig::load(&path).unwrap().is_none());
+        assert!(!path.exists());
+
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(&path, "  \n").unwrap();
+        assert!(AppConfig::load(&path).unwrap().is_none());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "  \n");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn repair_on_load_drops_dangling_tab_refs_and_saves_once() {
+        let dir = std::env::temp_dir().join(format!("hyprsets_test_repair_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("config.toml");
+        let raw = r#"
+[[tab]]
+id = "main"
+label = "Main"
+worksets = ["gone", "dev", "also-gone", "notes"]
+
+[[workset]]
+id = "dev"
+name = "Dev"
+desc = ""
+
+[[workset]]
+id = "notes"
+name = "Notes"
+desc = ""
+"#;
+        fs::write(&path, raw).unwrap();
+        let cfg = AppConfig::load_or_init(&path).unwrap();
+        assert_eq!(cfg.tabs[0].worksets.len(), 4, "repair is opt-in");
+        assert_eq!(fs::read_to_string(&path).unwrap(), raw);
+
+        fs::write(&path, format!("repair_on_load = true\n{raw}")).unwrap();
+        let cfg = AppConfig::load_or_init(&path).unwrap();
+        assert_eq!(cfg.tabs[0].worksets, vec!["dev", "notes"]);
+        let saved = AppConfig::load(&path).unwrap().unwrap();
+        assert_eq!(saved.tabs[0].worksets, vec!["dev", "notes"]);
+        assert!(saved.repair_on_load);
+
+        let written = fs::read_to_string(&path).unwrap();
+        AppConfig::load_or_init(&path).unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            written,
+            "clean configs aren't rewritten"
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn load_rejects_duplicate_workset_ids() {
+        let dir = std::env::temp_dir().join(format!("hyprsets_test_dup_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("config.toml");
+        fs::write(
+            &path,
+            r#"
+[[workset]]
+id = "dev"
+name = "Dev"
+desc = ""
+commands = []
+
+[[workset]]
+id = "dev"
+name = "Dev again"
+desc = ""
+commands = []
+"#,
+        )
+        .unwrap();
+
+        let err = AppConfig::load_or_init(&path).unwrap_err();
+        assert_eq!(err.to_string(), "duplicate workset id found: dev");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn commands_file_fills_commands_relative_to_the_config() {
+        let dir = std::env::temp_dir().join(format!(
+            "hyprsets_test_commands_file_{}",
+            std::process::id()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("config.toml");
+        fs::write(
+            dir.join("dev.sh"),
+            "# editor first\nkitty nvim\n\n  firefox  \n",
+        )
+        .unwrap();
+        fs::write(
+            &path,
+            r#"
+[[workset]]
+id = "dev"
+name = "Dev"
+desc = ""
+commands_file = "dev.sh"
+"#,
+        )
+        .unwrap();
+
+        let cfg = AppConfig::load(&path).unwrap().unwrap();
+        assert_eq!(cfg.worksets[0].commands, vec!["kitty nvim", "firefox"]);
+        cfg.save(&path).unwrap();
+        let saved = fs::read_to_string(&path).unwrap();
+        assert!(saved.contains("commands_file = \"dev.sh\""));
+        assert!(!saved.contains("kitty nvim"));
+        assert_eq!(
+            AppConfig::load(&path).unwrap().unwrap().worksets[0].commands,
+            vec!["kitty nvim", "firefox"]
+        );
+
+        fs::write(
+            &path,
+            r#"
+[[workset]]
+id = "dev"
+name = "Dev"
+desc = ""
+commands = ["kitty"]
+commands_file = "dev.sh"
+"#,
+        )
+        .unwrap();
+        let err = AppConfig::load(&path).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "workset dev: commands_file and commands are mutually exclusive"
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn switch_focus_defaults_to_true() {
+        let parse = |extra: &str| -> Workset {
+            toml::from_str(&format!(
+                "id = \"w\"\nname = \"W\"\ndesc = \"\"\ncommands = []\n{extra}"
+            ))
+            .unwrap()
+        };
+        assert!(parse("").switch_focus);
+        let background = parse("switch_focus = false");
+        assert!(!background.switch_focus);
+        assert!(
+            toml::to_string(&background)
+                .unwrap()
+                .contains("switch_focus = false")
+        );
+    }
+
+    #[test]
+    fn from_template_deep_copies_layout() {
+        let mut tpl = AppConfig::default_template().worksets.remove(0);
+        tpl.template = true;
+        tpl.env.insert("EDITOR".into(), "nvim".into());
+
+        let mut copy = Workset::from_template(&tpl, "fresh", "Fresh", "");
+        assert_eq!(copy.id, "fresh");
+        assert!(!copy.template);
+        assert_eq!(copy.env, tpl.env);
+
+        let Some(LayoutNode::Split(split)) = copy.layout.as_mut() else {
+            panic!("template layout should be a split");
+        };
+        let LayoutNode::Leaf(slot) = split.left.as_mut() else {
+            panic!("left side should be a leaf");
+        };
+        slot.slot_id = 42;
+        slot.command = "changed".into();
+
+        let Some(LayoutNode::Split(original)) = tpl.layout.as_ref() else {
+            panic!("template layout should be a split");
+        };
+        let LayoutNode::Leaf(original_slot) = original.left.as_ref() else {
+            panic!("left side should be a leaf");
+        };
+        assert_eq!(original_slot.slot_id, 1);
+        assert_ne!(original_slot.command, "changed");
+    }
+
+    #[test]
+    fn ratio_step_falls_back_for_invalid_values() {
+        let mut cfg = AppConfig::default_template();
+        assert_eq!(cfg.ratio_step(), DEFAULT_RATIO_STEP);
+        cfg.ratio_step = Some(0.25);
+        assert_eq!(cfg.ratio_step(), 0.25);
+        cfg.ratio_step = Some(-1.0);
+        assert_eq!(cfg.ratio_step(), DEFAULT_RATIO_STEP);
+        cfg.ratio_step = Some(f32::NAN);
+        assert_eq!(cfg.ratio_step(), DEFAULT_RATIO_STEP);
+        assert_eq!(cfg.split_hit_slop(), DEFAULT_SPLIT_HIT_SLOP);
+        cfg.split_hit_slop = Some(0);
+        assert_eq!(cfg.split_hit_slop(), 0);
+        cfg.split_hit_slop = Some(9);
+        assert_eq!(cfg.split_hit_slop(), MAX_SPLIT_HIT_SLOP);
+        assert_eq!(
+            cfg.slot_warning_threshold(),
+            Some(DEFAULT_SLOT_WARNING_THRESHOLD)
+        );
+        cfg.slot_warning_threshold = Some(0);
+        assert_eq!(cfg.slot_warning_threshold(), None);
+        cfg.slot_warning_threshold = Some(10);
+        assert_eq!(cfg.slot_warning_threshold(), Some(10));
+    }
+
+    #[test]
+    fn selection_settings_fall_back_when_invalid() {
+        let mut cfg = AppConfig::default_template();
+        assert_eq!(cfg.highlight_symbol(), DEFAULT_HIGHLIGHT_SYMBOL);
+        assert_eq!(cfg.selection_colors(), (Color::Black, Color::Cyan));
+
+        cfg.highlight_symbol = Some("▶ ".into());
+        cfg.selection_fg = Some("#ff0000".into());
+        cfg.selection_bg = Some("not-a-color".into());
+        assert_eq!(cfg.highlight_symbol(), "▶ ");
+        assert_eq!(cfg.selection_colors(), (Color::Rgb(255, 0, 0), Color::Cyan));
+
+        for bad in ["", "\t", "-----> "] {
+            cfg.highlight_symbol = Some(bad.into());
+            assert_eq!(cfg.highlight_symbol(), DEFAULT_HIGHLIGHT_SYMBOL, "{bad:?}");
+        }
+    }
+
+    #[test]
+    fn rename_workset_id_updates_tab_references() {
+        let mut cfg = AppConfig::default_template();
+        cfg.worksets = vec![workset("a"), workset("b")];
+        cfg.tabs = vec![TabConfig {
+            label: "T".into(),
+            ..tab("t", &["b", "a"], false)
+        }];
+
+        rename_workset_id(&mut cfg, "a", "c").unwrap();
+        assert_eq!(cfg.worksets[0].id, "c");
+        assert_eq!(cfg.tabs[0].worksets, vec!["b", "c"]);
+
+        let err = rename_workset_id(&mut cfg, "c", "b").unwrap_err();
+        assert!(err.to_string().contains("already exists"), "{err}");
+        assert!(rename_workset_id(&mut cfg, "missing", "d").is_err());
+        assert!(rename_workset_id(&mut cfg, "c", "  ").is_err());
+        assert_eq!(cfg.tabs[0].worksets, vec!["b", "c"]);
+    }
+
+    #[test]
+    fn move_workset_command_shifts_commands_and_validates_indices() {
+        let mut cfg = AppConfig::default_template();
+        let mut ws = workset("a");
+        ws.commands = vec!["one".into(), "two".into(), "three".into(), "four".into()];
+        cfg.worksets = vec![ws];
+
+        move_workset_command(&mut cfg, "a", 4, 1).unwrap();
+        assert_eq!(cfg.worksets[0].commands, ["four", "one", "two", "three"]);
+        move_workset_command(&mut cfg, "a", 1, 3).unwrap();
+        assert_eq!(cfg.worksets[0].commands, ["one", "two", "four", "three"]);
+        assert!(cfg.worksets[0].modified.is_some());
+
+        let err = move_workset_command(&mut cfg, "a", 0, 2).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "from index 0 is out of range: workset 'a' has 4 command(s)"
+        );
+        assert!(move_workset_command(&mut cfg, "a", 2, 5).is_err());
+        assert!(move_workset_command(&mut cfg, "missing", 1, 2).is_err());
+        assert_eq!(cfg.worksets[0].commands, ["one", "two", "four", "three"]);
+
+        cfg.worksets[0].layout = Some(LayoutNode::Leaf(WindowSlot::new(1, "one")));
+        let err = move_workset_command(&mut cfg, "a", 1, 2).unwrap_err();
+        assert!(err.to_string().contains("hyprsets edit a"), "{err}");
+    }
+
+    #[test]
+    fn startup_worksets_keep_config_order() {
+        let mut cfg = AppConfig::default_template();
+        cfg.worksets = ["c", "a", "b"].into_iter().map(workset).collect();
+        cfg.worksets[0].startup = true;
+        cfg.worksets[2].startup = true;
+        let ids: Vec<&str> = cfg
+            .startup_worksets()
+            .iter()
+            .map(|ws| ws.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["c", "b"]);
+    }
+
+    #[test]
+    fn effective_commands_fall_back_to_layout_leaves() {
+        let mut cfg = AppConfig::default_template();
+        let mut ws = cfg.worksets.remove(0);
+        let mut leaves = Vec::new();
+        ws.layout.as_ref().unwrap().collect_commands(&mut leaves);
+        assert!(!leaves.is_empty());
+
+        ws.commands.clear();
+        assert_eq!(ws.effective_commands(), leaves);
+
+        ws.commands = vec!["listed".into()];
+        assert_eq!(ws.effective_commands(), vec!["listed".to_string()]);
+
+        ws.commands.clear();
+        ws.layout = None;
+        assert!(ws.effective_commands().is_empty());
+    }
+
+    #[test]
+    fn commands_out_of_sync_compares_against_layout() {
+        let mut cfg = AppConfig::default_template();
+        let mut ws = cfg.worksets.remove(0);
+        let mut from_layout = Vec::new();
+        ws.layout
+            .as_ref()
+            .unwrap()
+            .collect_commands(&mut from_layout);
+        ws.commands = from_layout.iter().rev().cloned().collect();
+        assert!(!ws.commands_out_of_sync(), "order alone is not a mismatch");
+
+        ws.commands.push("extra".into());
+        assert!(ws.commands_out_of_sync());
+        ws.commands.clear();
+        assert!(!ws.commands_out_of_sync());
+        ws.commands = vec!["a".into()];
+        ws.layout = None;
+        assert!(!ws.commands_out_of_sync());
+    }
+
+    #[test]
+    fn duplicate_slot_ids_are_found_and_renumbered() {
+        let mut cfg = AppConfig::default_template();
+        let mut layout = cfg.worksets.remove(0).layout.unwrap();
+        assert!(layout.duplicate_slot_ids().is_empty());
+        let LayoutNode::Split(split) = &mut layout else {
+            panic!("template layout should be a split");
+        };
+        let LayoutNode::Leaf(right) = split.right.as_mut() else {
+            panic!("template right side should be a slot");
+        };
+        right.slot_id = 1;
+
+        let mut ids = Vec::new();
+        layout.collect_slot_ids_list(&mut ids);
+        assert_eq!(ids, vec![1, 1]);
+        assert_eq!(layout.duplicate_slot_ids(), vec![1]);
+
+        layout.renumber_slots();
+        let mut ids = Vec::new();
+        layout.collect_slot_ids_list(&mut ids);
+        assert_eq!(ids, vec![1, 2]);
+        assert!(layout.duplicate_slot_ids().is_empty());
+    }
+
+    #[test]
+    fn linear_layouts_have_splits_in_one_direction_only() {
+        let layout = AppConfig::default_template()
+            .worksets
+            .remove(0)
+            .layout
+            .unwrap();
+        let LayoutNode::Split(template) = &layout else {
+            panic!("template layout should be a split");
+        };
+        let leaf = template.left.as_ref().clone();
+        let split = |direction, left: LayoutNode, right: LayoutNode| {
+            LayoutNode::Split(SplitNode {
+                direction,
+                ratio: 1.0,
+                left: Box::new(left),
+                right: Box::new(right),
+            })
+        };
+        let (h, v) = (SplitDirection::Horizontal, SplitDirection::Vertical);
+
+        assert!(leaf.is_linear());
+        assert!(layout.is_linear());
+        // A row of three, nested either way round.
+        assert!(split(h, leaf.clone(), split(h, leaf.clone(), leaf.clone())).is_linear());
+        assert!(split(h, split(h, leaf.clone(), leaf.clone()), leaf.clone()).is_linear());
+        assert!(split(v, split(v, leaf.clone(), leaf.clone()), leaf.clone()).is_linear());
+        // One column next to a slot is a real grid.
+        assert!(!split(h, leaf.clone(), split(v, leaf.clone(), leaf.clone())).is_linear());
+        assert!(!split(v, split(h, leaf.clone(), leaf.clone()), leaf).is_linear());
+    }
+}
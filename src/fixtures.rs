@@ -0,0 +1,30 @@
+//! Test fixtures shared across modules.
+
+use std::path::{Path, PathBuf};
+
+/// A fresh temp dir, removed on drop so it goes even when an assert fails first.
+pub(crate) struct TestDir(PathBuf);
+
+impl TestDir {
+    /// `name` keeps the dirs of concurrent tests apart.
+    pub(crate) fn new(name: &str) -> Self {
+        let dir =
+            Self(std::env::temp_dir().join(format!("hyprsets_test_{name}_{}", std::process::id())));
+        std::fs::create_dir_all(dir.path()).unwrap();
+        dir
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.0
+    }
+
+    pub(crate) fn config_path(&self) -> PathBuf {
+        self.0.join("hyprsets.toml")
+    }
+}
+
+impl Drop for TestDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
@@ -0,0 +1,297 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// Remappable actions on the home list. Arrow keys, `Enter`, `Esc`, `Tab` and digits stay fixed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HomeAction {
+    Quit,
+    Edit,
+    EditCommand,
+    New,
+    NewTab,
+    Clone,
+    Delete,
+    AssignTab,
+    TabMenu,
+    LaunchHere,
+    Dense,
+    Unassigned,
+    Warnings,
+    Undo,
+    RerunLast,
+    OpenCwd,
+    ToggleStartup,
+    JumpToWorkspace,
+    Up,
+    Down,
+    MoveUp,
+    MoveDown,
+}
+
+/// Remappable actions in the layout editor. `Enter`, `Esc`, `Tab` and `F2` stay fixed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditorAction {
+    Quit,
+    Next,
+    Prev,
+    SplitHorizontal,
+    SplitVertical,
+    InsertLeft,
+    InsertRight,
+    InsertUp,
+    InsertDown,
+    RatioUp,
+    RatioDown,
+    Delete,
+    EditCommand,
+    EditWorkset,
+    Swap,
+    ApplyAll,
+    OpenCwd,
+    Zoom,
+    ToggleEnabled,
+    PreviewExec,
+    ListView,
+}
+
+/// `[keys]` action name and default keys for every home action.
+pub const HOME_ACTIONS: &[(HomeAction, &str, &[char])] = &[
+    (HomeAction::Quit, "quit", &['q']),
+    (HomeAction::Edit, "edit", &['e']),
+    (HomeAction::EditCommand, "edit_command", &['E']),
+    (HomeAction::New, "new", &['n']),
+    (HomeAction::NewTab, "new_tab", &['N']),
+    (HomeAction::Clone, "clone", &['c']),
+    (HomeAction::Delete, "delete", &['d']),
+    (HomeAction::AssignTab, "assign_tab", &['a']),
+    (HomeAction::TabMenu, "tab_menu", &['t']),
+    (HomeAction::LaunchHere, "launch_here", &['w']),
+    (HomeAction::Dense, "dense", &['v']),
+    (HomeAction::Unassigned, "unassigned", &['u']),
+    (HomeAction::Warnings, "warnings", &['!']),
+    (HomeAction::Undo, "undo", &['z']),
+    (HomeAction::RerunLast, "rerun_last", &['.']),
+    (HomeAction::OpenCwd, "open_cwd", &['o']),
+    (HomeAction::ToggleStartup, "toggle_startup", &['*']),
+    (HomeAction::JumpToWorkspace, "jump_to_workspace", &['g']),
+    (HomeAction::Up, "up", &['k']),
+    (HomeAction::Down, "down", &['j']),
+    (HomeAction::MoveUp, "move_up", &['K']),
+    (HomeAction::MoveDown, "move_down", &['J']),
+];
+
+/// `[keys]` action name and default keys for every editor action.
+pub const EDITOR_ACTIONS: &[(EditorAction, &str, &[char])] = &[
+    (EditorAction::Quit, "editor_quit", &['q']),
+    (EditorAction::Next, "editor_next", &['l', 'j']),
+    (EditorAction::Prev, "editor_prev", &['k']),
+    (
+        EditorAction::SplitHorizontal,
+        "editor_split_horizontal",
+        &['h'],
+    ),
+    (
+        EditorAction::SplitVertical,
+        "editor_split_vertical",
+        &['v', 's'],
+    ),
+    (EditorAction::InsertLeft, "editor_insert_left", &['H']),
+    (EditorAction::InsertRight, "editor_insert_right", &['L']),
+    (EditorAction::InsertUp, "editor_insert_up", &['K']),
+    (EditorAction::InsertDown, "editor_insert_down", &['J']),
+    (EditorAction::RatioUp, "editor_ratio_up", &['+']),
+    (EditorAction::RatioDown, "editor_ratio_down", &['-']),
+    (EditorAction::Delete, "editor_delete", &['x', 'd', 'D']),
+    (EditorAction::EditCommand, "editor_edit_command", &['c']),
+    (
+        EditorAction::EditWorkset,
+        "editor_edit_workset",
+        &['e', 'E'],
+    ),
+    (EditorAction::Swap, "editor_swap", &['w']),
+    (EditorAction::ApplyAll, "editor_apply_all", &['A']),
+    (EditorAction::OpenCwd, "editor_open_cwd", &['o']),
+    (EditorAction::Zoom, "editor_zoom", &['z']),
+    (EditorAction::ToggleEnabled, "editor_toggle_enabled", &['#']),
+    (EditorAction::PreviewExec, "editor_preview_exec", &['p']),
+    (EditorAction::ListView, "editor_list_view", &['=']),
+];
+
+/// Character key bindings resolved from the `[keys]` config section on top of the defaults.
+#[derive(Debug, Clone)]
+pub struct KeyMap {
+    home: HashMap<char, HomeAction>,
+    editor: HashMap<char, EditorAction>,
+}
+
+impl Default for KeyMap {
+    fn default() -> Self {
+        Self::from_config(&BTreeMap::new()).0
+    }
+}
+
+impl KeyMap {
+    /// Builds the key map and returns warnings for unknown actions, unparsable keys and
+    /// conflicting bindings. A configured key replaces that action's defaults; actions left
+    /// unset keep theirs.
+    pub fn from_config(keys: &BTreeMap<String, String>) -> (Self, Vec<String>) {
+        let mut warnings = Vec::new();
+        for name in keys.keys() {
+            let known = HOME_ACTIONS.iter().any(|(_, n, _)| n == name)
+                || EDITOR_ACTIONS.iter().any(|(_, n, _)| n == name);
+            if !known {
+                warnings.push(format!("unknown key action '{name}' ignored"));
+            }
+        }
+        let home = resolve(HOME_ACTIONS, keys, &mut warnings);
+        let editor = resolve(EDITOR_ACTIONS, keys, &mut warnings);
+        (Self { home, editor }, warnings)
+    }
+
+    pub fn home(&self, key: &KeyEvent) -> Option<HomeAction> {
+        plain_char(key).and_then(|ch| self.home.get(&ch).copied())
+    }
+
+    pub fn editor(&self, key: &KeyEvent) -> Option<EditorAction> {
+        plain_char(key).and_then(|ch| self.editor.get(&ch).copied())
+    }
+}
+
+fn resolve<A: Copy>(
+    table: &[(A, &'static str, &[char])],
+    keys: &BTreeMap<String, String>,
+    warnings: &mut Vec<String>,
+) -> HashMap<char, A> {
+    let mut bound: HashMap<char, (A, &'static str)> = HashMap::new();
+    let mut custom = Vec::new();
+    for (action, name, defaults) in table {
+        let parsed = keys.get(*name).map(|raw| (raw, parse_key(raw)));
+        match parsed {
+            Some((_, Some(ch))) => custom.push((*action, *name, ch)),
+            Some((raw, None)) => {
+                warnings.push(format!(
+                    "key '{raw}' for '{name}' is not a single character; using the default"
+                ));
+                for ch in *defaults {
+                    bound.insert(*ch, (*action, name));
+                }
+            }
+            None => {
+                for ch in *defaults {
+                    bound.insert(*ch, (*action, name));
+                }
+            }
+        }
+    }
+
+    let mut claimed: HashMap<char, &str> = HashMap::new();
+    for (action, name, ch) in custom {
+        if let Some(first) = claimed.get(&ch) {
+            warnings.push(format!(
+                "key '{ch}' is bound to both '{first}' and '{name}'; keeping '{first}'"
+            ));
+            continue;
+        }
+        if let Some((_, previous)) = bound.get(&ch) {
+            warnings.push(format!(
+                "key '{ch}' for '{name}' replaces the default binding of '{previous}'"
+            ));
+        }
+        bound.insert(ch, (action, name));
+        claimed.insert(ch, name);
+    }
+
+    bound
+        .into_iter()
+        .map(|(ch, (action, _))| (ch, action))
+        .collect()
+}
+
+fn parse_key(raw: &str) -> Option<char> {
+    if raw.eq_ignore_ascii_case("space") {
+        return Some(' ');
+    }
+    let mut chars = raw.trim().chars();
+    match (chars.next(), chars.next()) {
+        (Some(ch), None) => Some(ch),
+        _ => None,
+    }
+}
+
+fn plain_char(key: &KeyEvent) -> Option<char> {
+    match key.code {
+        KeyCode::Char(ch)
+            if !key
+                .modifiers
+                .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+        {
+            Some(ch)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(ch: char) -> KeyEvent {
+        KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE)
+    }
+
+    fn keys(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn defaults_apply_when_unset() {
+        let map = KeyMap::default();
+        assert_eq!(map.home(&press('j')), Some(HomeAction::Down));
+        assert_eq!(map.home(&press('J')), Some(HomeAction::MoveDown));
+        assert_eq!(map.home(&press('N')), Some(HomeAction::NewTab));
+        assert_eq!(map.home(&press('z')), Some(HomeAction::Undo));
+        assert_eq!(map.home(&press('*')), Some(HomeAction::ToggleStartup));
+        assert_eq!(map.editor(&press('s')), Some(EditorAction::SplitVertical));
+        assert_eq!(map.editor(&press('#')), Some(EditorAction::ToggleEnabled));
+        assert_eq!(map.editor(&press('p')), Some(EditorAction::PreviewExec));
+        let ctrl_j = KeyEvent::new(KeyCode::Char('j'), KeyModifiers::CONTROL);
+        assert_eq!(map.home(&ctrl_j), None);
+    }
+
+    #[test]
+    fn partial_override_replaces_only_that_action() {
+        let (map, warnings) = KeyMap::from_config(&keys(&[("down", "h"), ("up", "t")]));
+        assert_eq!(map.home(&press('h')), Some(HomeAction::Down));
+        assert_eq!(map.home(&press('j')), None);
+        assert_eq!(map.home(&press('t')), Some(HomeAction::Up));
+        assert_eq!(map.home(&press('a')), Some(HomeAction::AssignTab));
+        assert_eq!(
+            warnings,
+            vec!["key 't' for 'up' replaces the default binding of 'tab_menu'"]
+        );
+    }
+
+    #[test]
+    fn conflicts_and_bad_entries_are_reported() {
+        let (map, warnings) = KeyMap::from_config(&keys(&[
+            ("clone", "x"),
+            ("delete", "x"),
+            ("new", "ctrl+n"),
+            ("teleport", "z"),
+        ]));
+        assert_eq!(map.home(&press('x')), Some(HomeAction::Clone));
+        assert_eq!(map.home(&press('n')), Some(HomeAction::New));
+        assert_eq!(warnings.len(), 3);
+        assert!(warnings[0].contains("teleport"));
+        assert!(warnings.iter().any(|w| w.contains("ctrl+n")));
+        assert!(
+            warnings
+                .iter()
+                .any(|w| w.contains("both 'clone' and 'delete'"))
+        );
+    }
+}
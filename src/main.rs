@@ -1,17 +1,25 @@
 mod config;
+#[cfg(test)]
+mod fixtures;
+mod keymap;
+mod output;
 mod run;
 mod state;
 mod ui;
 
-use anyhow::{Context, Result, anyhow};
+use anyhow::{Context, Result, anyhow, bail};
 use clap::{ArgAction, Parser, Subcommand, ValueHint};
-use config::{AppConfig, Workset, default_config_path};
-use run::run_workset as run_workset_launch;
+use config::{
+    AppConfig, CleanupMode, Workset, default_config_path, move_workset_command, rename_workset_id,
+};
+use output::status;
+use run::{Capture, RunOptions, clean_workset_workspaces, run_workset as run_workset_launch};
+use state::{AppState, default_state_path};
 use std::{
     path::{Path, PathBuf},
     process,
 };
-use ui::editor::{EditorExit, run_editor};
+use ui::editor::{EditorEntry, EditorExit, run_editor};
 use ui::home::{HomeExit, run_home};
 
 #[derive(Debug, Parser)]
@@ -19,7 +27,7 @@ use ui::home::{HomeExit, run_home};
     name = "hyprsets",
     version,
     about = "HyprSets — Launch and edit Hyprland worksets",
-    long_about = "No subcommand: open TUI list. run <id>: launch without TUI. edit <id>: jump into editor.",
+    long_about = "No subcommand: open TUI list. run <id>: launch without TUI. edit <id>: jump into editor. clean: close windows on workset workspaces.",
     after_help = "Config is saved at ~/.config/hyprsets/hyprsets.toml (or the path provided via --config)."
 )]
 struct Cli {
@@ -31,6 +39,18 @@ struct Cli {
     #[arg(short, long, action = ArgAction::Count, global = true)]
     verbose: u8,
 
+    /// Run launch commands directly and report their stderr instead of using Hyprland's exec
+    #[arg(long, global = true)]
+    capture: bool,
+
+    /// Fail instead of writing a starter config when none exists
+    #[arg(long, global = true)]
+    no_init: bool,
+
+    /// Print only errors, warnings and requested output (no progress or exit notice)
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    quiet: bool,
+
     #[command(subcommand)]
     command: Option<Command>,
 }
@@ -38,9 +58,46 @@ struct Cli {
 #[derive(Debug, Subcommand)]
 enum Command {
     /// Run a workset directly without TUI
-    Run { id: String },
+    Run {
+        id: String,
+        /// Launch on this workspace instead of the workset's configured one
+        #[arg(long, value_name = "WORKSPACE")]
+        workspace: Option<String>,
+        /// Only launch layout slots whose `class` has no window on the workspace yet, keeping
+        /// existing windows open
+        #[arg(long)]
+        fill: bool,
+        /// Keep every window already on the workspace, whatever the workset's cleanup mode
+        #[arg(long)]
+        no_cleanup: bool,
+    },
     /// Open editor for a workset id
-    Edit { id: String },
+    Edit {
+        id: String,
+        /// Create an empty workset with this id first (fails if the id is taken)
+        #[arg(long)]
+        create: bool,
+    },
+    /// Close all windows on the workspaces used by worksets (each workspace once)
+    Clean {
+        /// Only clean workspaces of worksets in this tab
+        #[arg(long, value_name = "TAB_ID")]
+        tab: Option<String>,
+    },
+    /// Launch every workset marked `startup = true`, in config order (for `exec-once`)
+    Startup {
+        /// Close existing windows where a workset's cleanup mode is `ask`, instead of keeping them
+        #[arg(long)]
+        yes: bool,
+    },
+    /// Print the hyprctl commands `run <id>` would issue as a shell script
+    Script { id: String },
+    /// Change a workset's id and update every tab that lists it
+    RenameId { old: String, new: String },
+    /// Set the workspace a workset launches on (id, name, name:<n>, special[:<n>] or current)
+    SetWorkspace { id: String, workspace: String },
+    /// Move a command of a command-only workset to another position (1-based)
+    Reorder { id: String, from: usize, to: usize },
     /// Show the hyprsets version and exit
     Version,
 }
@@ -57,6 +114,7 @@ fn main() {
 
 fn try_main() -> Result<()> {
     let cli = Cli::parse();
+    output::set_quiet(cli.quiet);
 
     if matches!(cli.command.as_ref(), Some(Command::Version)) {
         print_version();
@@ -64,25 +122,72 @@ fn try_main() -> Result<()> {
     }
 
     let config_path = cli.config.unwrap_or_else(default_config_path);
-    let app_cfg = AppConfig::load_or_init(&config_path)
-        .with_context(|| format!("failed to load config at {}", config_path.display()))?;
+    let app_cfg = if cli.no_init {
+        AppConfig::load(&config_path)
+            .with_context(|| format!("failed to load config at {}", config_path.display()))?
+            .ok_or_else(|| anyhow!("no config found at {}", config_path.display()))?
+    } else {
+        AppConfig::load_or_init(&config_path)
+            .with_context(|| format!("failed to load config at {}", config_path.display()))?
+    };
 
+    // The script goes to stdout, so keep the exit notice out of it.
+    let script_output = matches!(cli.command, Some(Command::Script { .. }));
     let result = match cli.command {
-        Some(Command::Run { id }) => run_workset(&app_cfg, &id, cli.verbose > 0, false),
-        Some(Command::Edit { id }) => edit_workset(config_path.as_path(), &id),
+        Some(Command::Run {
+            id,
+            workspace,
+            fill,
+            no_cleanup,
+        }) => run_workset(
+            &app_cfg,
+            &id,
+            no_cleanup.then_some(CleanupMode::Keep),
+            &RunOptions {
+                verbose: cli.verbose > 0,
+                workspace,
+                capture: Capture::new(cli.capture, app_cfg.detach_commands),
+                fill,
+                ..RunOptions::default()
+            },
+        ),
+        Some(Command::Edit { id, create }) => {
+            let created = if create {
+                create_workset(config_path.as_path(), &id)
+            } else {
+                Ok(())
+            };
+            created.and_then(|()| edit_workset(config_path.as_path(), &id, EditorEntry::Normal))
+        }
+        Some(Command::Clean { tab }) => clean_workspaces(&app_cfg, tab.as_deref(), cli.verbose > 0),
+        Some(Command::Startup { yes }) => run_startup(&app_cfg, yes, cli.verbose > 0, cli.capture),
+        Some(Command::Script { id }) => print_script(&app_cfg, &id),
+        Some(Command::RenameId { old, new }) => rename_id(config_path.as_path(), &old, &new),
+        Some(Command::SetWorkspace { id, workspace }) => {
+            set_workspace(config_path.as_path(), &id, &workspace)
+        }
+        Some(Command::Reorder { id, from, to }) => {
+            reorder_command(config_path.as_path(), &id, from, to)
+        }
         Some(Command::Version) => unreachable!("version is handled earlier"),
-        None => run_interactive(app_cfg, config_path.as_path(), cli.verbose > 0),
+        None => run_interactive(app_cfg, config_path.as_path(), cli.verbose > 0, cli.capture),
     };
 
     match &result {
-        Ok(_) => println!("hyprsets: exiting (normal)"),
+        Ok(_) if script_output => {}
+        Ok(_) => status!("hyprsets: exiting (normal)"),
         Err(err) => eprintln!("hyprsets: exiting with error: {err}"),
     }
 
     result
 }
 
-fn run_interactive(mut app_cfg: AppConfig, config_path: &Path, verbose: bool) -> Result<()> {
+fn run_interactive(
+    mut app_cfg: AppConfig,
+    config_path: &Path,
+    verbose: bool,
+    capture: bool,
+) -> Result<()> {
     let mut last_selected_id: Option<String> = None;
     loop {
         let initial_selection = last_selected_id.take();
@@ -91,29 +196,154 @@ fn run_interactive(mut app_cfg: AppConfig, config_path: &Path, verbose: bool) ->
             HomeExit::Run {
                 id,
                 preconfirm_clean,
+                launch_here,
             } => {
                 let cfg = AppConfig::load_or_init(config_path)?;
-                return run_workset(&cfg, &id, verbose, preconfirm_clean);
+                return run_workset(
+                    &cfg,
+                    &id,
+                    preconfirm_clean.then_some(CleanupMode::Close),
+                    &RunOptions {
+                        verbose,
+                        launch_here,
+                        capture: Capture::new(capture, cfg.detach_commands),
+                        ..RunOptions::default()
+                    },
+                );
             }
-            HomeExit::Edit(id) => {
+            HomeExit::Edit { id, entry } => {
                 last_selected_id = Some(id.clone());
-                edit_workset(config_path, &id)?;
+                edit_workset(config_path, &id, entry)?;
                 app_cfg = AppConfig::load_or_init(config_path)?;
             }
         }
     }
 }
 
-fn run_workset(cfg: &AppConfig, id: &str, verbose: bool, preconfirm_clean: bool) -> Result<()> {
+fn run_workset(
+    cfg: &AppConfig,
+    id: &str,
+    forced_cleanup: Option<CleanupMode>,
+    opts: &RunOptions,
+) -> Result<()> {
     let ws = find_workset(cfg, id)?;
-    run_workset_launch(ws, verbose, preconfirm_clean)
+    run_workset_launch(ws, launch_cleanup(cfg, ws, forced_cleanup), opts)
+}
+
+/// `--no-cleanup` (`Keep`) or a cleanup already confirmed in the TUI (`Close`) wins over the
+/// configured mode. `Keep` returns before any window is counted or closed.
+fn launch_cleanup(cfg: &AppConfig, ws: &Workset, forced: Option<CleanupMode>) -> CleanupMode {
+    forced.unwrap_or_else(|| cfg.cleanup_mode_for(ws))
+}
+
+/// Unlike a single `run`, a failing workset is reported and the rest still launch.
+fn run_startup(cfg: &AppConfig, yes: bool, verbose: bool, capture: bool) -> Result<()> {
+    let worksets = cfg.startup_worksets();
+    if worksets.is_empty() {
+        status!("no worksets marked startup = true");
+        return Ok(());
+    }
+    let opts = RunOptions {
+        verbose,
+        capture: Capture::new(capture, cfg.detach_commands),
+        ..RunOptions::default()
+    };
+    let mut failed = Vec::new();
+    for ws in &worksets {
+        // Nobody is there to answer a prompt at session start.
+        let cleanup = match cfg.cleanup_mode_for(ws) {
+            CleanupMode::Ask if yes => CleanupMode::Close,
+            CleanupMode::Ask => CleanupMode::Keep,
+            mode => mode,
+        };
+        match run_workset_launch(ws, cleanup, &opts) {
+            Ok(()) => status!("startup: '{}' ok", ws.id),
+            Err(err) => {
+                eprintln!("startup: '{}' failed: {err:#}", ws.id);
+                failed.push(ws.id.as_str());
+            }
+        }
+    }
+    if !failed.is_empty() {
+        bail!(
+            "{} of {} startup workset(s) failed: {}",
+            failed.len(),
+            worksets.len(),
+            failed.join(", ")
+        );
+    }
+    Ok(())
+}
+
+fn clean_workspaces(cfg: &AppConfig, tab: Option<&str>, verbose: bool) -> Result<()> {
+    let worksets = match tab {
+        Some(tab_id) => {
+            let state = AppState::load(&default_state_path());
+            cfg.worksets_in_tab(tab_id, &state.last_run)?
+        }
+        None => cfg.worksets.iter().collect(),
+    };
+    if worksets.is_empty() {
+        status!("no worksets to clean");
+        return Ok(());
+    }
+    clean_workset_workspaces(&worksets, verbose)?;
+    Ok(())
+}
+
+fn rename_id(config_path: &Path, old: &str, new: &str) -> Result<()> {
+    let mut cfg = AppConfig::load_or_init(config_path)?;
+    let new = new.trim();
+    rename_workset_id(&mut cfg, old, new)?;
+    cfg.save(config_path)?;
+    status!("renamed workset '{old}' to '{new}'");
+    Ok(())
+}
+
+fn set_workspace(config_path: &Path, id: &str, raw: &str) -> Result<()> {
+    let mut cfg = AppConfig::load_or_init(config_path)?;
+    let value = set_workset_workspace(&mut cfg, id, raw)?;
+    cfg.save(config_path)?;
+    status!("set workspace of '{id}' to {value}");
+    if run::workspace_exists(&value) == Some(false) {
+        eprintln!("warning: Hyprland has no workspace {value} right now; launching creates it");
+    }
+    Ok(())
+}
+
+fn set_workset_workspace(cfg: &mut AppConfig, id: &str, raw: &str) -> Result<String> {
+    let value = run::validate_workspace_value(raw)?;
+    let Some(ws) = cfg.worksets.iter_mut().find(|w| w.id == id) else {
+        bail!("workset not found: {id}");
+    };
+    ws.workspace = Some(value.clone());
+    ws.touch();
+    Ok(value)
+}
+
+fn reorder_command(config_path: &Path, id: &str, from: usize, to: usize) -> Result<()> {
+    let mut cfg = AppConfig::load_or_init(config_path)?;
+    move_workset_command(&mut cfg, id, from, to)?;
+    cfg.save(config_path)?;
+    status!("moved command {from} of '{id}' to position {to}");
+    Ok(())
+}
+
+fn print_script(cfg: &AppConfig, id: &str) -> Result<()> {
+    let ws = cfg
+        .worksets
+        .iter()
+        .find(|w| w.id == id)
+        .ok_or_else(|| anyhow!("workset not found: {id}"))?;
+    print!("{}", run::hyprctl_script(ws));
+    Ok(())
 }
 
 fn print_version() {
     println!("{} {}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"));
 }
 
-fn edit_workset(config_path: &Path, id: &str) -> Result<()> {
+fn edit_workset(config_path: &Path, id: &str, entry: EditorEntry) -> Result<()> {
     let cfg = AppConfig::load_or_init(config_path)?;
     let ws = cfg
         .worksets
@@ -122,8 +352,31 @@ fn edit_workset(config_path: &Path, id: &str) -> Result<()> {
         .cloned()
         .ok_or_else(|| anyhow!("workset not found: {id}"))?;
 
-    let EditorExit::Saved(updated) = run_editor(ws, config_path)?;
-    println!("finished editing workset '{}'", updated.id);
+    let EditorExit::Saved(updated) = run_editor(ws, config_path, entry)?;
+    status!("finished editing workset '{}'", updated.id);
+    Ok(())
+}
+
+/// Saves an empty workset under `id` so `edit --create` can open the editor on it.
+fn create_workset(config_path: &Path, id: &str) -> Result<()> {
+    let mut cfg = AppConfig::load_or_init(config_path)?;
+    insert_blank_workset(&mut cfg, id)?;
+    cfg.save(config_path)
+        .with_context(|| format!("failed to write config to {}", config_path.display()))?;
+    status!("created workset '{id}'");
+    Ok(())
+}
+
+fn insert_blank_workset(cfg: &mut AppConfig, id: &str) -> Result<()> {
+    if id.trim().is_empty() || id.trim() != id {
+        bail!("invalid workset id '{id}': it must be non-empty without surrounding spaces");
+    }
+    if cfg.worksets.iter().any(|w| w.id == id) {
+        bail!("workset '{id}' already exists; run `hyprsets edit {id}` without --create");
+    }
+    let mut ws = Workset::blank(id, id, "");
+    ws.touch();
+    cfg.worksets.push(ws);
     Ok(())
 }
 
@@ -144,3 +397,90 @@ fn find_workset<'a>(cfg: &'a AppConfig, id: &str) -> Result<&'a Workset> {
 
     Err(anyhow!("workset not found: {id}. available: {available}"))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quiet_is_global_and_excludes_verbose() {
+        let cli = Cli::try_parse_from(["hyprsets", "run", "dev", "--quiet"]).unwrap();
+        assert!(cli.quiet);
+        assert!(matches!(cli.command, Some(Command::Run { .. })));
+        assert!(!Cli::try_parse_from(["hyprsets"]).unwrap().quiet);
+        assert!(Cli::try_parse_from(["hyprsets", "-q", "-v", "clean"]).is_err());
+    }
+
+    #[test]
+    fn create_rejects_taken_or_blank_ids() {
+        let mut cfg: AppConfig = toml::from_str(
+            r#"
+[[workset]]
+id = "dev"
+name = "Dev"
+desc = ""
+"#,
+        )
+        .unwrap();
+        assert!(insert_blank_workset(&mut cfg, "dev").is_err());
+        assert!(insert_blank_workset(&mut cfg, "  ").is_err());
+        insert_blank_workset(&mut cfg, "notes").unwrap();
+        let created = find_workset(&cfg, "notes").unwrap();
+        assert_eq!(created.name, "notes");
+        assert!(created.commands.is_empty() && created.layout.is_none());
+    }
+
+    #[test]
+    fn set_workspace_updates_the_field_and_special_values_round_trip() {
+        let mut cfg: AppConfig = toml::from_str(
+            r#"
+[[workset]]
+id = "dev"
+name = "Dev"
+desc = ""
+workspace = "3"
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            set_workset_workspace(&mut cfg, "dev", " special:scratch ").unwrap(),
+            "special:scratch"
+        );
+        assert!(cfg.worksets[0].modified.is_some());
+        let saved: AppConfig = toml::from_str(&toml::to_string(&cfg).unwrap()).unwrap();
+        assert_eq!(
+            saved.worksets[0].workspace.as_deref(),
+            Some("special:scratch")
+        );
+
+        for bad in ["", "0", "-2", "name:", "special: "] {
+            assert!(
+                set_workset_workspace(&mut cfg, "dev", bad).is_err(),
+                "{bad:?}"
+            );
+        }
+        assert!(set_workset_workspace(&mut cfg, "missing", "2").is_err());
+        set_workset_workspace(&mut cfg, "dev", "name:code").unwrap();
+        assert_eq!(cfg.worksets[0].workspace.as_deref(), Some("name:code"));
+    }
+
+    #[test]
+    fn no_cleanup_skips_closing_whatever_the_workset_says() {
+        let cfg: AppConfig = toml::from_str(
+            r#"
+[[workset]]
+id = "dev"
+name = "Dev"
+desc = ""
+cleanup = "close"
+"#,
+        )
+        .unwrap();
+        let ws = &cfg.worksets[0];
+        assert_eq!(launch_cleanup(&cfg, ws, None), CleanupMode::Close);
+        assert_eq!(
+            launch_cleanup(&cfg, ws, Some(CleanupMode::Keep)),
+            CleanupMode::Keep
+        );
+    }
+}
@@ -1,12 +1,28 @@
 mod actions_layout;
 mod actions_workspace;
+mod capture;
 mod lock;
+mod script;
 mod util;
 
-pub use actions_workspace::{run_workset, workspace_cleanup_status};
+pub use capture::Capture;
 
+pub use actions_workspace::{
+    RunOptions, SPECIAL_WORKSPACE_WARNING, clean_workset_workspaces, run_workset,
+    scratchpad_toggle_ready, validate_workspace_value, workspace_cleanup_status, workspace_exists,
+};
+
+pub(crate) use actions_layout::{count_slots, effective_command_count};
+pub(crate) use actions_workspace::{
+    LiveWindowCounts, SPECIAL_WORKSPACE_GLYPH, WorkspacePreview, active_workspace,
+    workset_for_workspace, workspace_glyph,
+};
 pub(crate) use lock::acquire_launch_lock;
-pub(crate) use util::build_exec_command;
+pub(crate) use script::hyprctl_script;
+pub(crate) use util::{
+    build_exec_command, ensure_hyprland_session, launch_cwd, open_in_file_manager, scope_warning,
+    slot_exec_command,
+};
 
 use std::time::Duration;
 
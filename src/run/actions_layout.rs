@@ -7,15 +7,22 @@ use anyhow::{Context, Result, bail};
 use hyprland::{
     data::{Client, Clients},
     dispatch::{Dispatch, DispatchType, WindowIdentifier},
+    keyword::{Keyword, OptionValue},
     shared::{Address, HyprData, HyprDataActiveOptional},
 };
 
-use crate::config::{LayoutNode, WindowSlot, Workset};
+use crate::{
+    config::{LayoutNode, WindowSlot, Workset},
+    output::status,
+};
 
 use super::{
     HYPR_SPLIT_MAX, HYPR_SPLIT_MIN, SLOT_LAUNCH_DELAY, WINDOW_APPEAR_TIMEOUT, WINDOW_POLL_INTERVAL,
     actions_workspace::{WorkspaceContext, WorkspaceTarget, ensure_workspace_focus},
     build_exec_command,
+    capture::{Capture, capture_dir, spawn_captured},
+    slot_exec_command,
+    util::{launch_cwd, launch_scope},
 };
 use std::collections::HashSet;
 
@@ -23,25 +30,89 @@ pub(crate) fn run_commands(
     ws: &Workset,
     verbose: bool,
     workspace_target: &WorkspaceTarget,
+    launched: &mut usize,
+    capture: Capture,
+    budget: &LaunchBudget,
+) -> Result<()> {
+    dispatch_commands(
+        ws,
+        verbose,
+        launched,
+        &mut || ensure_workspace_focus(workspace_target, verbose),
+        &mut |exec, label| launch_exec(exec, label, capture, verbose),
+        budget,
+    )
+}
+
+/// Overall deadline for one launch (`total_timeout_ms`), checked before each slot or command.
+/// A window that is slow to appear still gets its full per-slot timeout.
+pub(crate) struct LaunchBudget<'a> {
+    started: Instant,
+    limit: Option<Duration>,
+    clock: &'a dyn Fn() -> Instant,
+}
+
+impl<'a> LaunchBudget<'a> {
+    pub(crate) fn new(limit: Option<Duration>, clock: &'a dyn Fn() -> Instant) -> Self {
+        Self {
+            started: clock(),
+            limit,
+            clock,
+        }
+    }
+
+    /// Fails once the budget is spent, naming how far the launch got.
+    fn check(&self, done: usize, total: usize, unit: &str) -> Result<()> {
+        let Some(limit) = self.limit else {
+            return Ok(());
+        };
+        if (self.clock)().duration_since(self.started) >= limit {
+            bail!(
+                "workset launch exceeded total timeout ({}ms) after {done} of {total} {unit}",
+                limit.as_millis()
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Sequential mode refocuses the target before each command and pauses between them;
+/// `parallel` focuses once and fires every command back to back, so the order windows
+/// appear in (and which one ends up focused) is up to the apps.
+fn dispatch_commands(
+    ws: &Workset,
+    verbose: bool,
+    launched: &mut usize,
+    focus: &mut dyn FnMut() -> Result<()>,
+    exec: &mut dyn FnMut(&str, &str) -> Result<()>,
+    budget: &LaunchBudget,
 ) -> Result<()> {
     let cmds = &ws.commands;
     if cmds.is_empty() {
-        println!("no commands to run");
+        status!("no commands to run");
         return Ok(());
     }
+    if ws.parallel {
+        status!(" launching {} command(s) in parallel", cmds.len());
+        focus()?;
+    }
 
     for (idx, cmd) in cmds.iter().enumerate() {
-        ensure_workspace_focus(workspace_target, verbose)?;
+        budget.check(idx, cmds.len(), "commands")?;
+        if !ws.parallel {
+            focus()?;
+        }
         if verbose {
-            println!(" exec[{idx}]: {cmd}");
+            status!(" exec[{idx}]: {cmd}");
         } else {
-            println!(" exec: {cmd}");
+            status!(" exec: {cmd}");
         }
-        let exec = build_exec_command(cmd, ws.cwd.as_deref(), [&ws.env]);
-        Dispatch::call(DispatchType::Exec(exec.as_str()))
+        let command = build_exec_command(cmd, launch_cwd(ws, None), [&ws.env], launch_scope(ws));
+        exec(&command, &format!("{}-cmd{idx}", ws.id))
             .with_context(|| format!("failed to exec command: {cmd}"))?;
-        if idx + 1 < cmds.len() {
-            println!(
+        *launched += 1;
+        if !ws.parallel && idx + 1 < cmds.len() {
+            status!(
                 " waiting {} before next command...",
                 format_delay(SLOT_LAUNCH_DELAY)
             );
@@ -58,7 +129,16 @@ pub(crate) fn run_layout(
     verbose: bool,
     workspace: &WorkspaceContext,
     workspace_target: &WorkspaceTarget,
+    launched: &mut usize,
+    capture: Capture,
+    fill: bool,
+    budget: &LaunchBudget,
 ) -> Result<()> {
+    let _animations = if ws.suppress_animations {
+        AnimationGuard::disable(verbose)
+    } else {
+        None
+    };
     let current_clients = Clients::get().context("failed to list Hyprland clients")?;
     let mut known_clients: HashSet<Address> = current_clients
         .iter()
@@ -66,15 +146,35 @@ pub(crate) fn run_layout(
         .map(|c| c.address.clone())
         .collect();
     let base_clients = known_clients.len();
-    let total_slots = count_slots(node);
-    let mut launched = 0usize;
     let mut launched_slots = HashSet::new();
+    if fill {
+        let classes: Vec<&str> = current_clients
+            .iter()
+            .filter(|c| workspace.matches(&c.workspace))
+            .map(|c| c.class.as_str())
+            .collect();
+        let present = present_slots(node, &classes);
+        let mut ids: Vec<u32> = present.iter().copied().collect();
+        ids.sort_unstable();
+        for id in &ids {
+            status!(" slot #{id} already open; skipping");
+        }
+        // Marking them launched makes run_layout_inner step over them like repeated slots.
+        launched_slots = present;
+    }
+    let mut disabled: Vec<u32> = disabled_slots(node).into_iter().collect();
+    disabled.sort_unstable();
+    for id in &disabled {
+        status!(" slot #{id} disabled; skipping");
+    }
+    launched_slots.extend(disabled);
+    let total_slots = count_slots(node).saturating_sub(launched_slots.len());
     let mut pending_ratio = None;
     run_layout_inner(
         node,
         ws,
         verbose,
-        &mut launched,
+        launched,
         total_slots,
         workspace,
         base_clients,
@@ -82,6 +182,9 @@ pub(crate) fn run_layout(
         workspace_target,
         &mut known_clients,
         &mut launched_slots,
+        &mut 0,
+        capture,
+        budget,
     )?;
     Ok(())
 }
@@ -99,47 +202,45 @@ fn run_layout_inner(
     workspace_target: &WorkspaceTarget,
     known_clients: &mut HashSet<Address>,
     launched_slots: &mut HashSet<u32>,
+    offsite: &mut usize,
+    capture: Capture,
+    budget: &LaunchBudget,
 ) -> Result<Option<Address>> {
     match node {
         LayoutNode::Leaf(slot) => {
             if !launched_slots.insert(slot.slot_id) {
                 return Ok(active_address_on_workspace(workspace));
             }
-            ensure_workspace_focus(workspace_target, verbose)?;
-            println!(" slot #{} exec: {}", slot.slot_id, slot.command);
-            let cwd = slot.cwd.as_deref().or(ws.cwd.as_deref());
-            let exec = build_exec_command(&slot.command, cwd, [&ws.env, &slot.env]);
-            Dispatch::call(DispatchType::Exec(exec.as_str()))
+            budget.check(*launched, total_slots, "slots")?;
+            let slot_target = WorkspaceTarget::for_slot(slot, workspace_target);
+            ensure_workspace_focus(slot_target.as_ref().unwrap_or(workspace_target), verbose)?;
+            status!(" slot #{} exec: {}", slot.slot_id, slot.command);
+            let exec = slot_exec_command(ws, slot);
+            let label = format!("{}-slot{}", ws.id, slot.slot_id);
+            launch_exec(&exec, &label, capture, verbose)
                 .with_context(|| format!("failed to exec slot #{} command", slot.slot_id))?;
             *launched += 1;
-            let target_clients = base_clients + *launched;
+            if let Some(target) = &slot_target {
+                // Its window opens elsewhere, so there is nothing here to wait for or split.
+                *offsite += 1;
+                status!(" slot #{} sent to {}", slot.slot_id, target.label());
+                pause_before_next_slot(slot, total_slots.saturating_sub(*launched));
+                return Ok(None);
+            }
+            let target_clients = base_clients + *launched - *offsite;
             wait_for_clients_on_workspace(workspace, target_clients, verbose, workspace_target)?;
 
             if let Some(ratio) = pending_ratio.take() {
                 apply_split_ratio(ratio, verbose);
             }
 
-            let anchor = newly_added_address(workspace, known_clients)
+            let anchor = newly_added_address(workspace, known_clients, slot.class_hint())
                 .or_else(|| active_address_on_workspace(workspace));
             if let Some(addr) = &anchor {
                 known_clients.insert(addr.clone());
             }
 
-            let remaining = total_slots.saturating_sub(*launched);
-            if remaining > 0 {
-                let launch_delay = slot_launch_delay(slot);
-                println!(
-                    " waiting {} ({}) before next slot... (remaining: {})",
-                    format_delay(launch_delay),
-                    if slot.wait_after_ms.is_some() {
-                        "custom wait_after_ms"
-                    } else {
-                        "default"
-                    },
-                    remaining
-                );
-                thread::sleep(launch_delay);
-            }
+            pause_before_next_slot(slot, total_slots.saturating_sub(*launched));
             Ok(anchor)
         }
         LayoutNode::Split(split) => {
@@ -149,9 +250,10 @@ fn run_layout_inner(
                     crate::config::SplitDirection::Horizontal => "horizontal",
                     crate::config::SplitDirection::Vertical => "vertical",
                 };
-                println!(
+                status!(
                     " split {dir} ratio {:.2} (hyprctl {:.3})",
-                    split.ratio, hypr_ratio
+                    split.ratio,
+                    hypr_ratio
                 );
             }
             let (left_anchor, left_was_split) = match &*split.left {
@@ -168,6 +270,9 @@ fn run_layout_inner(
                         workspace_target,
                         known_clients,
                         launched_slots,
+                        offsite,
+                        capture,
+                        budget,
                     )?;
                     (anchor, true)
                 }
@@ -184,6 +289,9 @@ fn run_layout_inner(
                         workspace_target,
                         known_clients,
                         launched_slots,
+                        offsite,
+                        capture,
+                        budget,
                     )?;
                     (anchor, false)
                 }
@@ -206,9 +314,12 @@ fn run_layout_inner(
                 workspace_target,
                 known_clients,
                 launched_slots,
+                offsite,
+                capture,
+                budget,
             )?;
 
-            let target_clients = base_clients + *launched;
+            let target_clients = base_clients + *launched - *offsite;
             wait_for_clients_on_workspace(workspace, target_clients, verbose, workspace_target)?;
             let remaining_left = if left_was_split {
                 if let Some(addr) = left_anchor.clone() {
@@ -228,6 +339,9 @@ fn run_layout_inner(
                     workspace_target,
                     known_clients,
                     launched_slots,
+                    offsite,
+                    capture,
+                    budget,
                 )?
             } else {
                 None
@@ -237,7 +351,97 @@ fn run_layout_inner(
     }
 }
 
-fn slot_launch_delay(slot: &WindowSlot) -> Duration {
+fn pause_before_next_slot(slot: &WindowSlot, remaining: usize) {
+    if remaining == 0 {
+        return;
+    }
+    let launch_delay = slot_launch_delay(slot);
+    status!(
+        " waiting {} ({}) before next slot... (remaining: {})",
+        format_delay(launch_delay),
+        if slot.wait_after_ms.is_some() {
+            "custom wait_after_ms"
+        } else {
+            "default"
+        },
+        remaining
+    );
+    thread::sleep(launch_delay);
+}
+
+/// Set through `hyprctl keyword` rather than a `Custom` dispatch: `keyword` is its own IPC
+/// command, not a dispatcher, so `dispatch keyword ...` would be rejected.
+const ANIMATIONS_KEYWORD: &str = "animations:enabled";
+
+/// Turns Hyprland animations off for the duration of a launch and restores the previous
+/// value when dropped, including on early returns and errors.
+struct AnimationGuard {
+    previous: i64,
+    verbose: bool,
+}
+
+impl AnimationGuard {
+    fn disable(verbose: bool) -> Option<Self> {
+        let previous = match Keyword::get(ANIMATIONS_KEYWORD) {
+            Ok(keyword) => match keyword.value {
+                OptionValue::Int(value) => value,
+                other => {
+                    if verbose {
+                        status!(" animation suppression skipped (unexpected value {other:?})");
+                    }
+                    return None;
+                }
+            },
+            Err(err) => {
+                if verbose {
+                    status!(" animation suppression skipped (failed to read state: {err})");
+                }
+                return None;
+            }
+        };
+        if previous == 0 {
+            if verbose {
+                status!(" animations already disabled");
+            }
+            return None;
+        }
+        if let Err(err) = Keyword::set(ANIMATIONS_KEYWORD, 0) {
+            eprintln!(" animation suppression skipped: {err}");
+            return None;
+        }
+        if verbose {
+            status!(" animations disabled for launch");
+        }
+        Some(Self { previous, verbose })
+    }
+}
+
+impl Drop for AnimationGuard {
+    fn drop(&mut self) {
+        match Keyword::set(ANIMATIONS_KEYWORD, self.previous) {
+            Ok(()) => {
+                if self.verbose {
+                    status!(
+                        " animations restored ({ANIMATIONS_KEYWORD} = {})",
+                        self.previous
+                    );
+                }
+            }
+            Err(err) => eprintln!(" failed to restore {ANIMATIONS_KEYWORD}: {err}"),
+        }
+    }
+}
+
+/// Hands `exec` to Hyprland, or with `--capture` runs it directly so failures show its stderr.
+fn launch_exec(exec: &str, label: &str, capture: Capture, verbose: bool) -> Result<()> {
+    if let Capture::On { detach } = capture {
+        return spawn_captured(exec, label, &capture_dir(), detach, verbose);
+    }
+    Dispatch::call(DispatchType::Exec(exec))?;
+    Ok(())
+}
+
+pub(super) fn slot_launch_delay(slot: &WindowSlot) -> Duration {
     slot.wait_after_ms
         .map(Duration::from_millis)
         .unwrap_or(SLOT_LAUNCH_DELAY)
@@ -251,7 +455,7 @@ fn format_delay(delay: Duration) -> String {
     }
 }
 
-fn to_hypr_split_ratio(user_ratio: f32) -> f32 {
+pub(super) fn to_hypr_split_ratio(user_ratio: f32) -> f32 {
     let safe = user_ratio.max(0.01);
     let converted = 2.0 * safe / (safe + 1.0);
     converted.clamp(HYPR_SPLIT_MIN, HYPR_SPLIT_MAX)
@@ -260,14 +464,86 @@ fn to_hypr_split_ratio(user_ratio: f32) -> f32 {
 fn apply_split_ratio(hypr_ratio: f32, verbose: bool) {
     let arg = format!("exact {:.3}", hypr_ratio);
     if verbose {
-        println!(" applying split ratio: splitratio {arg}");
+        status!(" applying split ratio: splitratio {arg}");
     }
     if let Err(err) = Dispatch::call(DispatchType::Custom("splitratio", arg.as_str())) {
         eprintln!(" split ratio change skipped (splitratio {arg}): {err}");
     }
 }
 
-fn count_slots(node: &LayoutNode) -> usize {
+/// Number of windows a workset is expected to open: one per layout slot, or one per command.
+pub(crate) fn expected_window_count(ws: &Workset) -> usize {
+    match &ws.layout {
+        Some(layout) => count_slots(layout) - disabled_slots(layout).len(),
+        None => ws.commands.len(),
+    }
+}
+
+/// Commands or layout slots that would actually launch something; blank ones are skipped.
+pub(crate) fn effective_command_count(ws: &Workset) -> usize {
+    match &ws.layout {
+        Some(layout) => count_filled_slots(layout),
+        None => ws.commands.iter().filter(|c| !c.trim().is_empty()).count(),
+    }
+}
+
+fn count_filled_slots(node: &LayoutNode) -> usize {
+    match node {
+        LayoutNode::Leaf(slot) => usize::from(slot.enabled && !slot.command.trim().is_empty()),
+        LayoutNode::Split(split) => {
+            count_filled_slots(&split.left) + count_filled_slots(&split.right)
+        }
+    }
+}
+
+/// Slots whose expected `class` already has a window on the workspace. Each window satisfies
+/// at most one slot, so two slots of the same class need two open windows.
+fn present_slots(node: &LayoutNode, classes: &[&str]) -> HashSet<u32> {
+    let mut unclaimed: Vec<&str> = classes.to_vec();
+    let mut present = HashSet::new();
+    collect_present_slots(node, &mut unclaimed, &mut present);
+    present
+}
+
+fn collect_present_slots(node: &LayoutNode, unclaimed: &mut Vec<&str>, present: &mut HashSet<u32>) {
+    match node {
+        LayoutNode::Leaf(slot) => {
+            let Some(class) = slot.class_hint().filter(|_| slot.enabled) else {
+                return;
+            };
+            if let Some(pos) = unclaimed.iter().position(|c| c.eq_ignore_ascii_case(class)) {
+                unclaimed.swap_remove(pos);
+                present.insert(slot.slot_id);
+            }
+        }
+        LayoutNode::Split(split) => {
+            collect_present_slots(&split.left, unclaimed, present);
+            collect_present_slots(&split.right, unclaimed, present);
+        }
+    }
+}
+
+/// Ids of slots switched off with `enabled = false`; they keep their place but never launch.
+pub(crate) fn disabled_slots(node: &LayoutNode) -> HashSet<u32> {
+    fn walk(node: &LayoutNode, ids: &mut HashSet<u32>) {
+        match node {
+            LayoutNode::Leaf(slot) => {
+                if !slot.enabled {
+                    ids.insert(slot.slot_id);
+                }
+            }
+            LayoutNode::Split(split) => {
+                walk(&split.left, ids);
+                walk(&split.right, ids);
+            }
+        }
+    }
+    let mut ids = HashSet::new();
+    walk(node, &mut ids);
+    ids
+}
+
+pub(crate) fn count_slots(node: &LayoutNode) -> usize {
     match node {
         LayoutNode::Leaf(_) => 1,
         LayoutNode::Split(split) => count_slots(&split.left) + count_slots(&split.right),
@@ -282,7 +558,7 @@ fn focus_window(
 ) -> Result<()> {
     let id = WindowIdentifier::Address(addr.clone());
     if verbose {
-        println!(" focusing window at address {addr}");
+        status!(" focusing window at address {addr}");
     }
     Dispatch::call(DispatchType::FocusWindow(id))
         .with_context(|| format!("failed to focus window at address {addr}"))?;
@@ -293,7 +569,7 @@ fn focus_window(
         .filter(|c| workspace.matches(&c.workspace))
         .map(|c| c.address);
     if active != Some(addr.clone()) && verbose {
-        println!(" focus verification skipped/failed (expected {addr:?}, got {active:?})");
+        status!(" focus verification skipped/failed (expected {addr:?}, got {active:?})");
     }
     Ok(())
 }
@@ -306,16 +582,33 @@ fn active_address_on_workspace(workspace: &WorkspaceContext) -> Option<Address>
         .map(|c| c.address)
 }
 
-fn newly_added_address(workspace: &WorkspaceContext, known: &HashSet<Address>) -> Option<Address> {
+fn newly_added_address(
+    workspace: &WorkspaceContext,
+    known: &HashSet<Address>,
+    class_hint: Option<&str>,
+) -> Option<Address> {
     Clients::get().ok().and_then(|clients| {
-        clients
+        let fresh = clients
             .into_iter()
-            .filter(|c| workspace.matches(&c.workspace))
-            .map(|c| c.address)
-            .find(|addr| !known.contains(addr))
+            .filter(|c| workspace.matches(&c.workspace) && !known.contains(&c.address))
+            .map(|c| (c.address, c.class))
+            .collect();
+        pick_new_window(fresh, class_hint)
     })
 }
 
+/// When several windows appeared at once, the one whose class matches the slot's hint wins;
+/// otherwise the first new window is the anchor.
+fn pick_new_window(fresh: Vec<(Address, String)>, class_hint: Option<&str>) -> Option<Address> {
+    let matching = class_hint.and_then(|hint| {
+        fresh
+            .iter()
+            .position(|(_, class)| class.eq_ignore_ascii_case(hint))
+    });
+    let idx = matching.unwrap_or(0);
+    fresh.into_iter().nth(idx).map(|(addr, _)| addr)
+}
+
 #[allow(clippy::too_many_arguments)]
 fn run_left_anchor(
     node: &LayoutNode,
@@ -329,6 +622,9 @@ fn run_left_anchor(
     workspace_target: &WorkspaceTarget,
     known_clients: &mut HashSet<Address>,
     launched_slots: &mut HashSet<u32>,
+    offsite: &mut usize,
+    capture: Capture,
+    budget: &LaunchBudget,
 ) -> Result<Option<Address>> {
     match node {
         LayoutNode::Leaf(_) => run_layout_inner(
@@ -343,6 +639,9 @@ fn run_left_anchor(
             workspace_target,
             known_clients,
             launched_slots,
+            offsite,
+            capture,
+            budget,
         ),
         LayoutNode::Split(split) => run_left_anchor(
             &split.left,
@@ -356,6 +655,9 @@ fn run_left_anchor(
             workspace_target,
             known_clients,
             launched_slots,
+            offsite,
+            capture,
+            budget,
         ),
     }
 }
@@ -379,7 +681,7 @@ fn wait_for_clients_on_workspace(
 
         if count >= target {
             if verbose {
-                println!(" {}: clients ready ({}/{})", label, count, target);
+                status!(" {}: clients ready ({}/{})", label, count, target);
             }
             return Ok(());
         }
@@ -395,7 +697,7 @@ fn wait_for_clients_on_workspace(
         }
 
         if verbose && first_log {
-            println!(" waiting for windows... {}: {}/{}", label, count, target);
+            status!(" waiting for windows... {}: {}/{}", label, count, target);
             first_log = false;
         }
         thread::sleep(WINDOW_POLL_INTERVAL);
@@ -420,30 +722,12 @@ mod tests {
         let layout = LayoutNode::Split(crate::config::SplitNode {
             direction: SplitDirection::Horizontal,
             ratio: 0.5,
-            left: Box::new(LayoutNode::Leaf(crate::config::WindowSlot {
-                slot_id: 1,
-                command: "a".into(),
-                cwd: None,
-                env: Default::default(),
-                wait_after_ms: None,
-            })),
+            left: Box::new(LayoutNode::Leaf(crate::config::WindowSlot::new(1, "a"))),
             right: Box::new(LayoutNode::Split(crate::config::SplitNode {
                 direction: SplitDirection::Vertical,
                 ratio: 0.5,
-                left: Box::new(LayoutNode::Leaf(crate::config::WindowSlot {
-                    slot_id: 2,
-                    command: "b".into(),
-                    cwd: None,
-                    env: Default::default(),
-                    wait_after_ms: None,
-                })),
-                right: Box::new(LayoutNode::Leaf(crate::config::WindowSlot {
-                    slot_id: 3,
-                    command: "c".into(),
-                    cwd: None,
-                    env: Default::default(),
-                    wait_after_ms: None,
-                })),
+                left: Box::new(LayoutNode::Leaf(crate::config::WindowSlot::new(2, "b"))),
+                right: Box::new(LayoutNode::Leaf(crate::config::WindowSlot::new(3, "c"))),
             })),
         });
         assert_eq!(count_slots(&layout), 3);
@@ -451,13 +735,7 @@ mod tests {
 
     #[test]
     fn slot_launch_delay_prefers_override() {
-        let mut slot = crate::config::WindowSlot {
-            slot_id: 1,
-            command: "cmd".into(),
-            cwd: None,
-            env: Default::default(),
-            wait_after_ms: None,
-        };
+        let mut slot = crate::config::WindowSlot::new(1, "cmd");
         assert_eq!(slot_launch_delay(&slot), SLOT_LAUNCH_DELAY);
 
         slot.wait_after_ms = Some(2_500);
@@ -472,4 +750,184 @@ mod tests {
         assert_eq!(format_delay(Duration::from_millis(1_000)), "1.00s");
         assert_eq!(format_delay(Duration::from_millis(1_001)), "1.00s");
     }
+
+    #[test]
+    fn expected_window_count_uses_layout_or_commands() {
+        let mut ws = Workset {
+            id: "w".into(),
+            name: "w".into(),
+            commands: vec!["a".into(), "b".into()],
+            ..Default::default()
+        };
+        assert_eq!(expected_window_count(&ws), 2);
+
+        ws.layout = Some(LayoutNode::Leaf(crate::config::WindowSlot::new(1, "a")));
+        assert_eq!(expected_window_count(&ws), 1);
+
+        if let Some(LayoutNode::Leaf(slot)) = ws.layout.as_mut() {
+            slot.enabled = false;
+        }
+        assert_eq!(expected_window_count(&ws), 0);
+        assert_eq!(effective_command_count(&ws), 0);
+        assert_eq!(
+            disabled_slots(ws.layout.as_ref().unwrap()),
+            HashSet::from([1])
+        );
+    }
+
+    #[test]
+    fn effective_command_count_skips_blank_commands() {
+        let mut ws = Workset {
+            id: "w".into(),
+            name: "w".into(),
+            commands: vec!["a".into(), "  ".into(), String::new()],
+            ..Default::default()
+        };
+        assert_eq!(effective_command_count(&ws), 1);
+
+        let slot = |command: &str| Box::new(LayoutNode::Leaf(WindowSlot::new(1, command)));
+        ws.layout = Some(LayoutNode::Split(crate::config::SplitNode {
+            direction: crate::config::SplitDirection::Horizontal,
+            ratio: 1.0,
+            left: slot(""),
+            right: slot(" "),
+        }));
+        assert_eq!(effective_command_count(&ws), 0);
+        assert_eq!(expected_window_count(&ws), 2);
+    }
+
+    #[test]
+    fn parallel_commands_issue_every_exec_without_waiting() {
+        let ws = Workset {
+            id: "w".into(),
+            name: "w".into(),
+            commands: vec!["a".into(), "b".into(), "c".into()],
+            parallel: true,
+            ..Default::default()
+        };
+
+        let mut focused = 0;
+        let mut execs = Vec::new();
+        let mut launched = 0;
+        let started = Instant::now();
+        dispatch_commands(
+            &ws,
+            false,
+            &mut launched,
+            &mut || {
+                focused += 1;
+                Ok(())
+            },
+            &mut |exec, _| {
+                execs.push(exec.to_string());
+                Ok(())
+            },
+            &LaunchBudget::new(None, &Instant::now),
+        )
+        .unwrap();
+
+        assert_eq!(execs.len(), 3);
+        assert!(execs[2].ends_with('c'));
+        assert_eq!((launched, focused), (3, 1));
+        assert!(started.elapsed() < SLOT_LAUNCH_DELAY);
+    }
+
+    #[test]
+    fn total_timeout_stops_between_slow_commands() {
+        let ws = Workset {
+            id: "w".into(),
+            name: "w".into(),
+            commands: vec!["a".into(), "b".into(), "c".into()],
+            parallel: true,
+            total_timeout_ms: Some(100),
+            ..Default::default()
+        };
+
+        let start = Instant::now();
+        let now = std::cell::Cell::new(start);
+        let clock = || now.get();
+        let budget = LaunchBudget::new(ws.total_timeout(), &clock);
+        let mut execs = Vec::new();
+        let mut launched = 0;
+        let err = dispatch_commands(
+            &ws,
+            false,
+            &mut launched,
+            &mut || Ok(()),
+            &mut |exec, _| {
+                // Each command takes 60ms of the 100ms budget.
+                now.set(now.get() + Duration::from_millis(60));
+                execs.push(exec.to_string());
+                Ok(())
+            },
+            &budget,
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "workset launch exceeded total timeout (100ms) after 2 of 3 commands"
+        );
+        assert_eq!((execs.len(), launched), (2, 2));
+    }
+
+    #[test]
+    fn present_slots_matches_one_window_per_class() {
+        let slot = |slot_id: u32, class: Option<&str>| {
+            Box::new(LayoutNode::Leaf(WindowSlot {
+                class: class.map(str::to_string),
+                ..WindowSlot::new(slot_id, "app")
+            }))
+        };
+        let split = |left, right| {
+            Box::new(LayoutNode::Split(crate::config::SplitNode {
+                direction: crate::config::SplitDirection::Vertical,
+                ratio: 1.0,
+                left,
+                right,
+            }))
+        };
+        let layout = split(
+            slot(1, Some("kitty")),
+            split(
+                slot(2, Some("Kitty")),
+                split(slot(3, Some("firefox")), slot(4, None)),
+            ),
+        );
+
+        let present = present_slots(&layout, &["firefox", "kitty", "discord"]);
+        assert_eq!(present, HashSet::from([1, 3]));
+        assert!(present_slots(&layout, &[]).is_empty());
+    }
+
+    #[test]
+    fn app_stands_in_for_class_when_picking_the_new_window() {
+        let mut slot = WindowSlot {
+            app: Some("firefox".into()),
+            ..WindowSlot::new(1, "firefox --new-window")
+        };
+        assert_eq!(slot.class_hint(), Some("firefox"));
+        let fresh = || {
+            vec![
+                (Address::new("0x1"), "kitty".to_string()),
+                (Address::new("0x2"), "Firefox".to_string()),
+            ]
+        };
+        assert_eq!(
+            pick_new_window(fresh(), slot.class_hint()),
+            Some(Address::new("0x2"))
+        );
+
+        slot.class = Some("kitty".into());
+        assert_eq!(slot.class_hint(), Some("kitty"));
+        assert_eq!(
+            pick_new_window(fresh(), slot.class_hint()),
+            Some(Address::new("0x1"))
+        );
+        assert_eq!(
+            pick_new_window(fresh(), Some("discord")),
+            Some(Address::new("0x1"))
+        );
+        assert_eq!(pick_new_window(Vec::new(), Some("kitty")), None);
+    }
 }
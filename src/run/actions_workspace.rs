@@ -3,21 +3,29 @@ use std::{
     fs,
     io::{self, Write},
     thread,
-    time::Instant,
+    time::{Duration, Instant},
 };
 
 use anyhow::{Context, Result, bail};
 use hyprland::{
-    data::{Client, Clients, Monitor, WorkspaceBasic},
+    data::{Client, Clients, Monitor, Monitors, WorkspaceBasic, Workspaces},
     dispatch::{Dispatch, DispatchType, WindowIdentifier, WorkspaceIdentifierWithSpecial},
-    shared::{Address, HyprData, HyprDataActive, HyprDataActiveOptional},
+    shared::{Address, HyprData, HyprDataActive, HyprDataActiveOptional, HyprDataVec},
 };
 
-use crate::config::Workset;
+use crate::{
+    config::{CleanupMode, WindowSlot, Workset},
+    output::status,
+};
 
 use super::{
     WINDOW_APPEAR_TIMEOUT, WINDOW_POLL_INTERVAL, WORKSPACE_SWITCH_TIMEOUT, acquire_launch_lock,
-    actions_layout::{run_commands, run_layout},
+    actions_layout::{
+        LaunchBudget, effective_command_count, expected_window_count, run_commands, run_layout,
+    },
+    capture::Capture,
+    ensure_hyprland_session,
+    util::{scope_warning, shell_escape},
 };
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -31,6 +39,8 @@ struct CloseCandidate {
     address: Address,
     class: String,
     title: String,
+    floating: bool,
+    pinned: bool,
 }
 
 #[derive(Clone, Debug)]
@@ -49,15 +59,55 @@ struct ActiveWorkspaceState {
 #[derive(Clone, Debug)]
 pub struct WorkspaceCleanupStatus {
     pub workspace_name: String,
+    /// All windows that would be closed; `tiled_windows + floating_windows`.
     pub closable_windows: usize,
+    pub tiled_windows: usize,
+    /// Floating windows, pinned ones included (Hyprland only pins floating windows).
+    pub floating_windows: usize,
+    pub pinned_windows: usize,
+    /// Cleaning a special workspace empties a scratchpad, so prompts call it out.
+    pub is_special: bool,
 }
 
-#[derive(Clone, Debug)]
+/// Shown next to cleanup prompts for special workspaces.
+pub const SPECIAL_WORKSPACE_WARNING: &str =
+    "This is a SPECIAL workspace: closing its windows empties that scratchpad.";
+
+impl WorkspaceCleanupStatus {
+    fn from_candidates(context: &WorkspaceContext, candidates: &[CloseCandidate]) -> Self {
+        let floating_windows = candidates.iter().filter(|c| c.floating).count();
+        Self {
+            workspace_name: context.workspace.name.clone(),
+            is_special: context.is_special,
+            closable_windows: candidates.len(),
+            tiled_windows: candidates.len() - floating_windows,
+            floating_windows,
+            pinned_windows: candidates.iter().filter(|c| c.pinned).count(),
+        }
+    }
+
+    /// e.g. `2 tiled, 1 floating (1 pinned)`.
+    pub fn breakdown(&self) -> String {
+        let mut text = format!(
+            "{} tiled, {} floating",
+            self.tiled_windows, self.floating_windows
+        );
+        if self.pinned_windows > 0 {
+            text.push_str(&format!(" ({} pinned)", self.pinned_windows));
+        }
+        text
+    }
+}
+
+/// `workspace` value meaning "the active workspace", as opposed to leaving it unset.
+const CURRENT_WORKSPACE_KEYWORD: &str = "current";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub(crate) struct WorkspaceTarget {
     kind: WorkspaceTargetKind,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 enum WorkspaceTargetKind {
     Id(i32),
     Name(String),
@@ -96,8 +146,29 @@ impl WorkspaceContext {
 }
 
 impl WorkspaceTarget {
-    fn from_raw(raw: &str) -> Self {
+    /// A slot's own `workspace` when it points somewhere other than `main`; `None` keeps the
+    /// slot on the workset's workspace.
+    pub(crate) fn for_slot(slot: &WindowSlot, main: &WorkspaceTarget) -> Option<Self> {
+        let target = slot
+            .workspace
+            .as_deref()
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .and_then(Self::from_raw)?;
+        (target != *main).then_some(target)
+    }
+
+    /// Parses a `workspace` value. The `current` keyword yields `None`: launch on whatever
+    /// workspace is active, without falling back to a less specific setting.
+    pub(super) fn from_raw(raw: &str) -> Option<Self> {
         let trimmed = raw.trim();
+        if trimmed.eq_ignore_ascii_case(CURRENT_WORKSPACE_KEYWORD) {
+            return None;
+        }
+        Some(Self::parse_target(trimmed))
+    }
+
+    fn parse_target(trimmed: &str) -> Self {
         if let Some(name) = trimmed.strip_prefix("special:") {
             return Self {
                 kind: WorkspaceTargetKind::Special(Some(name.to_string())),
@@ -154,7 +225,7 @@ impl WorkspaceTarget {
         }
     }
 
-    fn label(&self) -> String {
+    pub(crate) fn label(&self) -> String {
         match &self.kind {
             WorkspaceTargetKind::Id(id) => format!("workspace {id}"),
             WorkspaceTargetKind::Name(name) => format!("workspace '{name}'"),
@@ -165,6 +236,16 @@ impl WorkspaceTarget {
         }
     }
 
+    /// Argument for `hyprctl dispatch workspace`, matching what `identifier` sends over IPC.
+    pub(super) fn dispatch_arg(&self) -> String {
+        match &self.kind {
+            WorkspaceTargetKind::Id(id) => id.to_string(),
+            WorkspaceTargetKind::Name(name) => shell_escape(&format!("name:{name}")),
+            WorkspaceTargetKind::Special(None) => "special".into(),
+            WorkspaceTargetKind::Special(Some(name)) => shell_escape(&format!("special:{name}")),
+        }
+    }
+
     fn identifier(&self) -> WorkspaceIdentifierWithSpecial<'_> {
         match &self.kind {
             WorkspaceTargetKind::Id(id) => WorkspaceIdentifierWithSpecial::Id(*id),
@@ -202,6 +283,154 @@ impl WorkspaceTarget {
         let target_ctx = self.context();
         ctx.matches(&target_ctx.workspace)
     }
+
+    fn is_special(&self) -> bool {
+        matches!(self.kind, WorkspaceTargetKind::Special(_))
+    }
+
+    fn special_name(&self) -> Option<String> {
+        match &self.kind {
+            WorkspaceTargetKind::Special(name) => name.clone(),
+            _ => None,
+        }
+    }
+}
+
+/// Workspace of every open window, read with a single `Clients::get()` so the home list can
+/// show how many windows each workset's workspace holds.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct LiveWindowCounts {
+    workspaces: Vec<WorkspaceBasic>,
+}
+
+impl LiveWindowCounts {
+    /// `None` outside a Hyprland session or when the client list cannot be read.
+    pub(crate) fn fetch() -> Option<Self> {
+        ensure_hyprland_session().ok()?;
+        let clients = Clients::get().ok()?;
+        Some(Self {
+            workspaces: clients.iter().map(|c| c.workspace.clone()).collect(),
+        })
+    }
+
+    /// Windows on the workset's own workspace; `None` when it has none (unset or `current`).
+    pub(crate) fn for_workset(&self, ws: &Workset) -> Option<usize> {
+        let target = workspace_override(ws)?.context();
+        Some(
+            self.workspaces
+                .iter()
+                .filter(|workspace| target.matches(workspace))
+                .count(),
+        )
+    }
+}
+
+/// Switches to worksets' workspaces while the home list previews them
+/// (`preview_workspace_on_select`), remembering where the user started so `restore` can
+/// go back.
+#[derive(Debug, Default)]
+pub(crate) struct WorkspacePreview {
+    original: Option<WorkspaceBasic>,
+    shown: Option<WorkspaceTarget>,
+}
+
+impl WorkspacePreview {
+    /// Shows `ws`'s workspace. Worksets without their own workspace and special workspaces,
+    /// which would toggle an overlay, are left alone. Returns whether it switched.
+    pub(crate) fn show(&mut self, ws: &Workset) -> Result<bool> {
+        let Some(target) = workspace_override(ws).filter(|target| !target.is_special()) else {
+            return Ok(false);
+        };
+        if self.shown.as_ref() == Some(&target) {
+            return Ok(false);
+        }
+        ensure_hyprland_session()?;
+        if self.original.is_none() {
+            self.original = Some(focused_monitor()?.active_workspace);
+        }
+        Dispatch::call(DispatchType::Workspace(target.identifier()))
+            .with_context(|| format!("failed to preview {}", target.label()))?;
+        self.shown = Some(target);
+        Ok(true)
+    }
+
+    /// Goes back to the workspace that was active before the first preview, if any was shown.
+    pub(crate) fn restore(&mut self) -> Result<()> {
+        let (Some(original), Some(_)) = (self.original.take(), self.shown.take()) else {
+            return Ok(());
+        };
+        if original.id < 1 {
+            return Ok(());
+        }
+        Dispatch::call(DispatchType::Workspace(WorkspaceIdentifierWithSpecial::Id(
+            original.id,
+        )))
+        .with_context(|| format!("failed to return to workspace {}", original.name))
+    }
+}
+
+/// Checks a `workspace` value before it is saved and returns it trimmed. Anything that is
+/// not an id, `special[:name]` or `current` is a name, so only empty names and ids below 1
+/// are rejected.
+pub fn validate_workspace_value(raw: &str) -> Result<String> {
+    let value = raw.trim();
+    if value.is_empty() {
+        bail!("workspace must not be empty");
+    }
+    match WorkspaceTarget::from_raw(value).map(|target| target.kind) {
+        Some(WorkspaceTargetKind::Id(id)) if id < 1 => {
+            bail!("workspace ids start at 1 (got {id}); use special:<name> for special workspaces")
+        }
+        Some(WorkspaceTargetKind::Name(name)) if name.trim().is_empty() => {
+            bail!("workspace name after 'name:' must not be empty")
+        }
+        Some(WorkspaceTargetKind::Special(Some(name))) if name.trim().is_empty() => {
+            bail!("special workspace name must not be empty; use 'special' for the default one")
+        }
+        _ => Ok(value.to_string()),
+    }
+}
+
+/// Whether Hyprland has the workspace `value` points at right now. `None` for `current`,
+/// outside a Hyprland session or when the list cannot be read.
+pub fn workspace_exists(value: &str) -> Option<bool> {
+    let target = WorkspaceTarget::from_raw(value)?.context();
+    ensure_hyprland_session().ok()?;
+    let workspaces = Workspaces::get().ok()?;
+    Some(workspaces.iter().any(|workspace| {
+        target.matches(&WorkspaceBasic {
+            id: workspace.id,
+            name: workspace.name.clone(),
+        })
+    }))
+}
+
+/// The workspace that is active right now, from a single query of the focused monitor.
+pub(crate) fn active_workspace() -> Result<WorkspaceContext> {
+    ensure_hyprland_session()?;
+    active_workspace_light()
+}
+
+/// Index of the first workset whose own `workspace` is `active`. Worksets without one, or set
+/// to `current`, follow whatever is focused and never match.
+pub(crate) fn workset_for_workspace(
+    worksets: &[Workset],
+    active: &WorkspaceContext,
+) -> Option<usize> {
+    worksets
+        .iter()
+        .position(|ws| workspace_override(ws).is_some_and(|target| target.matches(active)))
+}
+
+/// Marks special workspaces in the home list (`workspace_icons`).
+pub(crate) const SPECIAL_WORKSPACE_GLYPH: &str = "★";
+
+/// Glyph the home list puts before a `workspace` value: [`SPECIAL_WORKSPACE_GLYPH`] for
+/// special workspaces, `None` for regular ones and `current`.
+pub(crate) fn workspace_glyph(raw: &str) -> Option<&'static str> {
+    WorkspaceTarget::from_raw(raw)
+        .filter(WorkspaceTarget::is_special)
+        .map(|_| SPECIAL_WORKSPACE_GLYPH)
 }
 
 fn workspace_override(ws: &Workset) -> Option<WorkspaceTarget> {
@@ -209,7 +438,16 @@ fn workspace_override(ws: &Workset) -> Option<WorkspaceTarget> {
         .as_deref()
         .map(str::trim)
         .filter(|v| !v.is_empty())
-        .map(WorkspaceTarget::from_raw)
+        .and_then(WorkspaceTarget::from_raw)
+}
+
+/// Workspace a launch targets: the `--workspace` flag wins over the workset's `workspace`.
+/// `--workspace current` launches on the active workspace even when the workset names one.
+pub(super) fn launch_target(ws: &Workset, workspace: Option<&str>) -> Option<WorkspaceTarget> {
+    match workspace.map(str::trim).filter(|v| !v.is_empty()) {
+        Some(raw) => WorkspaceTarget::from_raw(raw),
+        None => workspace_override(ws),
+    }
 }
 
 fn resolve_active_workspace(verbose: bool) -> Result<(WorkspaceContext, Clients)> {
@@ -218,7 +456,7 @@ fn resolve_active_workspace(verbose: bool) -> Result<(WorkspaceContext, Clients)
     if let Some(focused) = active_client {
         let ctx = WorkspaceContext::from_basic(focused.workspace);
         if verbose {
-            println!(
+            status!(
                 " active workspace determined from active window: {}",
                 ctx.label()
             );
@@ -226,38 +464,71 @@ fn resolve_active_workspace(verbose: bool) -> Result<(WorkspaceContext, Clients)
         return Ok((ctx, clients));
     }
 
-    let monitor = Monitor::get_active().context("failed to get active monitor from Hyprland")?;
-    let special_ctx = WorkspaceContext::from_basic(monitor.special_workspace.clone());
-    let special_active =
-        special_ctx.workspace.id != 0 || !special_ctx.workspace.name.trim().is_empty();
-    if special_active {
-        if verbose {
-            println!(
+    let (ctx, special) = monitor_workspace(&focused_monitor()?);
+    if verbose {
+        if special {
+            status!(
                 " active special workspace on focused monitor: {}",
-                special_ctx.label()
+                ctx.label()
             );
+        } else {
+            status!(" active workspace: {}", ctx.label());
         }
-        return Ok((special_ctx, clients));
     }
+    Ok((ctx, clients))
+}
 
-    let ctx = WorkspaceContext::from_basic(monitor.active_workspace.clone());
-    if verbose {
-        println!(" active workspace: {}", ctx.label());
+/// Active workspace read from the focused monitor alone. Polling loops only compare it with a
+/// target, so one IPC call per poll replaces the client list plus active window lookup of
+/// [`resolve_active_workspace`]; the focused monitor shows the same workspace either way.
+fn active_workspace_light() -> Result<WorkspaceContext> {
+    Ok(monitor_workspace(&focused_monitor()?).0)
+}
+
+fn focused_monitor() -> Result<Monitor> {
+    match Monitors::get()
+        .ok()
+        .and_then(|m| pick_focused(m.to_vec(), |m| m.focused))
+    {
+        Some(monitor) => Ok(monitor),
+        None => Monitor::get_active().context("failed to get active monitor from Hyprland"),
     }
-    Ok((ctx, clients))
+}
+
+/// The monitor's open special workspace if it has one, else its regular active workspace.
+/// The flag tells which one it was.
+fn monitor_workspace(monitor: &Monitor) -> (WorkspaceContext, bool) {
+    let special = &monitor.special_workspace;
+    if special.id != 0 || !special.name.trim().is_empty() {
+        (WorkspaceContext::from_basic(special.clone()), true)
+    } else {
+        (
+            WorkspaceContext::from_basic(monitor.active_workspace.clone()),
+            false,
+        )
+    }
+}
+
+/// The monitor flagged `focused` among all of them. `Monitor::get_active` can name another
+/// monitor than the one holding the cursor on multi-monitor setups.
+fn pick_focused<M>(monitors: Vec<M>, focused: impl Fn(&M) -> bool) -> Option<M> {
+    monitors.into_iter().find(|m| focused(m))
 }
 
 fn ensure_target_active(target: &WorkspaceTarget, verbose: bool) -> Result<WorkspaceContext> {
-    let (current_ctx, _) = resolve_active_workspace(false)?;
+    let current_ctx = active_workspace_light()?;
     if target.matches(&current_ctx) {
         if verbose {
-            println!(" workspace override already active: {}", target.label());
+            status!(" workspace override already active: {}", target.label());
         }
         return Ok(current_ctx);
     }
+    switch_to_target(target, verbose)
+}
 
+fn switch_to_target(target: &WorkspaceTarget, verbose: bool) -> Result<WorkspaceContext> {
     if verbose {
-        println!(" switching to {} before launch...", target.label());
+        status!(" switching to {} before launch...", target.label());
     }
     Dispatch::call(DispatchType::Workspace(target.identifier()))
         .with_context(|| format!("failed to switch to {}", target.label()))?;
@@ -266,18 +537,23 @@ fn ensure_target_active(target: &WorkspaceTarget, verbose: bool) -> Result<Works
 }
 
 pub(crate) fn ensure_workspace_focus(target: &WorkspaceTarget, verbose: bool) -> Result<()> {
-    let (current_ctx, _) = resolve_active_workspace(false)?;
-    if target.matches(&current_ctx) {
+    if target.matches(&active_workspace_light()?) {
         return Ok(());
     }
-    ensure_target_active(target, verbose).map(|_| ())
+    switch_to_target(target, verbose).map(|_| ())
 }
 
 pub(crate) fn resolve_launch_workspace(
     ws: &Workset,
     verbose: bool,
+    launch_here: bool,
+    workspace: Option<&str>,
 ) -> Result<(WorkspaceTarget, WorkspaceContext)> {
-    if let Some(target) = workspace_override(ws) {
+    if launch_here {
+        if verbose {
+            status!(" launching on the active workspace (configured workspace ignored)");
+        }
+    } else if let Some(target) = launch_target(ws, workspace) {
         let ctx = ensure_target_active(&target, verbose)?;
         return Ok((target, ctx));
     }
@@ -287,60 +563,279 @@ pub(crate) fn resolve_launch_workspace(
     Ok((target, ctx))
 }
 
+/// Polls until `target` is active. The first `Workspace` dispatch is occasionally dropped on
+/// a cold compositor, so it is re-issued once halfway through the timeout.
 fn wait_for_target_workspace(target: &WorkspaceTarget, verbose: bool) -> Result<WorkspaceContext> {
+    poll_target_workspace(
+        target,
+        verbose,
+        &mut active_workspace_light,
+        &mut || {
+            Dispatch::call(DispatchType::Workspace(target.identifier()))
+                .with_context(|| format!("failed to switch to {}", target.label()))
+        },
+        WORKSPACE_SWITCH_TIMEOUT,
+    )
+}
+
+/// One `query` per poll; `redispatch` is the single retry of the switch.
+fn poll_target_workspace(
+    target: &WorkspaceTarget,
+    verbose: bool,
+    query: &mut dyn FnMut() -> Result<WorkspaceContext>,
+    redispatch: &mut dyn FnMut() -> Result<()>,
+    timeout: Duration,
+) -> Result<WorkspaceContext> {
     let expected = target.context();
-    let deadline = Instant::now() + WORKSPACE_SWITCH_TIMEOUT;
+    let start = Instant::now();
+    let retry_at = start + timeout / 2;
+    let deadline = start + timeout;
+    let mut retried = false;
     loop {
-        let (ctx, _) = resolve_active_workspace(false)?;
+        let ctx = query()?;
         if ctx.matches(&expected.workspace) {
             if verbose {
-                println!(" active workspace is now {}", target.label());
+                status!(" active workspace is now {}", target.label());
             }
             return Ok(ctx);
         }
-        if Instant::now() >= deadline {
+        let now = Instant::now();
+        if now >= deadline {
             bail!(
                 "timed out after {:?} waiting for {}",
-                WORKSPACE_SWITCH_TIMEOUT,
+                timeout,
                 target.label()
             );
         }
+        if !retried && now >= retry_at {
+            retried = true;
+            if verbose {
+                status!(
+                    " {} still not active after {:?}; retrying switch",
+                    target.label(),
+                    now - start
+                );
+            }
+            redispatch()?;
+        }
         thread::sleep(WINDOW_POLL_INTERVAL);
     }
 }
 
-pub fn run_workset(ws: &Workset, verbose: bool, preconfirm_clean: bool) -> Result<()> {
+/// A scratchpad workset is considered launched when its special workspace already holds at
+/// least as many windows as the workset opens (one per layout slot, or one per command),
+/// and at least one. In that case running it only toggles the special workspace.
+pub fn scratchpad_toggle_ready(ws: &Workset) -> Result<bool> {
+    if !ws.scratchpad {
+        return Ok(false);
+    }
+    let Some(target) = workspace_override(ws).filter(WorkspaceTarget::is_special) else {
+        return Ok(false);
+    };
+    let context = target.context();
+    let clients = Clients::get().context("failed to list Hyprland clients")?;
+    let present = clients
+        .iter()
+        .filter(|c| context.matches(&c.workspace))
+        .count();
+    Ok(present > 0 && present >= expected_window_count(ws))
+}
+
+fn toggle_scratchpad(ws: &Workset, verbose: bool) -> Result<()> {
+    let name = workspace_override(ws).and_then(|target| target.special_name());
+    if verbose {
+        status!(
+            " scratchpad '{}' already running; toggling special workspace {}",
+            ws.id,
+            name.as_deref().unwrap_or("(default)")
+        );
+    }
+    Dispatch::call(DispatchType::ToggleSpecialWorkspace(name))
+        .context("failed to toggle special workspace")?;
+    status!("toggled scratchpad workset '{}'", ws.name);
+    Ok(())
+}
+
+/// Launch settings that come from the caller rather than the workset: the `run` flags, or
+/// what the user picked in the TUI.
+#[derive(Debug, Clone, Default)]
+pub struct RunOptions {
+    pub verbose: bool,
+    /// Launch on the active workspace instead of the workset's.
+    pub launch_here: bool,
+    /// Overrides the workset's configured workspace.
+    pub workspace: Option<String>,
+    pub capture: Capture,
+    /// Only launch slots whose window is not on the workspace yet.
+    pub fill: bool,
+}
+
+pub fn run_workset(ws: &Workset, cleanup: CleanupMode, opts: &RunOptions) -> Result<()> {
+    let RunOptions {
+        verbose,
+        launch_here,
+        ..
+    } = *opts;
+    let workspace = opts.workspace.as_deref();
+    ensure_hyprland_session()?;
     let _launch_lock = acquire_launch_lock(verbose)?;
-    let (workspace_target, workspace_ctx) = resolve_launch_workspace(ws, verbose)?;
+    let clock = Instant::now;
+    let budget = LaunchBudget::new(ws.total_timeout(), &clock);
+    if ws.scratchpad && !launch_here && workspace.is_none() {
+        if workspace_override(ws).is_none_or(|target| !target.is_special()) {
+            eprintln!(
+                "warning: workset '{}' is a scratchpad but does not target a special workspace; launching normally",
+                ws.id
+            );
+        } else if scratchpad_toggle_ready(ws)? {
+            return toggle_scratchpad(ws, verbose);
+        }
+    }
+    if effective_command_count(ws) == 0 {
+        status!("workset '{}' has no commands to launch", ws.id);
+        // Only an explicit `close` still empties the workspace; asking first would be noise.
+        if cleanup == CleanupMode::Close {
+            let (_, workspace_ctx) = resolve_launch_workspace(ws, verbose, launch_here, workspace)?;
+            clean_workspace(&workspace_ctx, verbose, cleanup)
+                .context("failed to clean target workspace")?;
+        }
+        return Ok(());
+    }
+    if let Some(warning) = scope_warning(ws) {
+        eprintln!("warning: {warning}");
+    }
+    let origin = if ws.switch_focus {
+        None
+    } else {
+        let (ctx, _) = resolve_active_workspace(false)?;
+        Some(WorkspaceTarget::from_context(&ctx))
+    };
+    let result = launch_workset(ws, cleanup, opts, &budget);
+    if let Some(origin) = origin {
+        restore_focus(&origin, verbose);
+    }
+    result
+}
 
-    match clean_workspace(&workspace_ctx, verbose, preconfirm_clean)
+fn launch_workset(
+    ws: &Workset,
+    cleanup: CleanupMode,
+    opts: &RunOptions,
+    budget: &LaunchBudget,
+) -> Result<()> {
+    let RunOptions {
+        verbose,
+        launch_here,
+        capture,
+        fill,
+        ..
+    } = *opts;
+    let (workspace_target, workspace_ctx) =
+        resolve_launch_workspace(ws, verbose, launch_here, opts.workspace.as_deref())?;
+
+    // Filling in keeps what is already open; cleaning first would defeat the point.
+    let cleanup = if fill && ws.layout.is_some() {
+        CleanupMode::Keep
+    } else {
+        if fill {
+            eprintln!(
+                "warning: --fill needs a layout with slot classes; launching workset '{}' normally",
+                ws.id
+            );
+        }
+        cleanup
+    };
+    match clean_workspace(&workspace_ctx, verbose, cleanup)
         .context("failed to clean target workspace before launch")?
     {
         WorkspaceCleanAction::Proceed => {}
         WorkspaceCleanAction::Cancelled => {
-            println!("Launch cancelled: existing windows were kept open.");
+            status!("Launch cancelled: existing windows were kept open.");
             return Ok(());
         }
     }
 
-    if let Some(layout) = &ws.layout {
-        println!("launching workset '{}' with layout...", ws.name);
-        run_layout(layout, ws, verbose, &workspace_ctx, &workspace_target)
-            .with_context(|| format!("failed to launch layout (id: {})", ws.id))?;
+    let started = Instant::now();
+    let mut launched = 0usize;
+    let result = if let Some(layout) = &ws.layout {
+        status!("launching workset '{}' with layout...", ws.name);
+        run_layout(
+            layout,
+            ws,
+            verbose,
+            &workspace_ctx,
+            &workspace_target,
+            &mut launched,
+            capture,
+            fill,
+            budget,
+        )
+        .with_context(|| format!("failed to launch layout (id: {})", ws.id))
     } else {
-        println!("launching workset '{}' (commands sequential)...", ws.name);
-        run_commands(ws, verbose, &workspace_target)
-            .with_context(|| format!("failed to run commands (id: {})", ws.id))?;
+        let mode = if ws.parallel {
+            "parallel"
+        } else {
+            "sequential"
+        };
+        status!("launching workset '{}' (commands {mode})...", ws.name);
+        run_commands(
+            ws,
+            verbose,
+            &workspace_target,
+            &mut launched,
+            capture,
+            budget,
+        )
+        .with_context(|| format!("failed to run commands (id: {})", ws.id))
+    };
+    status!(
+        "{}",
+        launch_summary(
+            launched,
+            expected_window_count(ws),
+            &workspace_ctx.label(),
+            started.elapsed(),
+            result.is_ok(),
+        )
+    );
+    result
+}
+
+/// Returns to the workspace that was active before a `switch_focus = false` launch. A failure
+/// here only warns: the windows are already open.
+fn restore_focus(origin: &WorkspaceTarget, verbose: bool) {
+    if verbose {
+        status!(" returning focus to {}", origin.label());
+    }
+    if let Err(err) = ensure_workspace_focus(origin, false) {
+        eprintln!("warning: failed to return to {}: {err:#}", origin.label());
+    }
+}
+
+/// One-line closing report for a launch; on failure it names how far the launch got so a
+/// timed-out slot stands out.
+fn launch_summary(
+    launched: usize,
+    expected: usize,
+    workspace: &str,
+    elapsed: Duration,
+    succeeded: bool,
+) -> String {
+    let secs = elapsed.as_secs_f32();
+    if succeeded {
+        format!("launched {launched} window(s) on {workspace} in {secs:.1}s")
+    } else {
+        format!("launch stopped after {launched}/{expected} window(s) on {workspace} in {secs:.1}s")
     }
-    Ok(())
 }
 
 pub fn workspace_cleanup_status(target: Option<&Workset>) -> Result<WorkspaceCleanupStatus> {
+    ensure_hyprland_session()?;
     let state = if let Some(ws) = target {
         if let Some(target_workspace) = workspace_override(ws) {
             let context = target_workspace.context();
             let clients = Clients::get().context("failed to list Hyprland clients")?;
-            collect_workspace_state_with_clients(context, clients, false)?
+            collect_workspace_state_with_clients(context, &clients, false)?
         } else {
             collect_active_workspace_state(false)?
         }
@@ -348,33 +843,45 @@ pub fn workspace_cleanup_status(target: Option<&Workset>) -> Result<WorkspaceCle
         collect_active_workspace_state(false)?
     };
 
-    Ok(WorkspaceCleanupStatus {
-        workspace_name: state.context.workspace.name.clone(),
-        closable_windows: state.candidates.len(),
-    })
+    Ok(WorkspaceCleanupStatus::from_candidates(
+        &state.context,
+        &state.candidates,
+    ))
 }
 
 fn clean_workspace(
     target_context: &WorkspaceContext,
     verbose: bool,
-    preconfirm: bool,
+    cleanup: CleanupMode,
 ) -> Result<WorkspaceCleanAction> {
+    if cleanup == CleanupMode::Keep {
+        if verbose {
+            status!(
+                "closing skipped: cleanup = keep for {}",
+                target_context.label()
+            );
+        }
+        return Ok(WorkspaceCleanAction::Proceed);
+    }
     let state = collect_workspace_state(target_context.clone(), verbose)?;
     let label = state.context.label();
 
     if state.candidates.is_empty() {
         if verbose {
-            println!("closing skipped: {} had no windows", label);
+            status!("closing skipped: {} had no windows", label);
         }
         return Ok(WorkspaceCleanAction::Proceed);
     }
 
-    if !preconfirm {
+    if cleanup == CleanupMode::Ask {
         println!(
             "{} has {} window(s). Close all before launching the workset? [y/N]",
             label,
             state.candidates.len()
         );
+        if state.context.is_special {
+            println!("warning: {SPECIAL_WORKSPACE_WARNING}");
+        }
         print!("> ");
         io::stdout().flush().context("failed to flush stdout")?;
 
@@ -388,11 +895,19 @@ fn clean_workspace(
         }
     }
 
+    let closed = close_workspace_windows(state, verbose)?;
+    status!("closed {closed} window(s) on {} before launch", label);
+    Ok(WorkspaceCleanAction::Proceed)
+}
+
+/// Close every candidate window collected for a workspace and wait until they are gone.
+fn close_workspace_windows(state: ActiveWorkspaceState, verbose: bool) -> Result<usize> {
+    let label = state.context.label();
     let expected_remaining = state.initial_clients.saturating_sub(state.candidates.len());
     let mut closed = 0usize;
     for c in state.candidates {
         if verbose {
-            println!(
+            status!(
                 " closing: {} ({})",
                 c.class,
                 if c.title.is_empty() {
@@ -412,8 +927,57 @@ fn clean_workspace(
         wait_for_clients_at_most(&state.context, expected_remaining, verbose)
             .with_context(|| format!("failed to wait for windows to close on {}", label))?;
     }
-    println!("closed {closed} window(s) on {} before launch", label);
-    Ok(WorkspaceCleanAction::Proceed)
+    Ok(closed)
+}
+
+/// Close windows on every workspace targeted by `worksets`, cleaning each workspace once.
+/// Worksets without a configured workspace resolve to the active workspace.
+pub fn clean_workset_workspaces(worksets: &[&Workset], verbose: bool) -> Result<usize> {
+    ensure_hyprland_session()?;
+    let _launch_lock = acquire_launch_lock(verbose)?;
+    let clients = Clients::get().context("failed to list Hyprland clients")?;
+    let mut active_ctx: Option<WorkspaceContext> = None;
+    let mut cleaned: HashSet<String> = HashSet::new();
+    let mut total_closed = 0usize;
+
+    for ws in worksets {
+        let context = match workspace_override(ws) {
+            Some(target) => target.context(),
+            None => match &active_ctx {
+                Some(ctx) => ctx.clone(),
+                None => {
+                    let (ctx, _) = resolve_active_workspace(verbose)
+                        .context("failed to resolve active workspace context")?;
+                    active_ctx = Some(ctx.clone());
+                    ctx
+                }
+            },
+        };
+        let label = context.label();
+        if !cleaned.insert(label.clone()) {
+            if verbose {
+                status!(" {label} already cleaned; skipping workset '{}'", ws.id);
+            }
+            continue;
+        }
+
+        let state = collect_workspace_state_with_clients(context, &clients, verbose)?;
+        if state.candidates.is_empty() {
+            if verbose {
+                status!("closing skipped: {label} had no windows");
+            }
+            continue;
+        }
+        let closed = close_workspace_windows(state, verbose)?;
+        status!("closed {closed} window(s) on {label}");
+        total_closed += closed;
+    }
+
+    status!(
+        "cleaned {} workspace(s), closed {total_closed} window(s) in total",
+        cleaned.len()
+    );
+    Ok(total_closed)
 }
 
 fn collect_workspace_state(
@@ -421,18 +985,18 @@ fn collect_workspace_state(
     verbose: bool,
 ) -> Result<ActiveWorkspaceState> {
     let clients = Clients::get().context("failed to list Hyprland clients")?;
-    collect_workspace_state_with_clients(context, clients, verbose)
+    collect_workspace_state_with_clients(context, &clients, verbose)
 }
 
 fn collect_active_workspace_state(verbose: bool) -> Result<ActiveWorkspaceState> {
     let (context, clients) =
         resolve_active_workspace(verbose).context("failed to resolve active workspace context")?;
-    collect_workspace_state_with_clients(context, clients, verbose)
+    collect_workspace_state_with_clients(context, &clients, verbose)
 }
 
 fn collect_workspace_state_with_clients(
     context: WorkspaceContext,
-    clients: Clients,
+    clients: &Clients,
     verbose: bool,
 ) -> Result<ActiveWorkspaceState> {
     let self_pid = std::process::id() as i32;
@@ -449,7 +1013,7 @@ fn collect_workspace_state_with_clients(
     {
         if ancestors.contains(&c.pid) {
             if verbose {
-                println!(" skipping self/parent window: {} ({})", c.class, c.pid);
+                status!(" skipping self/parent window: {} ({})", c.class, c.pid);
             }
             continue;
         }
@@ -457,6 +1021,8 @@ fn collect_workspace_state_with_clients(
             address: c.address.clone(),
             class: c.class.clone(),
             title: c.title.clone(),
+            floating: c.floating,
+            pinned: c.pinned,
         });
     }
 
@@ -479,7 +1045,7 @@ fn wait_for_clients_at_most(workspace: &WorkspaceContext, max: usize, verbose: b
             .count();
         if count <= max {
             if verbose {
-                println!(" {}: clients drained ({}/{})", label, count, max);
+                status!(" {}: clients drained ({}/{})", label, count, max);
             }
             return Ok(());
         }
@@ -493,9 +1059,11 @@ fn wait_for_clients_at_most(workspace: &WorkspaceContext, max: usize, verbose: b
             );
         }
         if verbose && first_log {
-            println!(
+            status!(
                 " waiting for windows to close... {}: {}/{}",
-                label, count, max
+                label,
+                count,
+                max
             );
             first_log = false;
         }
@@ -547,7 +1115,6 @@ fn collect_ancestor_pids() -> Result<HashSet<i32>> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::HashMap;
 
     fn ctx(id: i32, name: &str) -> WorkspaceContext {
         WorkspaceContext::from_basic(WorkspaceBasic {
@@ -558,23 +1125,63 @@ mod tests {
 
     #[test]
     fn workspace_target_from_raw_parses_variants() {
-        let t1 = WorkspaceTarget::from_raw("special:foo");
+        let t1 = WorkspaceTarget::from_raw("special:foo").unwrap();
         assert!(matches!(t1.kind, WorkspaceTargetKind::Special(Some(_))));
         let ctx_special = t1.context();
         assert!(ctx_special.is_special);
         assert!(t1.matches(&ctx_special));
 
-        let t2 = WorkspaceTarget::from_raw("name:work");
+        let t2 = WorkspaceTarget::from_raw("name:work").unwrap();
         assert!(matches!(t2.kind, WorkspaceTargetKind::Name(_)));
         let ctx_named = ctx(1, "work");
         assert!(t2.matches(&ctx_named));
 
-        let t3 = WorkspaceTarget::from_raw("3");
+        let t3 = WorkspaceTarget::from_raw("3").unwrap();
         assert!(matches!(t3.kind, WorkspaceTargetKind::Id(3)));
         let ctx_numeric = ctx(3, "");
         assert!(t3.matches(&ctx_numeric));
     }
 
+    #[test]
+    fn current_keyword_targets_the_active_workspace() {
+        assert_eq!(WorkspaceTarget::from_raw(" Current "), None);
+        let mut ws = Workset {
+            id: "id".into(),
+            name: "name".into(),
+            desc: "".into(),
+            workspace: Some("current".into()),
+            ..Default::default()
+        };
+        assert_eq!(launch_target(&ws, None), None);
+        ws.workspace = Some("3".into());
+        assert_eq!(launch_target(&ws, Some("current")), None);
+        assert_eq!(
+            launch_target(&ws, None),
+            WorkspaceTarget::from_raw("3"),
+            "an unset flag still falls back to the workset"
+        );
+    }
+
+    #[test]
+    fn slot_workspace_switches_focus_only_when_it_differs() {
+        let slot: WindowSlot =
+            toml::from_str("slot_id = 2\ncommand = \"discord\"\nworkspace = \"2\"").unwrap();
+        assert_eq!(slot.workspace.as_deref(), Some("2"));
+
+        let main = WorkspaceTarget::from_raw("1").unwrap();
+        let target = WorkspaceTarget::for_slot(&slot, &main).unwrap();
+        assert_eq!(target, WorkspaceTarget::from_raw("2").unwrap());
+        assert_eq!(target.label(), "workspace 2");
+
+        let same = WorkspaceTarget::from_raw("2").unwrap();
+        assert!(WorkspaceTarget::for_slot(&slot, &same).is_none());
+        let plain = WindowSlot {
+            workspace: None,
+            ..slot
+        };
+        assert!(WorkspaceTarget::for_slot(&plain, &main).is_none());
+    }
+
     #[test]
     fn workspace_override_reads_workset_field() {
         let ws = Workset {
@@ -582,15 +1189,130 @@ mod tests {
             name: "name".into(),
             desc: "".into(),
             workspace: Some("special:test".into()),
-            commands: vec![],
-            cwd: None,
-            env: HashMap::new(),
-            layout: None,
+            ..Default::default()
         };
         let target = workspace_override(&ws).expect("should parse override");
         assert!(matches!(target.kind, WorkspaceTargetKind::Special(Some(ref n)) if n == "test"));
     }
 
+    #[test]
+    fn workspace_poll_queries_once_per_iteration() {
+        let target = WorkspaceTarget::from_raw("4").unwrap();
+        let mut queries = 0;
+        let mut redispatches = 0;
+        let found = poll_target_workspace(
+            &target,
+            false,
+            &mut || {
+                queries += 1;
+                Ok(if queries < 3 {
+                    ctx(2, "2")
+                } else {
+                    ctx(4, "4")
+                })
+            },
+            &mut || {
+                redispatches += 1;
+                Ok(())
+            },
+            Duration::from_secs(5),
+        )
+        .unwrap();
+        assert_eq!(found.workspace.id, 4);
+        assert_eq!((queries, redispatches), (3, 0));
+
+        let mut queries = 0;
+        let err = poll_target_workspace(
+            &target,
+            false,
+            &mut || {
+                queries += 1;
+                Ok(ctx(2, "2"))
+            },
+            &mut || {
+                redispatches += 1;
+                Ok(())
+            },
+            Duration::ZERO,
+        )
+        .unwrap_err();
+        assert!(err.to_string().starts_with("timed out"));
+        assert_eq!((queries, redispatches), (1, 0));
+    }
+
+    #[test]
+    fn pick_focused_prefers_the_flagged_monitor() {
+        let monitors = vec![("DP-1", false), ("HDMI-A-1", true), ("eDP-1", false)];
+        assert_eq!(
+            pick_focused(monitors, |(_, focused)| *focused),
+            Some(("HDMI-A-1", true))
+        );
+        let none_focused = vec![("DP-1", false), ("eDP-1", false)];
+        assert_eq!(pick_focused(none_focused, |(_, focused)| *focused), None);
+    }
+
+    #[test]
+    fn live_window_counts_match_by_id_or_name() {
+        let basic = |id, name: &str| WorkspaceBasic {
+            id,
+            name: name.to_string(),
+        };
+        let counts = LiveWindowCounts {
+            workspaces: vec![
+                basic(3, "3"),
+                basic(3, "3"),
+                basic(5, "chat"),
+                basic(1, "1"),
+            ],
+        };
+        let mut ws = Workset {
+            id: "id".into(),
+            name: "name".into(),
+            desc: "".into(),
+            workspace: Some("3".into()),
+            ..Default::default()
+        };
+        assert_eq!(counts.for_workset(&ws), Some(2));
+        ws.workspace = Some("name:chat".into());
+        assert_eq!(counts.for_workset(&ws), Some(1));
+        ws.workspace = Some("7".into());
+        assert_eq!(counts.for_workset(&ws), Some(0));
+        ws.workspace = Some("current".into());
+        assert_eq!(counts.for_workset(&ws), None);
+        ws.workspace = None;
+        assert_eq!(counts.for_workset(&ws), None);
+    }
+
+    #[test]
+    fn launch_summary_reports_success_and_partial_launches() {
+        let elapsed = Duration::from_millis(6_240);
+        assert_eq!(
+            launch_summary(5, 5, "workspace work (id 3)", elapsed, true),
+            "launched 5 window(s) on workspace work (id 3) in 6.2s"
+        );
+        assert_eq!(
+            launch_summary(2, 4, "workspace 1", elapsed, false),
+            "launch stopped after 2/4 window(s) on workspace 1 in 6.2s"
+        );
+    }
+
+    #[test]
+    fn launch_target_prefers_cli_workspace_over_workset() {
+        let ws = Workset {
+            id: "id".into(),
+            name: "name".into(),
+            desc: "".into(),
+            workspace: Some("3".into()),
+            ..Default::default()
+        };
+        let target = launch_target(&ws, Some("5")).expect("flag target");
+        assert!(matches!(target.kind, WorkspaceTargetKind::Id(5)));
+        let target = launch_target(&ws, Some("  ")).expect("configured target");
+        assert!(matches!(target.kind, WorkspaceTargetKind::Id(3)));
+        let target = launch_target(&ws, None).expect("configured target");
+        assert!(matches!(target.kind, WorkspaceTargetKind::Id(3)));
+    }
+
     #[test]
     fn workspace_context_label_includes_id_or_name() {
         let unnamed = ctx(4, "");
@@ -616,4 +1338,75 @@ mod tests {
             name: "beta".into(),
         }));
     }
+
+    #[test]
+    fn workspace_target_special_name_only_for_special() {
+        let named = WorkspaceTarget::from_raw("special:term").unwrap();
+        assert!(named.is_special());
+        assert_eq!(named.special_name().as_deref(), Some("term"));
+
+        let default_special = WorkspaceTarget::from_raw("special").unwrap();
+        assert!(default_special.is_special());
+        assert_eq!(default_special.special_name(), None);
+
+        let regular = WorkspaceTarget::from_raw("3").unwrap();
+        assert!(!regular.is_special());
+        assert_eq!(regular.special_name(), None);
+    }
+
+    #[test]
+    fn cleanup_status_splits_tiled_and_floating() {
+        let candidate = |floating, pinned| CloseCandidate {
+            address: Address::new("0x1"),
+            class: String::new(),
+            title: String::new(),
+            floating,
+            pinned,
+        };
+        let status = WorkspaceCleanupStatus::from_candidates(
+            &ctx(3, "work"),
+            &[
+                candidate(false, false),
+                candidate(false, false),
+                candidate(true, true),
+            ],
+        );
+        assert_eq!(status.closable_windows, 3);
+        assert_eq!(status.breakdown(), "2 tiled, 1 floating (1 pinned)");
+        assert!(!status.is_special);
+
+        let special = WorkspaceTarget::from_raw("special:term").unwrap().context();
+        let tiled = WorkspaceCleanupStatus::from_candidates(&special, &[candidate(false, false)]);
+        assert_eq!(tiled.breakdown(), "1 tiled, 0 floating");
+        assert!(tiled.is_special);
+    }
+
+    #[test]
+    fn workset_for_workspace_matches_ids_and_names() {
+        let with_workspace = |id: &str, workspace: Option<&str>| {
+            let mut ws = Workset::blank(id, id, "");
+            ws.workspace = workspace.map(str::to_string);
+            ws
+        };
+        let worksets = vec![
+            with_workspace("none", None),
+            with_workspace("here", Some("current")),
+            with_workspace("two", Some("2")),
+            with_workspace("mail", Some("name:mail")),
+            with_workspace("two-again", Some(" 2 ")),
+        ];
+        assert_eq!(workset_for_workspace(&worksets, &ctx(2, "2")), Some(2));
+        assert_eq!(workset_for_workspace(&worksets, &ctx(7, "mail")), Some(3));
+        assert_eq!(workset_for_workspace(&worksets, &ctx(3, "3")), None);
+    }
+
+    #[test]
+    fn workspace_glyph_marks_only_special_workspaces() {
+        assert_eq!(workspace_glyph("special"), Some("★"));
+        assert_eq!(workspace_glyph(" special:term "), Some("★"));
+        assert_eq!(workspace_glyph("2"), None);
+        assert_eq!(workspace_glyph("name:special"), None);
+        assert_eq!(workspace_glyph("current"), None);
+        assert_eq!(workspace_glyph(""), None);
+    }
 }
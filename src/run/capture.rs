@@ -0,0 +1,156 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    thread,
+    time::Instant,
+};
+
+use anyhow::{Context, Result, bail};
+
+use crate::output::status;
+
+use super::{SLOT_LAUNCH_DELAY, WINDOW_POLL_INTERVAL, util::spawn_detached};
+
+/// How launch commands start: through Hyprland's `exec` dispatcher, or with `--capture`
+/// through `sh -c` with their output logged to files.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Capture {
+    #[default]
+    Off,
+    /// `detach` starts each command in its own process group (`detach_commands`).
+    On { detach: bool },
+}
+
+impl Capture {
+    pub fn new(enabled: bool, detach: bool) -> Self {
+        if enabled {
+            Self::On { detach }
+        } else {
+            Self::Off
+        }
+    }
+}
+
+/// Lines of captured output shown when a command fails.
+const CAPTURE_PREVIEW_LINES: usize = 5;
+
+/// Output goes to files rather than pipes so GUI apps that outlive hyprsets keep running
+/// instead of dying on a closed pipe.
+pub(crate) fn capture_dir() -> PathBuf {
+    if let Some(runtime) = env::var_os("XDG_RUNTIME_DIR").filter(|v| !v.is_empty()) {
+        return PathBuf::from(runtime).join("hyprsets-capture");
+    }
+    env::temp_dir().join("hyprsets-capture")
+}
+
+/// Runs `exec` through `sh -c` instead of Hyprland's `exec` dispatcher and waits briefly for it,
+/// logging its output to `<label>.out`/`.err` in `dir`. A command that exits non-zero within the
+/// wait fails with the head of its stderr; one that is still running is left alone.
+pub(crate) fn spawn_captured(
+    exec: &str,
+    label: &str,
+    dir: &Path,
+    detach: bool,
+    verbose: bool,
+) -> Result<()> {
+    fs::create_dir_all(dir)
+        .with_context(|| format!("failed to create capture dir: {}", dir.display()))?;
+    let label = label.replace('/', "_");
+    let stdout_path = dir.join(format!("{label}.out"));
+    let stderr_path = dir.join(format!("{label}.err"));
+    let stdout = fs::File::create(&stdout_path)
+        .with_context(|| format!("failed to create {}", stdout_path.display()))?;
+    let stderr = fs::File::create(&stderr_path)
+        .with_context(|| format!("failed to create {}", stderr_path.display()))?;
+
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c")
+        .arg(exec)
+        .stdin(Stdio::null())
+        .stdout(stdout)
+        .stderr(stderr);
+    let mut child =
+        spawn_detached(&mut cmd, detach).with_context(|| format!("failed to spawn: {exec}"))?;
+
+    let started = Instant::now();
+    while started.elapsed() < SLOT_LAUNCH_DELAY {
+        if let Some(status) = child
+            .try_wait()
+            .context("failed to poll captured command")?
+        {
+            if !status.success() {
+                bail!(
+                    "command exited with {status}{}",
+                    preview_section("stderr", &stderr_path)
+                );
+            }
+            if verbose {
+                status!(
+                    " captured command exited successfully{}",
+                    preview_section("stdout", &stdout_path)
+                );
+            }
+            return Ok(());
+        }
+        thread::sleep(WINDOW_POLL_INTERVAL);
+    }
+    if verbose {
+        status!(
+            " captured command still running (pid {}); output in {}",
+            child.id(),
+            dir.display()
+        );
+    }
+    Ok(())
+}
+
+fn preview_section(stream: &str, path: &Path) -> String {
+    let raw = fs::read_to_string(path).unwrap_or_default();
+    let preview = first_lines(&raw, CAPTURE_PREVIEW_LINES);
+    if preview.is_empty() {
+        format!(" (no {stream} output)")
+    } else {
+        format!("; {stream}:\n{preview}")
+    }
+}
+
+fn first_lines(raw: &str, limit: usize) -> String {
+    let lines: Vec<&str> = raw.lines().filter(|l| !l.trim().is_empty()).collect();
+    let mut preview = lines
+        .iter()
+        .take(limit)
+        .map(|l| format!("  {l}"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    if lines.len() > limit {
+        preview.push_str(&format!("\n  ... ({} more line(s))", lines.len() - limit));
+    }
+    preview
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::TestDir;
+
+    #[test]
+    fn first_lines_truncates_and_skips_blank_lines() {
+        assert_eq!(first_lines("", 2), "");
+        assert_eq!(
+            first_lines("a\n\nb\nc\n", 2),
+            "  a\n  b\n  ... (1 more line(s))"
+        );
+    }
+
+    #[test]
+    fn failing_command_reports_stderr() {
+        let dir = TestDir::new("capture");
+        let err =
+            spawn_captured("echo boom >&2; exit 3", "cmd1", dir.path(), false, false).unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("exit status: 3"), "{message}");
+        assert!(message.contains("  boom"), "{message}");
+        assert!(dir.path().join("cmd1.err").exists());
+    }
+}
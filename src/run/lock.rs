@@ -3,6 +3,8 @@ use std::{env, fs, path::PathBuf};
 use anyhow::{Context, Result};
 use fs2::FileExt;
 
+use crate::output::status;
+
 pub(crate) fn launch_lock_path() -> PathBuf {
     if let Some(runtime) = env::var_os("XDG_RUNTIME_DIR").filter(|v| !v.is_empty()) {
         return PathBuf::from(runtime).join("hyprsets.run.lock");
@@ -26,12 +28,12 @@ pub(crate) fn acquire_launch_lock(verbose: bool) -> Result<fs::File> {
         .with_context(|| format!("failed to open launch lock: {}", path.display()))?;
 
     if verbose {
-        println!(" acquiring launch lock at {}...", path.display());
+        status!(" acquiring launch lock at {}...", path.display());
     }
     file.lock_exclusive()
         .with_context(|| format!("failed to acquire launch lock at {}", path.display()))?;
     if verbose {
-        println!(" launch lock acquired");
+        status!(" launch lock acquired");
     }
     Ok(file)
 }
@@ -0,0 +1,349 @@
+use std::{collections::HashSet, time::Duration};
+
+use crate::config::{LayoutNode, WindowSlot, Workset};
+
+use super::{
+    SLOT_LAUNCH_DELAY,
+    actions_layout::{count_slots, disabled_slots, slot_launch_delay, to_hypr_split_ratio},
+    actions_workspace::{WorkspaceTarget, launch_target},
+    build_exec_command, launch_cwd, slot_exec_command,
+    util::{launch_scope, shell_escape},
+};
+
+/// Shell variable holding the workspace that was active when the script started; only used
+/// when slots move to other workspaces and the workset has none of its own to return to.
+const MAIN_WORKSPACE_VAR: &str = "\"$main_ws\"";
+
+/// Renders the `hyprctl dispatch` sequence `run` would issue as a POSIX shell script.
+/// hyprsets polls for windows to appear; the script sleeps for the launch delay instead, and
+/// captures window addresses with `hyprctl activewindow` so splits refocus the right window.
+pub(crate) fn hyprctl_script(ws: &Workset) -> String {
+    let main = launch_target(ws, None);
+    let mut script = Script {
+        lines: Vec::new(),
+        main: main.as_ref().map(WorkspaceTarget::dispatch_arg),
+        current: None,
+    };
+
+    script.push("#!/bin/sh");
+    script.push(format!("# hyprsets workset '{}' ({})", ws.id, ws.name));
+    script.push("set -e");
+    script.push("");
+
+    match &ws.layout {
+        Some(layout) => {
+            script.push("active_window() {");
+            script
+                .push("    hyprctl activewindow | sed -n '1s/^Window \\([0-9a-f]*\\) .*/0x\\1/p'");
+            script.push("}");
+            script.push("");
+            if script.main.is_none() && has_offsite_slot(layout, main.as_ref()) {
+                script.push(
+                    "main_ws=$(hyprctl activeworkspace | sed -n '1s/^workspace ID \\([-0-9]*\\) .*/\\1/p')",
+                );
+                script.main = Some(MAIN_WORKSPACE_VAR.into());
+            }
+            script.focus_main();
+            // Disabled slots count as launched already, like repeated ids.
+            let mut launched = disabled_slots(layout);
+            let mut pending_ratio = None;
+            let total = count_slots(layout);
+            script.layout(
+                layout,
+                ws,
+                main.as_ref(),
+                &mut launched,
+                &mut pending_ratio,
+                total,
+            );
+        }
+        None => {
+            script.focus_main();
+            for (idx, cmd) in ws.commands.iter().enumerate() {
+                let exec =
+                    build_exec_command(cmd, launch_cwd(ws, None), [&ws.env], launch_scope(ws));
+                script.exec(&exec);
+                if !ws.parallel && idx + 1 < ws.commands.len() {
+                    script.sleep(SLOT_LAUNCH_DELAY);
+                }
+            }
+        }
+    }
+
+    let mut out = script.lines.join("\n");
+    out.push('\n');
+    out
+}
+
+struct Script {
+    lines: Vec<String>,
+    /// `hyprctl dispatch workspace` argument for the workset's workspace, if one is known.
+    main: Option<String>,
+    /// Workspace the script last switched to, to skip redundant dispatches.
+    current: Option<String>,
+}
+
+impl Script {
+    fn push(&mut self, line: impl Into<String>) {
+        self.lines.push(line.into());
+    }
+
+    fn switch_to(&mut self, arg: String) {
+        if self.current.as_ref() != Some(&arg) {
+            self.push(format!("hyprctl dispatch workspace {arg}"));
+            self.current = Some(arg);
+        }
+    }
+
+    fn focus_main(&mut self) {
+        if let Some(main) = self.main.clone() {
+            self.switch_to(main);
+        }
+    }
+
+    fn exec(&mut self, exec: &str) {
+        self.push(format!("hyprctl dispatch exec {}", shell_escape(exec)));
+    }
+
+    fn sleep(&mut self, delay: Duration) {
+        self.push(format!("sleep {}", sleep_arg(delay)));
+    }
+
+    /// Mirrors `run_layout_inner`: the leftmost slot of a split opens first, then the right
+    /// side is launched from it, then the rest of the left side. Returns the shell variable
+    /// holding the anchor window's address.
+    fn layout(
+        &mut self,
+        node: &LayoutNode,
+        ws: &Workset,
+        main: Option<&WorkspaceTarget>,
+        launched: &mut HashSet<u32>,
+        pending_ratio: &mut Option<f32>,
+        total: usize,
+    ) -> Option<String> {
+        match node {
+            LayoutNode::Leaf(slot) => {
+                if !launched.insert(slot.slot_id) {
+                    return None;
+                }
+                self.leaf(slot, ws, main, pending_ratio, total - launched.len())
+            }
+            LayoutNode::Split(split) => {
+                let hypr_ratio = to_hypr_split_ratio(split.ratio);
+                let left_was_split = matches!(&*split.left, LayoutNode::Split(_));
+                let left_anchor = if left_was_split {
+                    let leftmost = leftmost_leaf(&split.left);
+                    self.layout(leftmost, ws, main, launched, pending_ratio, total)
+                } else {
+                    self.layout(&split.left, ws, main, launched, pending_ratio, total)
+                };
+                self.focus_anchor(left_anchor.as_deref());
+                let mut right_pending = Some(hypr_ratio);
+                let right_anchor =
+                    self.layout(&split.right, ws, main, launched, &mut right_pending, total);
+                let remaining_left = if left_was_split {
+                    self.focus_anchor(left_anchor.as_deref());
+                    self.layout(&split.left, ws, main, launched, pending_ratio, total)
+                } else {
+                    None
+                };
+                left_anchor.or(right_anchor).or(remaining_left)
+            }
+        }
+    }
+
+    fn leaf(
+        &mut self,
+        slot: &WindowSlot,
+        ws: &Workset,
+        main: Option<&WorkspaceTarget>,
+        pending_ratio: &mut Option<f32>,
+        remaining: usize,
+    ) -> Option<String> {
+        let offsite = match main {
+            Some(main) => WorkspaceTarget::for_slot(slot, main),
+            None => slot_target(slot),
+        };
+        match &offsite {
+            Some(target) => self.switch_to(target.dispatch_arg()),
+            None => self.focus_main(),
+        }
+        self.push(format!("# slot #{}", slot.slot_id));
+        let exec = slot_exec_command(ws, slot);
+        self.exec(&exec);
+        if offsite.is_some() {
+            if remaining > 0 {
+                self.sleep(slot_launch_delay(slot));
+            }
+            return None;
+        }
+        self.sleep(slot_launch_delay(slot));
+        if let Some(ratio) = pending_ratio.take() {
+            self.push(format!("hyprctl dispatch splitratio exact {ratio:.3}"));
+        }
+        let var = format!("slot{}", slot.slot_id);
+        self.push(format!("{var}=$(active_window)"));
+        Some(var)
+    }
+
+    fn focus_anchor(&mut self, anchor: Option<&str>) {
+        self.focus_main();
+        if let Some(var) = anchor {
+            self.push(format!("hyprctl dispatch focuswindow address:\"${var}\""));
+        }
+    }
+}
+
+/// Without a workset workspace every slot `workspace` counts as elsewhere.
+fn slot_target(slot: &WindowSlot) -> Option<WorkspaceTarget> {
+    slot.workspace
+        .as_deref()
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .and_then(WorkspaceTarget::from_raw)
+}
+
+fn has_offsite_slot(node: &LayoutNode, main: Option<&WorkspaceTarget>) -> bool {
+    match node {
+        LayoutNode::Leaf(slot) => match main {
+            Some(main) => WorkspaceTarget::for_slot(slot, main).is_some(),
+            None => slot_target(slot).is_some(),
+        },
+        LayoutNode::Split(split) => {
+            has_offsite_slot(&split.left, main) || has_offsite_slot(&split.right, main)
+        }
+    }
+}
+
+fn leftmost_leaf(node: &LayoutNode) -> &LayoutNode {
+    match node {
+        LayoutNode::Leaf(_) => node,
+        LayoutNode::Split(split) => leftmost_leaf(&split.left),
+    }
+}
+
+fn sleep_arg(delay: Duration) -> String {
+    let millis = delay.as_millis();
+    if millis.is_multiple_of(1000) {
+        (millis / 1000).to_string()
+    } else {
+        format!("{}.{:03}", millis / 1000, millis % 1000)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{SplitDirection, SplitNode};
+
+    fn slot(slot_id: u32, command: &str) -> LayoutNode {
+        LayoutNode::Leaf(WindowSlot::new(slot_id, command))
+    }
+
+    fn workset(commands: Vec<String>, layout: Option<LayoutNode>) -> Workset {
+        Workset {
+            id: "dev".into(),
+            name: "Dev".into(),
+            desc: "".into(),
+            workspace: Some("3".into()),
+            commands,
+            layout,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn commands_are_quoted_for_hyprctl() {
+        let ws = workset(vec!["echo 'hi'".into(), "kitty".into()], None);
+        let script = hyprctl_script(&ws);
+        let lines: Vec<&str> = script.lines().skip(4).collect();
+        assert_eq!(
+            lines,
+            vec![
+                "hyprctl dispatch workspace 3",
+                "hyprctl dispatch exec 'echo '\"'\"'hi'\"'\"''",
+                "sleep 1",
+                "hyprctl dispatch exec 'kitty'",
+            ]
+        );
+    }
+
+    #[test]
+    fn layout_script_follows_launch_order() {
+        let layout = LayoutNode::Split(SplitNode {
+            direction: SplitDirection::Horizontal,
+            ratio: 1.0,
+            left: Box::new(slot(1, "a")),
+            right: Box::new(slot(2, "b")),
+        });
+        let ws = workset(Vec::new(), Some(layout));
+        let script = hyprctl_script(&ws);
+        let body: Vec<&str> = script
+            .lines()
+            .skip_while(|line| !line.starts_with("hyprctl dispatch workspace"))
+            .collect();
+        assert_eq!(
+            body,
+            vec![
+                "hyprctl dispatch workspace 3",
+                "# slot #1",
+                "hyprctl dispatch exec 'a'",
+                "sleep 1",
+                "slot1=$(active_window)",
+                "hyprctl dispatch focuswindow address:\"$slot1\"",
+                "# slot #2",
+                "hyprctl dispatch exec 'b'",
+                "sleep 1",
+                "hyprctl dispatch splitratio exact 1.000",
+                "slot2=$(active_window)",
+            ]
+        );
+    }
+
+    #[test]
+    fn disabled_slot_is_skipped_but_keeps_the_split() {
+        let mut disabled = slot(2, "b");
+        if let LayoutNode::Leaf(slot) = &mut disabled {
+            slot.enabled = false;
+        }
+        let layout = LayoutNode::Split(SplitNode {
+            direction: SplitDirection::Horizontal,
+            ratio: 1.0,
+            left: Box::new(slot(1, "a")),
+            right: Box::new(LayoutNode::Split(SplitNode {
+                direction: SplitDirection::Vertical,
+                ratio: 1.0,
+                left: Box::new(disabled),
+                right: Box::new(slot(3, "c")),
+            })),
+        });
+        let ws = workset(Vec::new(), Some(layout));
+        let script = hyprctl_script(&ws);
+        assert!(!script.contains("exec 'b'"));
+        assert!(!script.contains("# slot #2"));
+        assert!(script.contains("# slot #1\nhyprctl dispatch exec 'a'"));
+        assert!(script.contains(
+            "# slot #3\nhyprctl dispatch exec 'c'\nsleep 1\nhyprctl dispatch splitratio exact 1.000\n"
+        ));
+    }
+
+    #[test]
+    fn offsite_slot_returns_to_the_starting_workspace() {
+        let mut offsite = slot(2, "b");
+        if let LayoutNode::Leaf(slot) = &mut offsite {
+            slot.workspace = Some("name:chat".into());
+        }
+        let layout = LayoutNode::Split(SplitNode {
+            direction: SplitDirection::Vertical,
+            ratio: 1.0,
+            left: Box::new(offsite),
+            right: Box::new(slot(1, "a")),
+        });
+        let mut ws = workset(Vec::new(), Some(layout));
+        ws.workspace = None;
+        let script = hyprctl_script(&ws);
+        assert!(script.contains("main_ws=$(hyprctl activeworkspace"));
+        assert!(script.contains("hyprctl dispatch workspace 'name:chat'\n# slot #2\n"));
+        assert!(script.contains("hyprctl dispatch workspace \"$main_ws\"\n# slot #1\n"));
+    }
+}
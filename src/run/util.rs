@@ -1,9 +1,39 @@
-use std::{collections::HashMap, path::Path};
+use std::{
+    collections::HashMap,
+    ffi::OsStr,
+    io,
+    os::unix::process::CommandExt,
+    path::Path,
+    process::{Child, Command, Stdio},
+    sync::OnceLock,
+};
+
+use anyhow::{Context, Result, bail};
+
+use crate::config::{WindowSlot, Workset};
+
+const HYPRLAND_SIGNATURE_VAR: &str = "HYPRLAND_INSTANCE_SIGNATURE";
+
+/// Fails early with a readable error instead of letting the hyprland crate choke on a
+/// missing socket (SSH sessions, X11, other compositors).
+pub(crate) fn ensure_hyprland_session() -> Result<()> {
+    check_hyprland_signature(std::env::var_os(HYPRLAND_SIGNATURE_VAR).as_deref())
+}
+
+fn check_hyprland_signature(signature: Option<&OsStr>) -> Result<()> {
+    match signature {
+        Some(value) if !value.is_empty() => Ok(()),
+        _ => bail!(
+            "hyprsets requires a running Hyprland session ({HYPRLAND_SIGNATURE_VAR} is not set)"
+        ),
+    }
+}
 
 pub(crate) fn build_exec_command<'a>(
     base_cmd: &str,
     cwd: Option<&Path>,
     env_layers: impl IntoIterator<Item = &'a HashMap<String, String>>,
+    scope: Option<LaunchScope<'_>>,
 ) -> String {
     let mut exec = String::new();
 
@@ -30,10 +60,105 @@ pub(crate) fn build_exec_command<'a>(
         exec.push(' ');
     }
 
-    exec.push_str(base_cmd);
+    match scope {
+        Some(scope) => {
+            // A scope runs its command directly, so the shell syntax needs its own `sh -c`.
+            exec.push_str("systemd-run --user --scope --quiet ");
+            if let Some(slice) = scope.slice {
+                exec.push_str("--slice=");
+                exec.push_str(&shell_escape(slice));
+                exec.push(' ');
+            }
+            exec.push_str("-- sh -c ");
+            exec.push_str(&shell_escape(base_cmd));
+        }
+        None => exec.push_str(base_cmd),
+    }
     exec
 }
 
+/// How a workset's commands are wrapped in `systemd-run --scope`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct LaunchScope<'a> {
+    pub slice: Option<&'a str>,
+}
+
+/// The scope `ws` asks for with `scope`/`slice`. `None` when it asks for none, or when
+/// `systemd-run` is not installed; commands then start unwrapped (see [`scope_warning`]).
+pub(crate) fn launch_scope(ws: &Workset) -> Option<LaunchScope<'_>> {
+    let slice = scope_slice(ws);
+    if !ws.scope && slice.is_none() {
+        return None;
+    }
+    systemd_run_available().then_some(LaunchScope { slice })
+}
+
+/// Why `ws`'s `scope`/`slice` will be ignored, if it will be. Returned rather than printed so
+/// the TUI can show it on the status line instead of writing over the alternate screen.
+pub(crate) fn scope_warning(ws: &Workset) -> Option<&'static str> {
+    let wants_scope = ws.scope || scope_slice(ws).is_some();
+    (wants_scope && !systemd_run_available())
+        .then_some("systemd-run not found; launching without a systemd scope")
+}
+
+fn scope_slice(ws: &Workset) -> Option<&str> {
+    ws.slice.as_deref().map(str::trim).filter(|s| !s.is_empty())
+}
+
+fn systemd_run_available() -> bool {
+    static AVAILABLE: OnceLock<bool> = OnceLock::new();
+    *AVAILABLE.get_or_init(|| {
+        std::env::var_os("PATH").is_some_and(|path| {
+            std::env::split_paths(&path).any(|dir| dir.join("systemd-run").is_file())
+        })
+    })
+}
+
+/// The exec string a layout slot is launched with: its cwd, the workset env overlaid with the
+/// slot env, then the command. The editor's preview shows exactly this string.
+pub(crate) fn slot_exec_command(ws: &Workset, slot: &WindowSlot) -> String {
+    build_exec_command(
+        &slot.command,
+        launch_cwd(ws, Some(slot)),
+        [&ws.env, &slot.env],
+        launch_scope(ws),
+    )
+}
+
+/// Directory a command starts in: the slot's own `cwd`, else the workset's. Launching and
+/// "open cwd" both go through this so they never disagree.
+pub(crate) fn launch_cwd<'a>(ws: &'a Workset, slot: Option<&'a WindowSlot>) -> Option<&'a Path> {
+    slot.and_then(|slot| slot.cwd.as_deref())
+        .or(ws.cwd.as_deref())
+}
+
+/// Opens `dir` in the desktop's file manager with `xdg-open`, without waiting for it.
+pub(crate) fn open_in_file_manager(dir: &Path, detach: bool) -> Result<()> {
+    open_dir_with(dir, &mut |cmd| spawn_detached(cmd, detach).map(drop))
+}
+
+/// Spawns `cmd`, in a process group of its own when `detach` is set (`detach_commands`), so
+/// a Ctrl-C or hangup aimed at hyprsets' terminal job does not reach it. Every process
+/// hyprsets starts itself goes through here; Hyprland's `exec` dispatcher is unaffected.
+pub(crate) fn spawn_detached(cmd: &mut Command, detach: bool) -> io::Result<Child> {
+    if detach {
+        cmd.process_group(0);
+    }
+    cmd.spawn()
+}
+
+fn open_dir_with(dir: &Path, spawn: &mut dyn FnMut(&mut Command) -> io::Result<()>) -> Result<()> {
+    if !dir.is_dir() {
+        bail!("cwd is not a directory: {}", dir.display());
+    }
+    let mut cmd = Command::new("xdg-open");
+    cmd.arg(dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    spawn(&mut cmd).context("failed to run xdg-open")
+}
+
 pub(crate) fn shell_escape(raw: &str) -> String {
     let mut escaped = String::from("'");
     for ch in raw.chars() {
@@ -70,6 +195,7 @@ mod tests {
             "echo $FOO $BAZ",
             Some(Path::new("/tmp/work")),
             [&env1, &env2],
+            None,
         );
         assert!(
             cmd.starts_with("cd '/tmp/work' && "),
@@ -90,7 +216,102 @@ mod tests {
             "ls -la",
             None,
             std::iter::empty::<&HashMap<String, String>>(),
+            None,
         );
         assert_eq!(cmd, "ls -la");
     }
+
+    #[test]
+    fn build_exec_command_wraps_in_a_systemd_scope() {
+        let env = HashMap::from([("FOO".to_string(), "bar".to_string())]);
+        let base = "echo 'hi there' && kitty";
+        let plain = build_exec_command(base, Some(Path::new("/tmp/work")), [&env], None);
+        assert_eq!(
+            plain,
+            "cd '/tmp/work' && FOO='bar' echo 'hi there' && kitty"
+        );
+
+        let scoped = build_exec_command(
+            base,
+            Some(Path::new("/tmp/work")),
+            [&env],
+            Some(LaunchScope { slice: None }),
+        );
+        assert_eq!(
+            scoped,
+            "cd '/tmp/work' && FOO='bar' systemd-run --user --scope --quiet -- sh -c \
+             'echo '\"'\"'hi there'\"'\"' && kitty'"
+        );
+
+        let sliced = build_exec_command(
+            base,
+            None,
+            std::iter::empty::<&HashMap<String, String>>(),
+            Some(LaunchScope {
+                slice: Some("work.slice"),
+            }),
+        );
+        assert!(sliced.starts_with("systemd-run --user --scope --quiet --slice='work.slice' -- "));
+        assert!(sliced.ends_with(&format!("sh -c {}", shell_escape(base))));
+    }
+
+    #[test]
+    fn hyprland_guard_requires_instance_signature() {
+        let err = check_hyprland_signature(None).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("requires a running Hyprland session")
+        );
+        assert!(check_hyprland_signature(Some(OsStr::new(""))).is_err());
+        assert!(check_hyprland_signature(Some(OsStr::new("abc_123"))).is_ok());
+    }
+
+    #[test]
+    fn open_dir_runs_xdg_open_on_existing_dirs_only() {
+        let dir = std::env::temp_dir();
+        let mut calls = Vec::new();
+        open_dir_with(&dir, &mut |cmd| {
+            calls.push((
+                cmd.get_program().to_os_string(),
+                cmd.get_args().map(OsStr::to_os_string).collect::<Vec<_>>(),
+            ));
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(
+            calls,
+            vec![("xdg-open".into(), vec![dir.clone().into_os_string()])]
+        );
+
+        let missing = dir.join("hyprsets-no-such-dir");
+        assert!(open_dir_with(&missing, &mut |_| panic!("should not spawn")).is_err());
+    }
+
+    // Reads the process group from procfs.
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn spawn_detached_starts_a_new_process_group_only_when_asked() {
+        // Prints the shell's pid and its process group id.
+        let pid_and_group = |detach| {
+            let mut cmd = Command::new("sh");
+            cmd.arg("-c")
+                .arg("echo $$ $(cut -d' ' -f5 /proc/$$/stat)")
+                .stdout(Stdio::piped());
+            let out = spawn_detached(&mut cmd, detach)
+                .unwrap()
+                .wait_with_output()
+                .unwrap();
+            let text = String::from_utf8(out.stdout).unwrap();
+            let ids: Vec<u32> = text
+                .split_whitespace()
+                .map(|n| n.parse().unwrap())
+                .collect();
+            assert_eq!(ids.len(), 2, "unexpected output: {text:?}");
+            (ids[0], ids[1])
+        };
+        let (pid, group) = pid_and_group(true);
+        assert_eq!(pid, group);
+        let (pid, group) = pid_and_group(false);
+        assert_ne!(pid, group);
+    }
 }
@@ -1,14 +1,29 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::{
+    collections::BTreeMap,
     fs,
     io::ErrorKind,
     path::{Path, PathBuf},
+    time::SystemTime,
 };
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct AppState {
     pub last_tab_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dense: Option<bool>,
+    /// Workset most recently launched from the home screen, for `.` (re-run last).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_run_id: Option<String>,
+    /// When each workset was last launched from the home screen, for
+    /// `unassigned_order = "last_run"`.
+    #[serde(
+        default,
+        skip_serializing_if = "BTreeMap::is_empty",
+        with = "crate::config::unix_seconds::map"
+    )]
+    pub last_run: BTreeMap<String, SystemTime>,
 }
 
 pub fn default_state_path() -> PathBuf {
@@ -51,3 +66,26 @@ impl AppState {
             .with_context(|| format!("failed to write state file: {}", path.display()))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn last_run_round_trips_as_unix_seconds() {
+        let state = AppState {
+            last_run: [(
+                "dev".to_string(),
+                UNIX_EPOCH + Duration::from_secs(1_700_000_000),
+            )]
+            .into(),
+            ..AppState::default()
+        };
+        let raw = toml::to_string_pretty(&state).unwrap();
+        assert!(raw.contains("dev = 1700000000"), "{raw}");
+
+        let parsed: AppState = toml::from_str(&raw).unwrap();
+        assert_eq!(parsed.last_run, state.last_run);
+    }
+}
@@ -0,0 +1,57 @@
+use std::time::SystemTime;
+
+/// How long before `now` the time `then` was, in its largest whole unit: `just now`, `5m ago`,
+/// `3h ago`, `12d ago`. A `then` in the future (clock changes) reads as `just now`.
+pub(crate) fn relative_age(then: SystemTime, now: SystemTime) -> String {
+    let secs = now.duration_since(then).map_or(0, |age| age.as_secs());
+    match secs {
+        0..60 => "just now".to_string(),
+        60..3_600 => format!("{}m ago", secs / 60),
+        3_600..86_400 => format!("{}h ago", secs / 3_600),
+        _ => format!("{}d ago", secs / 86_400),
+    }
+}
+
+/// Local wall-clock time as `HH:MM`.
+pub(crate) fn local_time_hm() -> String {
+    let now = chrono::Local::now();
+    format_hm(now.timestamp(), now.offset().local_minus_utc())
+}
+
+/// `secs` since the Unix epoch as `HH:MM` in a zone `offset` seconds east of UTC.
+fn format_hm(secs: i64, offset: i32) -> String {
+    let minute_of_day = (secs + i64::from(offset))
+        .div_euclid(60)
+        .rem_euclid(24 * 60);
+    format!("{:02}:{:02}", minute_of_day / 60, minute_of_day % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relative_age_uses_the_largest_whole_unit() {
+        use std::time::{Duration, UNIX_EPOCH};
+
+        let now = UNIX_EPOCH + Duration::from_secs(10_000_000);
+        let ago = |secs| relative_age(now - Duration::from_secs(secs), now);
+        assert_eq!(ago(59), "just now");
+        assert_eq!(ago(60), "1m ago");
+        assert_eq!(ago(3_599), "59m ago");
+        assert_eq!(ago(7_200), "2h ago");
+        assert_eq!(ago(3 * 86_400 + 5), "3d ago");
+        assert_eq!(relative_age(now + Duration::from_secs(30), now), "just now");
+    }
+
+    #[test]
+    fn format_hm_applies_the_offset_and_wraps_the_day() {
+        // 2023-11-14 22:13:20 UTC.
+        let secs = 1_700_000_000;
+        assert_eq!(format_hm(secs, 0), "22:13");
+        assert_eq!(format_hm(secs, 2 * 3_600), "00:13");
+        assert_eq!(format_hm(secs, -(5 * 3_600 + 30 * 60)), "16:43");
+        assert_eq!(format_hm(59, 0), "00:00");
+        assert_eq!(format_hm(0, -60), "23:59");
+    }
+}
@@ -1,31 +1,38 @@
 use std::{
     io,
     path::{Path, PathBuf},
-    time::Instant,
+    time::{Duration, Instant},
 };
 
 use anyhow::Result;
-use crossterm::{
-    cursor::Show,
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyEventKind},
-    execute,
-    terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
-};
+use crossterm::event::{Event, KeyEventKind};
 use ratatui::{Terminal, backend::CrosstermBackend};
 
-use crate::config::{LayoutNode, SplitDirection, Workset};
+use crate::{
+    config::{LayoutNode, SplitDirection, Workset},
+    keymap::KeyMap,
+    ui::{
+        selection::Selection,
+        status::MessageExpiry,
+        terminal::{TerminalSession, is_interrupt, next_event},
+    },
+};
 
 mod actions;
 mod events;
+#[cfg(test)]
+mod fixtures;
 mod layout;
 mod persistence;
 mod render;
 
 use self::{
-    layout::{ensure_layout, first_leaf_path},
-    persistence::load_tab_state,
+    layout::{ensure_layout, first_leaf_path, leaf_paths},
+    persistence::{load_tab_state, load_ui_settings},
 };
 
+/// Consecutive autosave failures before autosave stops and the banner appears.
+const AUTOSAVE_FAILURE_LIMIT: u32 = 3;
 const RATIO_MIN: f32 = 0.3;
 const RATIO_MAX: f32 = 2.5;
 
@@ -52,6 +59,8 @@ struct ButtonHit {
 #[derive(Debug, Clone)]
 struct SplitHit {
     boundary: ratatui::layout::Rect,
+    /// `boundary` widened by the hit slop (clipped to `area`); only used for hit-testing.
+    grab: ratatui::layout::Rect,
     area: ratatui::layout::Rect,
     path: Vec<Side>,
     direction: SplitDirection,
@@ -62,6 +71,13 @@ struct ActiveDrag {
     path: Vec<Side>,
 }
 
+/// A slot being dragged onto another slot to swap them.
+#[derive(Debug, Clone)]
+struct LeafDrag {
+    source: Vec<Side>,
+    target: Option<Vec<Side>>,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum DialogField {
     Id,
@@ -88,6 +104,11 @@ enum Mode {
         target: Vec<Side>,
         slot_id: Option<u32>,
     },
+    /// Read-only view of the exec string the selected slot would be launched with.
+    PreviewExec {
+        slot_id: u32,
+        exec: String,
+    },
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -104,6 +125,24 @@ enum ToolbarAction {
     Cancel,
 }
 
+impl ToolbarAction {
+    /// One-line help shown in the status bar while the pointer rests on the button.
+    fn help(self) -> &'static str {
+        match self {
+            Self::Next => "Select the next slot",
+            Self::SplitH => "Split the selected slot side by side",
+            Self::SplitV => "Split the selected slot top and bottom",
+            Self::RatioUp => "Raise the ratio of the split around the selected slot",
+            Self::RatioDown => "Lower the ratio of the split around the selected slot",
+            Self::Delete => "Remove the selected slot",
+            Self::EditCmd => "Edit the selected slot's command and wait",
+            Self::Swap => "Swap the selected slot with the next one",
+            Self::EditWorkset => "Edit the workset's id, name, workspace, description and tab",
+            Self::Cancel => "Save and return to the list",
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 struct WorksetForm {
     id: String,
@@ -124,6 +163,15 @@ enum SlotField {
     Wait,
 }
 
+/// Where the editor starts when it opens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EditorEntry {
+    #[default]
+    Normal,
+    /// Select the first slot and open its command dialog immediately.
+    FirstSlotCommand,
+}
+
 #[derive(Debug)]
 pub enum EditorExit {
     Saved(Workset),
@@ -142,25 +190,14 @@ struct TabOption {
     label: String,
 }
 
-pub fn run_editor(workset: Workset, config_path: &Path) -> Result<EditorExit> {
+pub fn run_editor(workset: Workset, config_path: &Path, entry: EditorEntry) -> Result<EditorExit> {
     let mut app = EditorApp::new(workset, config_path.to_path_buf());
-    let backend = CrosstermBackend::new(io::stdout());
-    let mut terminal = Terminal::new(backend)?;
-    terminal.show_cursor()?;
-
-    enable_raw_mode()?;
-    execute!(io::stdout(), EnterAlternateScreen, EnableMouseCapture)?;
-
-    let result = app.event_loop(&mut terminal);
-
-    disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture,
-        Show
-    )?;
-
+    if entry == EditorEntry::FirstSlotCommand {
+        app.start_edit_command();
+    }
+    let mut session = TerminalSession::new(false)?;
+    let result = app.event_loop(session.terminal());
+    session.restore()?;
     result
 }
 
@@ -176,32 +213,80 @@ struct EditorApp {
     config_path: PathBuf,
     hover_toolbar: Option<ToolbarAction>,
     active_drag: Option<ActiveDrag>,
+    leaf_drag: Option<LeafDrag>,
     hover_split: Option<Vec<Side>>,
     tab_options: Vec<TabOption>,
     selected_tab_id: Option<String>,
+    keymap: KeyMap,
+    message_timeout: Option<Duration>,
+    ratio_step: f32,
+    split_hit_slop: u16,
+    selection: Selection,
+    /// `detach_commands`, for `xdg-open`.
+    detach_commands: bool,
+    message_expiry: MessageExpiry,
+    autosave_failures: u32,
+    /// Draw only the selected slot, full size. View-only: the layout is untouched.
+    zoomed: bool,
+    /// Draw a linear layout as a numbered list of its slots. View-only, like `zoomed`.
+    list_view: bool,
+    /// Set once autosave has failed `AUTOSAVE_FAILURE_LIMIT` times in a row; autosave stays
+    /// off and a banner shows this error until an explicit save succeeds.
+    autosave_error: Option<String>,
+    /// Edits that no save has written yet (autosave failed or is suspended).
+    unsaved: bool,
+    /// Set after a failed save on exit; the next exit leaves without saving.
+    exit_unsaved: bool,
 }
 
 impl EditorApp {
     fn new(workset: Workset, config_path: PathBuf) -> Self {
-        let root = ensure_layout(workset.clone());
+        let mut root = ensure_layout(workset.clone());
         let selected_path = first_leaf_path(&root).unwrap_or_default();
         let saved_id = workset.id.clone();
         let (tab_options, selected_tab_id) = load_tab_state(&config_path, &workset.id);
+        let settings = load_ui_settings(&config_path);
+        // Slots are tracked by id, so a hand-edited layout with repeats would lose slots.
+        let renumbered = !root.duplicate_slot_ids().is_empty();
+        if renumbered {
+            root.renumber_slots();
+        }
+        let message = if renumbered {
+            Some("duplicate slot ids renumbered in launch order; save to keep them".to_string())
+        } else {
+            workset.commands_out_of_sync().then(|| {
+                "commands differ from the layout; Ctrl+S rewrites them from the layout".to_string()
+            })
+        };
         Self {
             workset,
             saved_id,
             root,
             selected_path,
-            message: None,
+            message,
             mode: Mode::Normal,
             last_click: None,
             leaf_paths: Vec::new(),
             config_path,
             hover_toolbar: None,
             active_drag: None,
+            leaf_drag: None,
             hover_split: None,
             tab_options,
             selected_tab_id,
+            keymap: settings.keymap,
+            message_timeout: settings.message_timeout,
+            ratio_step: settings.ratio_step,
+            split_hit_slop: settings.split_hit_slop,
+            selection: settings.selection,
+            detach_commands: settings.detach_commands,
+            message_expiry: MessageExpiry::default(),
+            autosave_failures: 0,
+            zoomed: false,
+            list_view: false,
+            autosave_error: None,
+            unsaved: false,
+            exit_unsaved: false,
         }
     }
 
@@ -209,20 +294,42 @@ impl EditorApp {
         &mut self,
         terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     ) -> Result<EditorExit> {
+        let mut ui_meta = UiMeta::default();
+        let mut redraw = true;
         loop {
-            let mut ui_meta = UiMeta::default();
-            terminal.draw(|f| {
-                ui_meta = self.draw(f);
-            })?;
-            self.leaf_paths = ui_meta.leaf_hits.iter().map(|h| h.path.clone()).collect();
-            if (self.selected_path.is_empty() || !self.leaf_paths.contains(&self.selected_path))
-                && let Some(first) = self.leaf_paths.first()
-            {
-                self.selected_path = first.clone();
+            if redraw {
+                terminal.draw(|f| {
+                    ui_meta = self.draw(f);
+                })?;
+                // A zoomed view only draws one slot, a scrolled list or a too-small terminal
+                // fewer, but navigation still walks all of them.
+                self.leaf_paths = if self.zoomed || self.list_view || ui_meta.leaf_hits.is_empty() {
+                    leaf_paths(&self.root)
+                } else {
+                    ui_meta.leaf_hits.iter().map(|h| h.path.clone()).collect()
+                };
+                if (self.selected_path.is_empty() || !self.leaf_paths.contains(&self.selected_path))
+                    && let Some(first) = self.leaf_paths.first()
+                {
+                    self.selected_path = first.clone();
+                }
             }
 
-            match event::read()? {
+            let Some(event) = next_event()? else {
+                redraw = self
+                    .message_expiry
+                    .tick(&mut self.message, self.message_timeout);
+                continue;
+            };
+            redraw = true;
+            match event {
                 Event::Key(key) if key.kind == KeyEventKind::Press => {
+                    // Edits are autosaved, so an interrupt exits like `q`.
+                    if is_interrupt(&key)
+                        && let Some(exit) = self.exit_saved()?
+                    {
+                        return Ok(exit);
+                    }
                     if let Some(exit) = self.handle_key(key)? {
                         return Ok(exit);
                     }
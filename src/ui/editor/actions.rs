@@ -1,19 +1,34 @@
 use anyhow::{Result, bail};
 
-use crate::config::{AppConfig, LayoutNode, WindowSlot};
+use crate::{
+    config::{AppConfig, LayoutNode, WindowSlot},
+    run::{launch_cwd, open_in_file_manager, scope_warning, slot_exec_command},
+};
 
 use super::{
-    EditorApp, EditorExit, Mode, Side, WorksetForm,
+    AUTOSAVE_FAILURE_LIMIT, EditorApp, EditorExit, Mode, Side, WorksetForm,
     layout::{
-        adjust_ratio, collect_commands, leaf_at_path, next_slot_id, remove_leaf,
-        replace_leaf_with_split, set_leaf_at_path,
+        adjust_ratio, apply_context_to_all, next_slot_id, remove_leaf, replace_leaf_with_split,
+        swap_leaves,
     },
     persistence::persist_workset,
 };
 
 impl EditorApp {
+    /// `q`/`Esc`: saves and exits. Autosave has usually written everything already; edits it
+    /// could not write are saved here, and if that fails too the editor stays open once so
+    /// they are not lost silently. A second exit then leaves without them.
     pub(super) fn exit_saved(&mut self) -> Result<Option<EditorExit>> {
-        // 保存は各アクションのオートセーブで行うため、ここでは追加保存しない
+        if self.unsaved && !self.exit_unsaved {
+            if let Err(err) = self.save_current() {
+                self.exit_unsaved = true;
+                self.message = Some(format!(
+                    "Save failed: {err}; exit again to leave without saving"
+                ));
+                return Ok(None);
+            }
+            self.unsaved = false;
+        }
         Ok(Some(EditorExit::Saved(self.workset.clone())))
     }
 
@@ -179,12 +194,73 @@ impl EditorApp {
     pub(super) fn split_selected(&mut self, direction: super::SplitDirection) {
         let next_id = next_slot_id(&self.root);
         let path = self.selected_path.clone();
-        let replaced = replace_leaf_with_split(&mut self.root, &path, direction, next_id);
+        let replaced =
+            replace_leaf_with_split(&mut self.root, &path, direction, next_id, Side::Right);
         if replaced {
             self.mark_changed();
         }
     }
 
+    /// Adds an empty slot beside the selected one on `side` of a `direction` split and
+    /// selects it, so its command can be typed straight away.
+    pub(super) fn insert_beside_selected(&mut self, direction: super::SplitDirection, side: Side) {
+        let next_id = next_slot_id(&self.root);
+        let path = self.selected_path.clone();
+        if replace_leaf_with_split(&mut self.root, &path, direction, next_id, side) {
+            self.selected_path = path;
+            self.selected_path.push(side);
+            self.mark_changed();
+        }
+    }
+
+    /// Switches the selected slot off or back on; a disabled slot stays in the layout but
+    /// is not launched.
+    pub(super) fn toggle_selected_enabled(&mut self) {
+        let Some(slot) = self.current_leaf_mut() else {
+            return;
+        };
+        slot.enabled = !slot.enabled;
+        let state = if slot.enabled { "enabled" } else { "disabled" };
+        self.message = Some(format!("Slot #{} {state}", slot.slot_id));
+        self.mark_changed();
+    }
+
+    pub(super) fn toggle_list_view(&mut self) {
+        if self.list_view {
+            self.list_view = false;
+            self.message = Some("Back to the layout view".into());
+        } else if self.root.is_linear() {
+            self.list_view = true;
+            self.message = Some("List view: w swaps with the next slot".into());
+        } else {
+            self.message = Some("List view needs a layout whose splits all run one way".into());
+        }
+    }
+
+    pub(super) fn preview_selected_exec(&mut self) {
+        let Some(slot) = self.current_leaf() else {
+            return;
+        };
+        self.mode = Mode::PreviewExec {
+            slot_id: slot.slot_id,
+            exec: slot_exec_command(&self.workset, slot),
+        };
+        if let Some(warning) = scope_warning(&self.workset) {
+            self.message = Some(warning.into());
+        }
+    }
+
+    /// Opens the selected slot's cwd (or the workset's when the slot has none).
+    pub(super) fn open_selected_cwd(&mut self) {
+        self.message = Some(match launch_cwd(&self.workset, self.current_leaf()) {
+            None => "No cwd set for this slot or workset".into(),
+            Some(dir) => match open_in_file_manager(dir, self.detach_commands) {
+                Ok(()) => format!("Opened {}", dir.display()),
+                Err(err) => err.to_string(),
+            },
+        });
+    }
+
     pub(super) fn bump_ratio(&mut self, delta: f32) {
         if let Some((_, old, new)) = adjust_ratio(&mut self.root, &self.selected_path, delta)
             && (old - new).abs() > 0.0001
@@ -209,6 +285,22 @@ impl EditorApp {
         }
     }
 
+    pub(super) fn apply_context_to_all_slots(&mut self) {
+        let Some(source) = self.current_leaf().cloned() else {
+            return;
+        };
+        let changed = apply_context_to_all(&mut self.root, &source.cwd, &source.env);
+        if changed == 0 {
+            self.message = Some("All slots already share this cwd/env".into());
+            return;
+        }
+        self.message = Some(format!(
+            "Applied cwd/env of slot {} to {changed} slot(s)",
+            source.slot_id
+        ));
+        self.mark_changed();
+    }
+
     pub(super) fn swap_with_next(&mut self) {
         if self.leaf_paths.len() < 2 {
             self.message = Some("No adjacent slot to swap".into());
@@ -221,26 +313,29 @@ impl EditorApp {
             .position(|p| p == &self.selected_path)
             .unwrap_or(0);
         let next_idx = (current_idx + 1) % self.leaf_paths.len();
-        let next_path = &self.leaf_paths[next_idx];
-
-        let Some(current_leaf) = leaf_at_path(&self.root, &self.selected_path).cloned() else {
-            return;
-        };
-        let Some(next_leaf) = leaf_at_path(&self.root, next_path).cloned() else {
-            return;
-        };
+        let next_path = self.leaf_paths[next_idx].clone();
 
-        if set_leaf_at_path(&mut self.root, &self.selected_path, next_leaf)
-            && set_leaf_at_path(&mut self.root, next_path, current_leaf)
-        {
+        if swap_leaves(&mut self.root, &self.selected_path, &next_path) {
             self.message = Some("Swapped with adjacent slot".into());
             self.mark_changed();
         }
     }
 
+    /// Finishes a mouse drag from `source` onto `target`; selection follows the moved slot.
+    pub(super) fn drop_leaf(&mut self, source: &[Side], target: &[Side]) {
+        if source == target {
+            return;
+        }
+        if swap_leaves(&mut self.root, source, target) {
+            self.selected_path = target.to_vec();
+            self.message = Some("Swapped slots".into());
+            self.mark_changed();
+        }
+    }
+
     pub(super) fn commit_workset(&mut self) {
         let mut commands = Vec::new();
-        collect_commands(&self.root, &mut commands);
+        self.root.collect_commands(&mut commands);
         self.workset.commands = commands;
         self.workset.layout = Some(self.root.clone());
     }
@@ -258,12 +353,51 @@ impl EditorApp {
     }
 
     pub(super) fn autosave(&mut self) {
-        if let Err(err) = self.save_current() {
-            self.message = Some(format!("Autosave failed: {err}"));
+        if self.autosave_error.is_some() {
+            self.unsaved = true;
+            return;
+        }
+        match self.save_current() {
+            Ok(()) => {
+                self.autosave_failures = 0;
+                self.unsaved = false;
+            }
+            Err(err) => {
+                self.unsaved = true;
+                self.autosave_failures += 1;
+                if self.autosave_failures >= AUTOSAVE_FAILURE_LIMIT {
+                    self.autosave_error = Some(err.to_string());
+                } else {
+                    self.message = Some(format!("Autosave failed: {err}"));
+                }
+            }
+        }
+    }
+
+    /// Ctrl+S: saves and keeps editing, even while autosave is suspended, and resumes it on
+    /// success. The message reports the actual write.
+    pub(super) fn save_explicitly(&mut self) {
+        match self.save_current() {
+            Ok(()) => {
+                self.autosave_failures = 0;
+                self.autosave_error = None;
+                self.unsaved = false;
+                self.exit_unsaved = false;
+                self.message = Some("Saved ✓".into());
+            }
+            Err(err) => {
+                self.autosave_error = Some(err.to_string());
+                self.message = Some(format!("Save failed: {err}"));
+            }
         }
     }
 
     pub(super) fn mark_changed(&mut self) {
+        if self.list_view && !self.root.is_linear() {
+            // A split in the other direction turned the list into a grid.
+            self.list_view = false;
+            self.message = Some("Layout is no longer linear; back to the layout view".into());
+        }
         self.autosave();
     }
 }
@@ -1,11 +1,13 @@
 use std::time::Instant;
 
 use anyhow::Result;
-use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+
+use crate::keymap::EditorAction;
 
 use super::{
-    ActiveDrag, EditorApp, EditorExit, Mode, Side, SlotField, ToolbarAction, UiMeta,
-    layout::{ratio_from_position, set_ratio},
+    ActiveDrag, EditorApp, EditorExit, LeafDrag, Mode, Side, SlotField, ToolbarAction, UiMeta,
+    layout::{adjust_ratio, ratio_from_position, set_ratio},
     render::{hit_split, hit_toolbar, point_in_rect},
 };
 
@@ -53,36 +55,69 @@ impl EditorApp {
             Mode::ConfirmDelete { target, slot_id } => {
                 self.handle_key_confirm_delete(key, target, slot_id)
             }
+            Mode::PreviewExec { .. } => {
+                if matches!(key.code, KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q'))
+                    || self.keymap.editor(&key) == Some(EditorAction::PreviewExec)
+                {
+                    self.mode = Mode::Normal;
+                }
+                Ok(None)
+            }
         }
     }
 
     fn handle_key_normal(&mut self, key: KeyEvent) -> Result<Option<EditorExit>> {
+        if key.code == KeyCode::Char('s') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            self.save_explicitly();
+            return Ok(None);
+        }
+        if let Some(action) = self.keymap.editor(&key) {
+            match action {
+                EditorAction::Quit => return self.exit_saved(),
+                EditorAction::Next => self.move_tab(1),
+                EditorAction::Prev => self.move_tab(-1),
+                EditorAction::SplitHorizontal => {
+                    self.split_selected(super::SplitDirection::Horizontal)
+                }
+                EditorAction::SplitVertical => self.split_selected(super::SplitDirection::Vertical),
+                EditorAction::InsertLeft => {
+                    self.insert_beside_selected(super::SplitDirection::Horizontal, Side::Left)
+                }
+                EditorAction::InsertRight => {
+                    self.insert_beside_selected(super::SplitDirection::Horizontal, Side::Right)
+                }
+                EditorAction::InsertUp => {
+                    self.insert_beside_selected(super::SplitDirection::Vertical, Side::Left)
+                }
+                EditorAction::InsertDown => {
+                    self.insert_beside_selected(super::SplitDirection::Vertical, Side::Right)
+                }
+                EditorAction::RatioUp => self.bump_ratio(self.ratio_step),
+                EditorAction::RatioDown => self.bump_ratio(-self.ratio_step),
+                EditorAction::Delete => self.confirm_delete_selected(),
+                EditorAction::EditCommand => self.start_edit_command(),
+                EditorAction::EditWorkset => self.start_edit_workset(),
+                EditorAction::Swap => self.swap_with_next(),
+                EditorAction::ApplyAll => self.apply_context_to_all_slots(),
+                EditorAction::OpenCwd => self.open_selected_cwd(),
+                EditorAction::Zoom => self.zoomed = !self.zoomed,
+                EditorAction::ToggleEnabled => self.toggle_selected_enabled(),
+                EditorAction::PreviewExec => self.preview_selected_exec(),
+                EditorAction::ListView => self.toggle_list_view(),
+            }
+            return Ok(None);
+        }
         match key.code {
-            KeyCode::Char('q') | KeyCode::Esc => return self.exit_saved(),
-            KeyCode::Char('l') => self.move_tab(1),
-            KeyCode::Char('k') => self.move_tab(-1),
-            KeyCode::Char('j') => self.move_tab(1),
+            KeyCode::Esc if self.zoomed => self.zoomed = false,
+            KeyCode::Esc => return self.exit_saved(),
             KeyCode::Tab => self.move_tab(1),
             KeyCode::BackTab => self.move_tab(-1),
-            KeyCode::Char('h') => self.split_selected(super::SplitDirection::Horizontal),
-            KeyCode::Char('v') => self.split_selected(super::SplitDirection::Vertical),
-            KeyCode::Char('s') => self.split_selected(super::SplitDirection::Vertical),
-            KeyCode::Char('+') => self.bump_ratio(0.1),
-            KeyCode::Char('-') => self.bump_ratio(-0.1),
-            KeyCode::Char('x') => self.confirm_delete_selected(),
-            KeyCode::Char('d') | KeyCode::Char('D') => self.confirm_delete_selected(),
-            KeyCode::Enter | KeyCode::Char('c') => {
+            KeyCode::Enter => {
                 self.start_edit_command();
             }
-            KeyCode::Char('e') | KeyCode::Char('E') => {
-                self.start_edit_workset();
-            }
             KeyCode::F(2) => {
                 self.start_edit_workset();
             }
-            KeyCode::Char('w') => {
-                self.swap_with_next();
-            }
             _ => {}
         }
         Ok(None)
@@ -420,6 +455,10 @@ impl EditorApp {
                 {
                     let double = self.is_double_click(&hit.path);
                     self.selected_path = hit.path.clone();
+                    self.leaf_drag = Some(LeafDrag {
+                        source: hit.path.clone(),
+                        target: None,
+                    });
                     if double {
                         self.split_selected(super::SplitDirection::Horizontal);
                     }
@@ -427,17 +466,57 @@ impl EditorApp {
                 }
             }
             MouseEventKind::Drag(MouseButton::Left) => {
-                self.update_drag(mouse, ui);
+                if self.active_drag.is_some() {
+                    self.update_drag(mouse, ui);
+                } else if let Some(drag) = self.leaf_drag.as_mut() {
+                    drag.target = ui
+                        .leaf_hits
+                        .iter()
+                        .find(|hit| point_in_rect(mouse.column, mouse.row, hit.rect))
+                        .map(|hit| hit.path.clone())
+                        .filter(|path| *path != drag.source);
+                }
             }
             MouseEventKind::Up(MouseButton::Left) => {
                 self.active_drag = None;
+                if let Some(LeafDrag {
+                    source,
+                    target: Some(target),
+                }) = self.leaf_drag.take()
+                {
+                    self.drop_leaf(&source, &target);
+                }
                 self.hover_split = hit_split(mouse.column, mouse.row, ui).map(|h| h.path);
             }
+            MouseEventKind::ScrollUp => self.handle_scroll(mouse, ui, 1),
+            MouseEventKind::ScrollDown => self.handle_scroll(mouse, ui, -1),
             _ => {}
         }
         Ok(None)
     }
 
+    /// Wheel over a split boundary nudges that split's ratio; over a leaf it moves the
+    /// selection between slots.
+    fn handle_scroll(&mut self, mouse: MouseEvent, ui: &UiMeta, direction: isize) {
+        if let Some(hit) = hit_split(mouse.column, mouse.row, ui) {
+            let delta = self.ratio_step * direction as f32;
+            if let Some((_, old, new)) = adjust_ratio(&mut self.root, &hit.path, delta)
+                && (old - new).abs() > 0.0001
+            {
+                self.mark_changed();
+            }
+            self.hover_split = Some(hit.path);
+            return;
+        }
+        if ui
+            .leaf_hits
+            .iter()
+            .any(|hit| point_in_rect(mouse.column, mouse.row, hit.rect))
+        {
+            self.move_tab(-direction);
+        }
+    }
+
     fn start_drag(&mut self, hit: super::SplitHit) {
         self.active_drag = Some(ActiveDrag { path: hit.path });
         self.message = Some("Drag to adjust split ratio".into());
@@ -470,8 +549,8 @@ impl EditorApp {
             ToolbarAction::Next => self.move_tab(1),
             ToolbarAction::SplitH => self.split_selected(super::SplitDirection::Horizontal),
             ToolbarAction::SplitV => self.split_selected(super::SplitDirection::Vertical),
-            ToolbarAction::RatioUp => self.bump_ratio(0.1),
-            ToolbarAction::RatioDown => self.bump_ratio(-0.1),
+            ToolbarAction::RatioUp => self.bump_ratio(self.ratio_step),
+            ToolbarAction::RatioDown => self.bump_ratio(-self.ratio_step),
             ToolbarAction::Delete => self.confirm_delete_selected(),
             ToolbarAction::EditCmd => self.start_edit_command(),
             ToolbarAction::Swap => self.swap_with_next(),
@@ -554,6 +633,7 @@ fn next_grapheme_end(text: &str, cursor: usize) -> Option<usize> {
 
 #[cfg(test)]
 mod tests {
+    use super::super::fixtures::test_editor;
     use super::*;
 
     #[test]
@@ -606,4 +686,99 @@ mod tests {
         assert_eq!(prev_grapheme_start(text, 1), Some(0));
         assert_eq!(prev_grapheme_start(text, 0), None);
     }
+
+    #[test]
+    fn repeated_autosave_failures_suspend_until_explicit_save() {
+        let (mut app, dir) = test_editor("autosave");
+        // A directory cannot be read as a config file, so every save fails.
+        app.config_path = dir.path().to_path_buf();
+        for _ in 0..super::super::AUTOSAVE_FAILURE_LIMIT - 1 {
+            app.autosave();
+        }
+        assert!(app.autosave_error.is_none());
+        app.autosave();
+        assert!(app.autosave_error.is_some());
+        app.autosave();
+        assert_eq!(app.autosave_failures, super::super::AUTOSAVE_FAILURE_LIMIT);
+
+        app.config_path = dir.config_path();
+        let ctrl_s = KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL);
+        app.handle_key(ctrl_s).unwrap();
+        assert!(app.autosave_error.is_none());
+        assert_eq!(app.message.as_deref(), Some("Saved ✓"));
+    }
+
+    #[test]
+    fn exit_saves_edits_autosave_missed_and_warns_once_when_it_cannot() {
+        let (mut app, dir) = test_editor("exit");
+        let config_path = dir.config_path();
+        app.config_path = dir.path().to_path_buf();
+        app.workset.name = "Renamed".into();
+        app.mark_changed();
+        assert!(app.unsaved);
+
+        // The config path is a directory, so the save on exit fails: stay, then leave.
+        let quit = KeyEvent::from(KeyCode::Char('q'));
+        assert!(app.handle_key(quit).unwrap().is_none());
+        assert!(app.message.as_deref().unwrap().starts_with("Save failed"));
+        assert!(app.handle_key(quit).unwrap().is_some());
+
+        // Once the path works again, exiting writes the pending edit.
+        app.config_path = config_path.clone();
+        app.exit_unsaved = false;
+        assert!(app.handle_key(quit).unwrap().is_some());
+        let saved = crate::config::AppConfig::load(&config_path)
+            .unwrap()
+            .unwrap();
+        assert_eq!(saved.worksets[0].name, "Renamed");
+    }
+
+    #[test]
+    fn slot_dialog_edits_the_raw_command_of_a_labelled_slot() {
+        let (mut app, dir) = test_editor("raw_cmd");
+        let slot = app.current_leaf_mut().expect("starter layout has slots");
+        slot.class = Some("kitty".into());
+        let original = slot.command.clone();
+
+        app.handle_key(KeyEvent::from(KeyCode::Enter)).unwrap();
+        let Mode::EditSlot { buffer, .. } = &app.mode else {
+            panic!("Enter should open the slot dialog");
+        };
+        assert_eq!(buffer, &original);
+        for ch in " --hold".chars() {
+            app.handle_key(KeyEvent::from(KeyCode::Char(ch))).unwrap();
+        }
+        app.handle_key(KeyEvent::from(KeyCode::Enter)).unwrap();
+
+        let saved = crate::config::AppConfig::load(&dir.config_path())
+            .unwrap()
+            .unwrap()
+            .worksets
+            .remove(0);
+        let mut commands = Vec::new();
+        saved.layout.unwrap().collect_commands(&mut commands);
+        assert!(commands.contains(&format!("{original} --hold")));
+    }
+
+    #[test]
+    fn preview_shows_the_slot_exec_with_merged_env() {
+        let (mut app, _dir) = test_editor("preview");
+        app.workset.env.insert("MODE".into(), "workset".into());
+        let slot = app.current_leaf_mut().expect("starter layout has slots");
+        slot.env.insert("MODE".into(), "slot".into());
+        slot.cwd = Some("/tmp/proj".into());
+        let command = slot.command.clone();
+
+        app.handle_key(KeyEvent::from(KeyCode::Char('p'))).unwrap();
+        let Mode::PreviewExec { exec, .. } = &app.mode else {
+            panic!("p should open the exec preview");
+        };
+        // The slot value comes last, so the shell uses it.
+        assert_eq!(
+            exec,
+            &format!("cd '/tmp/proj' && MODE='workset' MODE='slot' {command}")
+        );
+        app.handle_key(KeyEvent::from(KeyCode::Esc)).unwrap();
+        assert_eq!(app.mode, Mode::Normal);
+    }
 }
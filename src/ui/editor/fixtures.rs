@@ -0,0 +1,17 @@
+//! Editor test fixtures: an `EditorApp` on the starter config, in a temp dir that is removed
+//! even when an assert fails first.
+
+use super::EditorApp;
+use crate::{config::AppConfig, fixtures::TestDir};
+
+/// An editor on the first workset of the starter config written to `hyprsets.toml` in a fresh
+/// temp dir; `name` keeps the dirs of concurrent tests apart.
+pub(super) fn test_editor(name: &str) -> (EditorApp, TestDir) {
+    let dir = TestDir::new(name);
+    let config_path = dir.config_path();
+    let workset = AppConfig::load_or_init(&config_path)
+        .unwrap()
+        .worksets
+        .remove(0);
+    (EditorApp::new(workset, config_path), dir)
+}
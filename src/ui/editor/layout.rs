@@ -1,3 +1,5 @@
+use std::{collections::HashMap, path::PathBuf};
+
 use ratatui::layout::Rect;
 
 use crate::config::{LayoutNode, SplitDirection, SplitNode, WindowSlot, Workset};
@@ -8,14 +10,12 @@ pub(super) fn ensure_layout(ws: Workset) -> LayoutNode {
     if let Some(layout) = ws.layout.clone() {
         layout
     } else {
-        let cmd = ws.commands.first().cloned().unwrap_or_default();
-        LayoutNode::Leaf(WindowSlot {
-            slot_id: 1,
-            command: cmd,
-            cwd: None,
-            env: Default::default(),
-            wait_after_ms: None,
-        })
+        let cmd = ws
+            .effective_commands()
+            .into_iter()
+            .next()
+            .unwrap_or_default();
+        LayoutNode::Leaf(WindowSlot::new(1, cmd))
     }
 }
 
@@ -79,37 +79,56 @@ pub(super) fn first_leaf_path(node: &LayoutNode) -> Option<Vec<Side>> {
     }
 }
 
+/// Paths of every leaf in draw order (left before right).
+pub(super) fn leaf_paths(node: &LayoutNode) -> Vec<Vec<Side>> {
+    match node {
+        LayoutNode::Leaf(_) => vec![vec![]],
+        LayoutNode::Split(split) => {
+            let mut paths = Vec::new();
+            for (side, child) in [(Side::Left, &split.left), (Side::Right, &split.right)] {
+                for mut rest in leaf_paths(child) {
+                    rest.insert(0, side);
+                    paths.push(rest);
+                }
+            }
+            paths
+        }
+    }
+}
+
 pub(super) fn next_slot_id(root: &LayoutNode) -> u32 {
     let mut max_id = 0;
     collect_slot_ids(root, &mut max_id);
     max_id + 1
 }
 
+/// Splits the leaf at `path`, putting a new empty slot on `new_side` (left/top or right/bottom).
+/// The ratio keeps the existing slot slightly larger for horizontal splits either way.
 pub(super) fn replace_leaf_with_split(
     node: &mut LayoutNode,
     path: &[Side],
     direction: SplitDirection,
     new_slot_id: u32,
+    new_side: Side,
 ) -> bool {
     if path.is_empty() {
         if let LayoutNode::Leaf(existing) = node {
-            let new_leaf = LayoutNode::Leaf(WindowSlot {
-                slot_id: new_slot_id,
-                command: String::new(),
-                cwd: None,
-                env: Default::default(),
-                wait_after_ms: None,
-            });
+            let new_leaf = LayoutNode::Leaf(WindowSlot::new(new_slot_id, ""));
             let old_leaf = LayoutNode::Leaf(existing.clone());
+            let old_share = if matches!(direction, SplitDirection::Horizontal) {
+                1.2
+            } else {
+                1.0
+            };
+            let (left, right, ratio) = match new_side {
+                Side::Left => (new_leaf, old_leaf, 1.0 / old_share),
+                Side::Right => (old_leaf, new_leaf, old_share),
+            };
             let split = SplitNode {
                 direction,
-                ratio: if matches!(direction, SplitDirection::Horizontal) {
-                    1.2
-                } else {
-                    1.0
-                },
-                left: Box::new(old_leaf),
-                right: Box::new(new_leaf),
+                ratio,
+                left: Box::new(left),
+                right: Box::new(right),
             };
             *node = LayoutNode::Split(split);
             return true;
@@ -126,7 +145,7 @@ pub(super) fn replace_leaf_with_split(
             } else {
                 &mut split.right
             };
-            replace_leaf_with_split(child, rest, direction, new_slot_id)
+            replace_leaf_with_split(child, rest, direction, new_slot_id, new_side)
         }
     }
 }
@@ -158,12 +177,24 @@ pub(super) fn remove_leaf(node: &mut LayoutNode, path: &[Side]) -> bool {
     false
 }
 
-pub(super) fn collect_commands(node: &LayoutNode, commands: &mut Vec<String>) {
+/// Copies `cwd` and `env` onto every leaf; returns how many leaves actually changed.
+pub(super) fn apply_context_to_all(
+    node: &mut LayoutNode,
+    cwd: &Option<PathBuf>,
+    env: &HashMap<String, String>,
+) -> usize {
     match node {
-        LayoutNode::Leaf(slot) => commands.push(slot.command.clone()),
+        LayoutNode::Leaf(slot) => {
+            if slot.cwd == *cwd && slot.env == *env {
+                return 0;
+            }
+            slot.cwd = cwd.clone();
+            slot.env = env.clone();
+            1
+        }
         LayoutNode::Split(split) => {
-            collect_commands(&split.left, commands);
-            collect_commands(&split.right, commands);
+            apply_context_to_all(&mut split.left, cwd, env)
+                + apply_context_to_all(&mut split.right, cwd, env)
         }
     }
 }
@@ -212,6 +243,17 @@ pub(super) fn set_leaf_at_path(node: &mut LayoutNode, path: &[Side], slot: Windo
     }
 }
 
+/// Exchanges the slots at `a` and `b`; false if either path is not a leaf.
+pub(super) fn swap_leaves(node: &mut LayoutNode, a: &[Side], b: &[Side]) -> bool {
+    let (Some(first), Some(second)) = (
+        leaf_at_path(node, a).cloned(),
+        leaf_at_path(node, b).cloned(),
+    ) else {
+        return false;
+    };
+    set_leaf_at_path(node, a, second) && set_leaf_at_path(node, b, first)
+}
+
 pub(super) fn adjust_ratio(
     node: &mut LayoutNode,
     path: &[Side],
@@ -317,15 +359,10 @@ fn collect_slot_ids(node: &LayoutNode, max_id: &mut u32) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::{prelude::*, sample::Index};
 
     fn leaf(id: u32) -> LayoutNode {
-        LayoutNode::Leaf(WindowSlot {
-            slot_id: id,
-            command: format!("cmd{id}"),
-            cwd: None,
-            env: Default::default(),
-            wait_after_ms: None,
-        })
+        LayoutNode::Leaf(WindowSlot::new(id, format!("cmd{id}")))
     }
 
     #[test]
@@ -368,7 +405,8 @@ mod tests {
     #[test]
     fn replace_leaf_with_split_inserts_new_slot() {
         let mut node = leaf(10);
-        let ok = replace_leaf_with_split(&mut node, &[], SplitDirection::Horizontal, 11);
+        let ok =
+            replace_leaf_with_split(&mut node, &[], SplitDirection::Horizontal, 11, Side::Right);
         assert!(ok);
         match node {
             LayoutNode::Split(split) => {
@@ -386,6 +424,57 @@ mod tests {
         }
     }
 
+    fn split_children(node: &LayoutNode) -> (bool, u32, u32) {
+        match node {
+            LayoutNode::Split(split) => match (split.left.as_ref(), split.right.as_ref()) {
+                (LayoutNode::Leaf(l), LayoutNode::Leaf(r)) => {
+                    let vertical = matches!(split.direction, SplitDirection::Vertical);
+                    (vertical, l.slot_id, r.slot_id)
+                }
+                _ => panic!("split children should be leaves"),
+            },
+            _ => panic!("expected split"),
+        }
+    }
+
+    #[test]
+    fn replace_leaf_with_split_puts_new_slot_on_left() {
+        let mut node = LayoutNode::Split(SplitNode {
+            direction: SplitDirection::Vertical,
+            ratio: 1.0,
+            left: Box::new(leaf(1)),
+            right: Box::new(leaf(2)),
+        });
+        assert!(replace_leaf_with_split(
+            &mut node,
+            &[Side::Right],
+            SplitDirection::Horizontal,
+            3,
+            Side::Left,
+        ));
+        let LayoutNode::Split(outer) = &node else {
+            panic!("expected split");
+        };
+        assert_eq!(split_children(&outer.right), (false, 3, 2));
+        let LayoutNode::Split(inner) = outer.right.as_ref() else {
+            panic!("expected split");
+        };
+        assert!(inner.ratio < 1.0, "existing slot keeps the larger share");
+    }
+
+    #[test]
+    fn replace_leaf_with_split_puts_new_slot_on_top() {
+        let mut node = leaf(5);
+        assert!(replace_leaf_with_split(
+            &mut node,
+            &[],
+            SplitDirection::Vertical,
+            6,
+            Side::Left,
+        ));
+        assert_eq!(split_children(&node), (true, 6, 5));
+    }
+
     #[test]
     fn remove_leaf_replaces_parent_with_sibling() {
         let mut node = LayoutNode::Split(SplitNode {
@@ -409,13 +498,7 @@ mod tests {
             left: Box::new(leaf(1)),
             right: Box::new(leaf(2)),
         });
-        let new_slot = WindowSlot {
-            slot_id: 99,
-            command: "new".into(),
-            cwd: None,
-            env: Default::default(),
-            wait_after_ms: None,
-        };
+        let new_slot = WindowSlot::new(99, "new");
         assert!(set_leaf_at_path(
             &mut node,
             &[Side::Right],
@@ -430,6 +513,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn swap_leaves_exchanges_slots_and_rejects_splits() {
+        let mut node = LayoutNode::Split(SplitNode {
+            direction: SplitDirection::Horizontal,
+            ratio: 1.0,
+            left: Box::new(leaf(1)),
+            right: Box::new(leaf(2)),
+        });
+        assert!(swap_leaves(&mut node, &[Side::Left], &[Side::Right]));
+        assert_eq!(leaf_at_path(&node, &[Side::Left]).unwrap().slot_id, 2);
+        assert_eq!(leaf_at_path(&node, &[Side::Right]).unwrap().slot_id, 1);
+        assert!(!swap_leaves(&mut node, &[], &[Side::Right]));
+    }
+
     #[test]
     fn adjust_ratio_clamps_and_returns_old_new() {
         let mut node = LayoutNode::Split(SplitNode {
@@ -495,4 +592,216 @@ mod tests {
         let area = Rect::new(0, 0, 5, 1);
         assert!(ratio_from_position(area, SplitDirection::Vertical, 0, 0).is_none());
     }
+
+    #[test]
+    fn apply_context_to_all_updates_every_leaf() {
+        let mut tree = LayoutNode::Split(SplitNode {
+            direction: SplitDirection::Horizontal,
+            ratio: 1.0,
+            left: Box::new(leaf(1)),
+            right: Box::new(LayoutNode::Split(SplitNode {
+                direction: SplitDirection::Vertical,
+                ratio: 1.0,
+                left: Box::new(leaf(2)),
+                right: Box::new(leaf(3)),
+            })),
+        });
+        let cwd = Some(PathBuf::from("/tmp/project"));
+        let env = HashMap::from([("RUST_LOG".to_string(), "debug".to_string())]);
+
+        assert_eq!(apply_context_to_all(&mut tree, &cwd, &env), 3);
+        for path in [
+            vec![Side::Left],
+            vec![Side::Right, Side::Left],
+            vec![Side::Right, Side::Right],
+        ] {
+            let slot = leaf_at_path(&tree, &path).unwrap();
+            assert_eq!(slot.cwd, cwd);
+            assert_eq!(slot.env, env);
+        }
+        assert_eq!(apply_context_to_all(&mut tree, &cwd, &env), 0);
+    }
+
+    fn split_ratios(node: &LayoutNode, out: &mut Vec<f32>) {
+        if let LayoutNode::Split(split) = node {
+            out.push(split.ratio);
+            split_ratios(&split.left, out);
+            split_ratios(&split.right, out);
+        }
+    }
+
+    fn sorted_slot_ids(node: &LayoutNode) -> Vec<u32> {
+        let mut ids = Vec::new();
+        node.collect_slot_ids_list(&mut ids);
+        ids.sort_unstable();
+        ids
+    }
+
+    /// Invariants every edit must keep: each leaf path resolves, the first one is
+    /// `first_leaf_path`, commands match leaves one to one, ids are exactly `expected_ids`
+    /// (no duplicates) and ratios stay within the clamp.
+    fn assert_consistent(node: &LayoutNode, expected_ids: &[u32], context: &str) {
+        let paths = leaf_paths(node);
+        assert!(!paths.is_empty(), "{context}: no leaves");
+        for path in &paths {
+            assert!(
+                leaf_at_path(node, path).is_some(),
+                "{context}: dangling {path:?}"
+            );
+        }
+        assert_eq!(first_leaf_path(node).as_ref(), paths.first(), "{context}");
+        let mut commands = Vec::new();
+        node.collect_commands(&mut commands);
+        assert_eq!(commands.len(), paths.len(), "{context}");
+        assert_eq!(sorted_slot_ids(node), expected_ids, "{context}");
+        assert!(
+            next_slot_id(node) > *expected_ids.last().unwrap(),
+            "{context}"
+        );
+        let mut ratios = Vec::new();
+        split_ratios(node, &mut ratios);
+        assert_eq!(
+            ratios.len() + 1,
+            paths.len(),
+            "{context}: every split has two children"
+        );
+        for ratio in ratios {
+            assert!(
+                (RATIO_MIN..=RATIO_MAX).contains(&ratio),
+                "{context}: ratio {ratio}"
+            );
+        }
+    }
+
+    fn renumber(node: &mut LayoutNode, next: &mut u32) {
+        match node {
+            LayoutNode::Leaf(slot) => {
+                *next += 1;
+                slot.slot_id = *next;
+                slot.command = format!("cmd{next}");
+            }
+            LayoutNode::Split(split) => {
+                renumber(&mut split.left, next);
+                renumber(&mut split.right, next);
+            }
+        }
+    }
+
+    fn arb_direction() -> impl Strategy<Value = SplitDirection> {
+        prop_oneof![
+            Just(SplitDirection::Horizontal),
+            Just(SplitDirection::Vertical)
+        ]
+    }
+
+    /// Trees of up to 32 leaves, ratios within the clamp, slot ids 1.. in leaf order.
+    fn arb_layout() -> impl Strategy<Value = LayoutNode> {
+        Just(leaf(0))
+            .prop_recursive(6, 32, 2, |inner| {
+                (arb_direction(), RATIO_MIN..=RATIO_MAX, inner.clone(), inner).prop_map(
+                    |(direction, ratio, left, right)| {
+                        LayoutNode::Split(SplitNode {
+                            direction,
+                            ratio,
+                            left: Box::new(left),
+                            right: Box::new(right),
+                        })
+                    },
+                )
+            })
+            .prop_map(|mut node| {
+                renumber(&mut node, &mut 0);
+                node
+            })
+    }
+
+    /// One editor operation on the leaf an `Index` picks from `leaf_paths`.
+    #[derive(Debug, Clone)]
+    enum Edit {
+        Split(Index, SplitDirection, Side),
+        Remove(Index),
+        Swap(Index, Index),
+        SetCommand(Index),
+        AdjustRatio(Index, f32),
+    }
+
+    fn arb_edit() -> impl Strategy<Value = Edit> {
+        let side = prop_oneof![Just(Side::Left), Just(Side::Right)];
+        prop_oneof![
+            2 => (any::<Index>(), arb_direction(), side)
+                .prop_map(|(at, direction, side)| Edit::Split(at, direction, side)),
+            1 => any::<Index>().prop_map(Edit::Remove),
+            1 => (any::<Index>(), any::<Index>()).prop_map(|(a, b)| Edit::Swap(a, b)),
+            1 => any::<Index>().prop_map(Edit::SetCommand),
+            1 => (any::<Index>(), -3.0f32..3.0)
+                .prop_map(|(at, delta)| Edit::AdjustRatio(at, delta)),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn edit_sequences_keep_generated_trees_consistent(
+            mut node in arb_layout(),
+            edits in prop::collection::vec(arb_edit(), 0..40),
+        ) {
+            let mut ids = sorted_slot_ids(&node);
+            assert_consistent(&node, &ids, "generated");
+            for (step, edit) in edits.into_iter().enumerate() {
+                let context = format!("step {step}: {edit:?}");
+                let paths = leaf_paths(&node);
+                match edit {
+                    Edit::Split(at, direction, side) => {
+                        let id = next_slot_id(&node);
+                        let path = at.get(&paths);
+                        assert!(replace_leaf_with_split(&mut node, path, direction, id, side));
+                        assert_eq!(leaf_paths(&node).len(), paths.len() + 1, "{context}");
+                        ids.push(id);
+                    }
+                    Edit::Remove(at) => {
+                        let path = at.get(&paths);
+                        let removed = leaf_at_path(&node, path).unwrap().slot_id;
+                        let ok = remove_leaf(&mut node, path);
+                        assert_eq!(ok, paths.len() > 1, "{context}: only the last leaf stays");
+                        if ok {
+                            ids.retain(|id| *id != removed);
+                            assert_eq!(leaf_paths(&node).len(), paths.len() - 1, "{context}");
+                        }
+                    }
+                    Edit::Swap(a, b) => {
+                        let (path, other) = (a.get(&paths), b.get(&paths));
+                        let a = leaf_at_path(&node, path).unwrap().slot_id;
+                        let b = leaf_at_path(&node, other).unwrap().slot_id;
+                        assert!(swap_leaves(&mut node, path, other), "{context}");
+                        assert_eq!(leaf_at_path(&node, path).unwrap().slot_id, b);
+                        assert_eq!(leaf_at_path(&node, other).unwrap().slot_id, a);
+                        if let Some((_, parent)) = path.split_last() {
+                            assert!(!swap_leaves(&mut node, parent, other), "{context}");
+                        }
+                    }
+                    Edit::SetCommand(at) => {
+                        let path = at.get(&paths);
+                        let mut slot = leaf_at_path(&node, path).unwrap().clone();
+                        slot.command = format!("edited {step}");
+                        assert!(set_leaf_at_path(&mut node, path, slot), "{context}");
+                        let updated = leaf_at_path(&node, path).unwrap();
+                        assert_eq!(updated.command, format!("edited {step}"));
+                        if let Some((_, parent)) = path.split_last() {
+                            let stray = updated.clone();
+                            assert!(!set_leaf_at_path(&mut node, parent, stray), "{context}");
+                        }
+                    }
+                    Edit::AdjustRatio(at, delta) => {
+                        let path = at.get(&paths);
+                        let adjusted = adjust_ratio(&mut node, path, delta);
+                        assert_eq!(adjusted.is_some(), !path.is_empty(), "{context}");
+                        if let Some((_, old, new)) = adjusted {
+                            assert_eq!(new, clamp_ratio(old + delta), "{context}");
+                        }
+                    }
+                }
+                ids.sort_unstable();
+                assert_consistent(&node, &ids, &context);
+            }
+        }
+    }
 }
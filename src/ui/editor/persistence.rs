@@ -1,8 +1,15 @@
-use std::path::Path;
+use std::{fs, path::Path, time::Duration};
 
 use anyhow::{Result, bail};
 
-use crate::config::{AppConfig, Workset};
+use crate::{
+    config::{
+        AppConfig, DEFAULT_MESSAGE_TIMEOUT_SECS, DEFAULT_RATIO_STEP, DEFAULT_SPLIT_HIT_SLOP,
+        Workset, rename_workset_id,
+    },
+    keymap::KeyMap,
+    ui::selection::Selection,
+};
 
 use super::TabOption;
 
@@ -32,6 +39,40 @@ pub(super) fn load_tab_state(
     }
 }
 
+/// Editor-wide settings taken from the config file.
+pub(super) struct UiSettings {
+    pub(super) keymap: KeyMap,
+    pub(super) message_timeout: Option<Duration>,
+    pub(super) ratio_step: f32,
+    pub(super) split_hit_slop: u16,
+    pub(super) selection: Selection,
+    pub(super) detach_commands: bool,
+}
+
+/// Config problems were already reported when the config was loaded before opening the
+/// editor, so this quietly falls back to the defaults.
+pub(super) fn load_ui_settings(config_path: &Path) -> UiSettings {
+    fs::read_to_string(config_path)
+        .ok()
+        .and_then(|raw| toml::from_str::<AppConfig>(&raw).ok())
+        .map(|cfg| UiSettings {
+            keymap: KeyMap::from_config(&cfg.keys).0,
+            message_timeout: cfg.message_timeout(),
+            ratio_step: cfg.ratio_step(),
+            split_hit_slop: cfg.split_hit_slop(),
+            selection: Selection::from_config(&cfg),
+            detach_commands: cfg.detach_commands,
+        })
+        .unwrap_or_else(|| UiSettings {
+            keymap: KeyMap::default(),
+            message_timeout: Some(Duration::from_secs(DEFAULT_MESSAGE_TIMEOUT_SECS)),
+            ratio_step: DEFAULT_RATIO_STEP,
+            split_hit_slop: DEFAULT_SPLIT_HIT_SLOP,
+            selection: Selection::default(),
+            detach_commands: false,
+        })
+}
+
 pub(super) fn persist_workset(
     ws: &Workset,
     saved_id: &str,
@@ -39,6 +80,8 @@ pub(super) fn persist_workset(
     config_path: &Path,
 ) -> Result<()> {
     let mut cfg = AppConfig::load_or_init(config_path)?;
+    let mut ws = ws.clone();
+    ws.touch();
 
     if cfg
         .worksets
@@ -48,6 +91,11 @@ pub(super) fn persist_workset(
         bail!("ID already exists");
     }
 
+    // Renaming first keeps tab references pointing at the workset under its new id.
+    if ws.id != saved_id && cfg.worksets.iter().any(|w| w.id == saved_id) {
+        rename_workset_id(&mut cfg, saved_id, &ws.id)?;
+    }
+
     if let Some(idx) = cfg.worksets.iter().position(|w| w.id == saved_id) {
         cfg.worksets[idx] = ws.clone();
     } else if let Some(idx) = cfg.worksets.iter().position(|w| w.id == ws.id) {
@@ -59,11 +107,7 @@ pub(super) fn persist_workset(
     let mut previous_tab_id = None;
     let mut previous_pos = None;
     for tab in &cfg.tabs {
-        if let Some(pos) = tab
-            .worksets
-            .iter()
-            .position(|id| id == saved_id || id == &ws.id)
-        {
+        if let Some(pos) = tab.worksets.iter().position(|id| id == &ws.id) {
             previous_tab_id = Some(tab.id.clone());
             previous_pos = Some(pos);
             break;
@@ -72,7 +116,7 @@ pub(super) fn persist_workset(
 
     // apply tab assignment (single tab membership)
     for tab in cfg.tabs.iter_mut() {
-        tab.worksets.retain(|id| id != saved_id && id != &ws.id);
+        tab.worksets.retain(|id| id != &ws.id);
     }
     if let Some(target_tab) = selected_tab {
         if let Some(tab) = cfg.tabs.iter_mut().find(|t| &t.id == target_tab) {
@@ -103,6 +147,7 @@ mod tests {
     };
 
     use crate::config::{LayoutNode, SplitDirection, SplitNode, TabConfig, WindowSlot};
+    use crate::ui::home::harness::tab;
 
     fn temp_config_path(prefix: &str) -> std::path::PathBuf {
         let suffix = SystemTime::now()
@@ -115,13 +160,7 @@ mod tests {
     }
 
     fn sample_leaf(slot_id: u32, command: &str) -> LayoutNode {
-        LayoutNode::Leaf(WindowSlot {
-            slot_id,
-            command: command.to_string(),
-            cwd: None,
-            env: Default::default(),
-            wait_after_ms: None,
-        })
+        LayoutNode::Leaf(WindowSlot::new(slot_id, command))
     }
 
     fn sample_workset(id: &str) -> Workset {
@@ -129,11 +168,7 @@ mod tests {
             id: id.to_string(),
             name: format!("Workset {id}"),
             desc: "".into(),
-            workspace: None,
-            commands: vec![],
-            cwd: None,
-            env: Default::default(),
-            layout: None,
+            ..Default::default()
         }
     }
 
@@ -147,25 +182,17 @@ mod tests {
     fn load_tab_state_selects_first_matching_tab() {
         let path = temp_config_path("load_tab_state");
         let cfg = AppConfig {
-            version: 1,
-            default_tab: None,
-            show_all_tab: None,
-            all_tab_position: None,
             tabs: vec![
                 TabConfig {
-                    id: "t1".into(),
                     label: "Tab1".into(),
-                    worksets: vec!["w1".into()],
-                    include_unassigned: false,
+                    ..tab("t1", &["w1"], false)
                 },
                 TabConfig {
-                    id: "t2".into(),
                     label: "Tab2".into(),
-                    worksets: vec!["w1".into(), "w2".into()],
-                    include_unassigned: false,
+                    ..tab("t2", &["w1", "w2"], false)
                 },
             ],
-            worksets: vec![],
+            ..Default::default()
         };
         cfg.save(&path).unwrap();
 
@@ -183,32 +210,23 @@ mod tests {
             id: "old".into(),
             name: "Old".into(),
             desc: "".into(),
-            workspace: None,
             commands: vec!["oldcmd".into()],
-            cwd: None,
-            env: Default::default(),
             layout: Some(sample_leaf(1, "oldcmd")),
+            ..Default::default()
         };
         let cfg = AppConfig {
-            version: 1,
-            default_tab: None,
-            show_all_tab: None,
-            all_tab_position: None,
             tabs: vec![
                 TabConfig {
-                    id: "tabA".into(),
                     label: "A".into(),
-                    worksets: vec!["old".into()],
-                    include_unassigned: false,
+                    ..tab("tabA", &["old"], false)
                 },
                 TabConfig {
-                    id: "tabB".into(),
                     label: "B".into(),
-                    worksets: vec![],
-                    include_unassigned: false,
+                    ..tab("tabB", &[], false)
                 },
             ],
             worksets: vec![existing],
+            ..Default::default()
         };
         cfg.save(&path).unwrap();
 
@@ -218,14 +236,13 @@ mod tests {
             desc: "d".into(),
             workspace: Some("ws".into()),
             commands: vec!["newcmd".into()],
-            cwd: None,
-            env: Default::default(),
             layout: Some(LayoutNode::Split(SplitNode {
                 direction: SplitDirection::Vertical,
                 ratio: 1.0,
                 left: Box::new(sample_leaf(1, "a")),
                 right: Box::new(sample_leaf(2, "b")),
             })),
+            ..Default::default()
         };
         persist_workset(&new_ws, "old", &Some("tabB".into()), &path).unwrap();
 
@@ -237,6 +254,9 @@ mod tests {
         assert!(tab_a.worksets.is_empty());
         let tab_b = updated.tabs.iter().find(|t| t.id == "tabB").unwrap();
         assert_eq!(tab_b.worksets, vec!["new"]);
+        // Saving stamps the workset, and the stamp survives the TOML round trip.
+        let modified = updated.worksets[0].modified.expect("persist sets modified");
+        assert!(modified.elapsed().unwrap().as_secs() < 60);
 
         cleanup(&path);
     }
@@ -245,21 +265,14 @@ mod tests {
     fn persist_workset_errors_on_conflicting_id() {
         let path = temp_config_path("persist_workset_conflict");
         let cfg = AppConfig {
-            version: 1,
-            default_tab: None,
-            show_all_tab: None,
-            all_tab_position: None,
-            tabs: vec![],
             worksets: vec![Workset {
                 id: "other".into(),
                 name: "Other".into(),
                 desc: "".into(),
-                workspace: None,
-                commands: vec![],
-                cwd: None,
-                env: Default::default(),
                 layout: Some(sample_leaf(1, "x")),
+                ..Default::default()
             }],
+            ..Default::default()
         };
         cfg.save(&path).unwrap();
 
@@ -267,11 +280,8 @@ mod tests {
             id: "other".into(),
             name: "New".into(),
             desc: "".into(),
-            workspace: None,
-            commands: vec![],
-            cwd: None,
-            env: Default::default(),
             layout: Some(sample_leaf(2, "y")),
+            ..Default::default()
         };
         let err = persist_workset(&ws, "different", &None, &path).unwrap_err();
         assert!(format!("{err}").contains("ID already exists"));
@@ -284,20 +294,16 @@ mod tests {
         let path = temp_config_path("persist_workset_order");
         let cfg = AppConfig {
             version: 2,
-            default_tab: None,
-            show_all_tab: None,
-            all_tab_position: None,
             tabs: vec![TabConfig {
-                id: "tabA".into(),
                 label: "A".into(),
-                worksets: vec!["w1".into(), "w2".into(), "w3".into()],
-                include_unassigned: false,
+                ..tab("tabA", &["w1", "w2", "w3"], false)
             }],
             worksets: vec![
                 sample_workset("w1"),
                 sample_workset("w2"),
                 sample_workset("w3"),
             ],
+            ..Default::default()
         };
         cfg.save(&path).unwrap();
 
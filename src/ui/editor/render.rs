@@ -4,18 +4,29 @@ use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span, Text},
-    widgets::{Block, Borders, Clear, Paragraph},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
 };
 use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
-use crate::config::{LayoutNode, SplitDirection};
+use crate::{
+    config::{LayoutNode, SplitDirection},
+    ui::terminal::draw_too_small,
+};
 
 use super::{
-    DialogField, EditorApp, Mode, Side, SlotField, ToolbarAction, UiMeta, layout::split_area,
+    DialogField, EditorApp, Mode, Side, SlotField, ToolbarAction, UiMeta,
+    layout::{leaf_at_path, leaf_paths, split_area},
 };
 
+/// Header, the layout's `Min(8)`, status and toolbar rows plus the bottom spacer.
+const MIN_WIDTH: u16 = 40;
+const MIN_HEIGHT: u16 = 2 + 8 + 2 + 2 + 2;
+
 impl EditorApp {
-    pub(super) fn draw(&mut self, f: &mut Frame) -> UiMeta {
+    pub(super) fn draw(&self, f: &mut Frame) -> UiMeta {
+        if draw_too_small(f, MIN_WIDTH, MIN_HEIGHT) {
+            return UiMeta::default();
+        }
         let area = f.size();
         let chunks = Layout::default()
             .direction(Direction::Vertical)
@@ -37,22 +48,45 @@ impl EditorApp {
             header_spans.push(Span::raw(&self.workset.desc));
             header_spans.push(Span::raw(")"));
         }
-        let header = Paragraph::new(Line::from(header_spans));
+        let mut header_lines = vec![Line::from(header_spans)];
+        if let Some(err) = &self.autosave_error {
+            header_lines.push(Line::styled(
+                format!(" Autosave failing: {err} — changes not saved (Ctrl+S to retry) "),
+                Style::default()
+                    .fg(Color::White)
+                    .bg(Color::Red)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+        let header = Paragraph::new(header_lines);
         f.render_widget(header, chunks[0]);
 
         let mut parent_split = None;
         let mut leaf_hits = Vec::new();
         let mut split_hits = Vec::new();
-        self.render_layout(
-            f,
-            chunks[1],
-            &self.root,
-            &mut Vec::new(),
-            &mut leaf_hits,
-            &mut split_hits,
-            &mut parent_split,
-            &self.selected_path,
-        );
+        let zoomed_leaf = self
+            .zoomed
+            .then(|| leaf_at_path(&self.root, &self.selected_path))
+            .flatten()
+            .map(|slot| LayoutNode::Leaf(slot.clone()));
+        let (view_root, mut view_path) = match &zoomed_leaf {
+            Some(leaf) => (leaf, self.selected_path.clone()),
+            None => (&self.root, Vec::new()),
+        };
+        if self.list_view && zoomed_leaf.is_none() {
+            self.render_slot_list(f, chunks[1], &mut leaf_hits);
+        } else {
+            self.render_layout(
+                f,
+                chunks[1],
+                view_root,
+                &mut view_path,
+                &mut leaf_hits,
+                &mut split_hits,
+                &mut parent_split,
+                &self.selected_path,
+            );
+        }
         self.render_status(f, chunks[2], parent_split);
         let toolbar_hits = self.render_toolbar(f, chunks[3], self.hover_toolbar);
 
@@ -68,6 +102,9 @@ impl EditorApp {
             Mode::ConfirmDelete { target: _, slot_id } => {
                 self.render_confirm_delete(f, area, *slot_id)
             }
+            Mode::PreviewExec { slot_id, exec } => {
+                self.render_exec_preview(f, area, *slot_id, exec)
+            }
             Mode::Normal => {}
         }
 
@@ -93,18 +130,51 @@ impl EditorApp {
         match node {
             LayoutNode::Leaf(slot) => {
                 let is_selected = *path == self.selected_path;
-                let title = format!("#{}  {}", slot.slot_id, slot.command);
-                let block = Block::default().borders(Borders::ALL).title(Span::styled(
-                    title,
+                let is_drop_target = self
+                    .leaf_drag
+                    .as_ref()
+                    .and_then(|drag| drag.target.as_ref())
+                    .is_some_and(|target| target == path);
+                let app = slot
+                    .app
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|app| !app.is_empty())
+                    .map(|app| format!(" [{app}]"))
+                    .unwrap_or_default();
+                let disabled = if slot.enabled { "" } else { " (disabled)" };
+                let title = format!(
+                    "{}#{}{app}{disabled}  {}",
                     if is_selected {
-                        Style::default()
-                            .fg(Color::Black)
-                            .bg(Color::Cyan)
-                            .add_modifier(Modifier::BOLD)
+                        self.selection.symbol.as_str()
                     } else {
-                        Style::default().fg(Color::White)
+                        ""
                     },
-                ));
+                    slot.slot_id,
+                    slot.command
+                );
+                let border_style = if is_drop_target {
+                    Style::default().fg(Color::Yellow)
+                } else if !slot.enabled {
+                    Style::default().fg(Color::DarkGray)
+                } else {
+                    Style::default()
+                };
+                let block = Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(border_style)
+                    .title(Span::styled(
+                        title,
+                        if is_selected {
+                            self.selection.style
+                        } else if !slot.enabled {
+                            Style::default()
+                                .fg(Color::DarkGray)
+                                .add_modifier(Modifier::ITALIC)
+                        } else {
+                            Style::default().fg(Color::White)
+                        },
+                    ));
                 let inner = block.inner(area);
                 f.render_widget(block, area);
                 let text = Paragraph::new(" ").alignment(Alignment::Center);
@@ -155,6 +225,7 @@ impl EditorApp {
                 if boundary.width > 0 && boundary.height > 0 {
                     split_hits.push(super::SplitHit {
                         boundary,
+                        grab: widen_boundary(boundary, area, split.direction, self.split_hit_slop),
                         area,
                         path: split_path.clone(),
                         direction: split.direction,
@@ -199,6 +270,51 @@ impl EditorApp {
         }
     }
 
+    /// One row per slot in launch order; each row is also the slot's click target.
+    fn render_slot_list(&self, f: &mut Frame, area: Rect, hits: &mut Vec<super::LeafHit>) {
+        let block = Block::default()
+            .title("Slots in launch order")
+            .borders(Borders::ALL);
+        let inner = block.inner(area);
+        f.render_widget(block, area);
+
+        let paths = leaf_paths(&self.root);
+        let selected = paths
+            .iter()
+            .position(|path| *path == self.selected_path)
+            .unwrap_or(0);
+        let rows = inner.height as usize;
+        let start = (selected + 1).saturating_sub(rows);
+        let mut lines = Vec::new();
+        for (idx, path) in paths.into_iter().enumerate().skip(start).take(rows) {
+            let Some(slot) = leaf_at_path(&self.root, &path) else {
+                continue;
+            };
+            let is_selected = idx == selected;
+            let disabled = if slot.enabled { "" } else { " (disabled)" };
+            let text = format!(
+                "{}{:>2}. #{}{disabled}  {}",
+                self.selection.prefix(is_selected),
+                idx + 1,
+                slot.slot_id,
+                slot.command
+            );
+            let style = if is_selected {
+                self.selection.style
+            } else if !slot.enabled {
+                Style::default().fg(Color::DarkGray)
+            } else {
+                Style::default()
+            };
+            hits.push(super::LeafHit {
+                rect: Rect::new(inner.x, inner.y + lines.len() as u16, inner.width, 1),
+                path,
+            });
+            lines.push(Line::styled(text, style));
+        }
+        f.render_widget(Paragraph::new(Text::from(lines)), inner);
+    }
+
     fn render_status(
         &self,
         f: &mut Frame,
@@ -210,6 +326,7 @@ impl EditorApp {
             Mode::EditSlot { .. } => "Slot",
             Mode::EditWorkset { .. } => "Workset",
             Mode::ConfirmDelete { .. } => "Delete?",
+            Mode::PreviewExec { .. } => "Preview",
         };
         let split_info = if let Some((dir, ratio)) = parent_split {
             let dir_str = match dir {
@@ -220,7 +337,16 @@ impl EditorApp {
         } else {
             "split: <root>".into()
         };
-        let msg = self.message.as_deref().unwrap_or("");
+        let msg = self
+            .hover_toolbar
+            .map(ToolbarAction::help)
+            .or(self.message.as_deref())
+            .unwrap_or("");
+        let wheel_hint = if self.hover_split.is_some() {
+            "  wheel: adjust this split"
+        } else {
+            "  wheel: select slot"
+        };
         let line = Line::from(vec![
             Span::styled(
                 format!(" [{mode_label}] "),
@@ -228,7 +354,22 @@ impl EditorApp {
             ),
             Span::raw(split_info),
             Span::raw("  "),
-            Span::styled("[Autosave]", Style::default().fg(Color::Green)),
+            if self.autosave_error.is_some() {
+                Span::styled("[Autosave off]", Style::default().fg(Color::Red))
+            } else {
+                Span::styled("[Autosave]", Style::default().fg(Color::Green))
+            },
+            if self.zoomed {
+                Span::styled(
+                    "  [Zoom: z/Esc to exit]",
+                    Style::default().fg(Color::Magenta),
+                )
+            } else if self.list_view {
+                Span::styled("  [List: = to exit]", Style::default().fg(Color::Magenta))
+            } else {
+                Span::raw("")
+            },
+            Span::styled(wheel_hint, Style::default().fg(Color::DarkGray)),
             Span::raw("  "),
             Span::raw(msg),
         ]);
@@ -489,6 +630,32 @@ impl EditorApp {
         f.render_widget(Paragraph::new(Text::from(lines)), inner);
     }
 
+    fn render_exec_preview(&self, f: &mut Frame, area: Rect, slot_id: u32, exec: &str) {
+        let popup = centered_rect(70, 40, area);
+        f.render_widget(Clear, popup);
+        let block = Block::default()
+            .title(format!("Exec for slot #{slot_id}"))
+            .borders(Borders::ALL)
+            .style(Style::default().bg(Color::Black));
+        let inner = block.inner(popup);
+        let mut lines = vec![Line::from(exec.to_string()), Line::from("")];
+        if !self
+            .current_leaf()
+            .is_some_and(|slot| slot.enabled && !slot.command.trim().is_empty())
+        {
+            lines.push(Line::styled(
+                "This slot is not launched (disabled or empty).",
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+        lines.push(Line::from("[Esc Close]"));
+        f.render_widget(block, popup);
+        f.render_widget(
+            Paragraph::new(Text::from(lines)).wrap(Wrap { trim: false }),
+            inner,
+        );
+    }
+
     fn field_line(&self, label: &str, val: &str, focused: bool) -> Span<'static> {
         if focused {
             Span::styled(
@@ -510,13 +677,47 @@ pub(super) fn hit_toolbar(x: u16, y: u16, ui: &UiMeta) -> Option<ToolbarAction>
         .map(|hit| hit.action)
 }
 
+/// Split whose grab area holds the point; where widened areas overlap, the bar closest to
+/// the point wins (the earlier one on a tie).
 pub(super) fn hit_split(x: u16, y: u16, ui: &UiMeta) -> Option<super::SplitHit> {
     ui.split_hits
         .iter()
-        .find(|hit| point_in_rect(x, y, hit.boundary))
+        .filter(|hit| point_in_rect(x, y, hit.grab))
+        .min_by_key(|hit| boundary_distance(x, y, hit))
         .cloned()
 }
 
+fn boundary_distance(x: u16, y: u16, hit: &super::SplitHit) -> u16 {
+    match hit.direction {
+        SplitDirection::Horizontal => hit.boundary.x.abs_diff(x),
+        SplitDirection::Vertical => hit.boundary.y.abs_diff(y),
+    }
+}
+
+/// Grows the one-cell divider by `slop` cells on each side, across the split only.
+fn widen_boundary(boundary: Rect, area: Rect, direction: SplitDirection, slop: u16) -> Rect {
+    match direction {
+        SplitDirection::Horizontal => {
+            let x = boundary.x.saturating_sub(slop).max(area.x);
+            let right = (boundary.x + boundary.width + slop).min(area.x + area.width);
+            Rect {
+                x,
+                width: right.saturating_sub(x),
+                ..boundary
+            }
+        }
+        SplitDirection::Vertical => {
+            let y = boundary.y.saturating_sub(slop).max(area.y);
+            let bottom = (boundary.y + boundary.height + slop).min(area.y + area.height);
+            Rect {
+                y,
+                height: bottom.saturating_sub(y),
+                ..boundary
+            }
+        }
+    }
+}
+
 pub(super) fn point_in_rect(x: u16, y: u16, rect: Rect) -> bool {
     x >= rect.x && x < rect.x + rect.width && y >= rect.y && y < rect.y + rect.height
 }
@@ -611,7 +812,7 @@ fn width_up_to(text: &str, cursor: usize) -> u16 {
 
 #[cfg(test)]
 mod tests {
-    use super::super::{ButtonHit, SplitHit};
+    use super::super::{ButtonHit, SplitHit, fixtures::test_editor};
     use super::*;
     use ratatui::layout::Rect;
 
@@ -709,6 +910,7 @@ mod tests {
         let boundary = Rect::new(2, 3, 4, 1);
         ui.split_hits.push(SplitHit {
             boundary,
+            grab: boundary,
             area: Rect::new(0, 0, 10, 10),
             path: vec![],
             direction: SplitDirection::Horizontal,
@@ -717,4 +919,77 @@ mod tests {
         assert_eq!(hit.boundary, boundary);
         assert!(hit_split(6, 4, &ui).is_none());
     }
+
+    #[test]
+    fn hit_split_accepts_clicks_within_the_slop_and_prefers_the_nearest_bar() {
+        let mut ui = UiMeta::default();
+        let area = Rect::new(0, 0, 20, 10);
+        let outer = Rect::new(10, 0, 1, 10);
+        ui.split_hits.push(SplitHit {
+            boundary: outer,
+            grab: widen_boundary(outer, area, SplitDirection::Horizontal, 1),
+            area,
+            path: vec![Side::Left],
+            direction: SplitDirection::Horizontal,
+        });
+        let inner_area = Rect::new(11, 0, 9, 10);
+        let inner = Rect::new(12, 0, 1, 10);
+        ui.split_hits.push(SplitHit {
+            boundary: inner,
+            grab: widen_boundary(inner, inner_area, SplitDirection::Horizontal, 1),
+            area: inner_area,
+            path: vec![Side::Right, Side::Left],
+            direction: SplitDirection::Horizontal,
+        });
+
+        let hit = hit_split(9, 4, &ui).expect("one cell off the divider still hits");
+        assert_eq!(hit.path, vec![Side::Left]);
+        assert!(hit_split(8, 4, &ui).is_none());
+        let hit = hit_split(11, 4, &ui).expect("overlap resolves to a bar");
+        assert_eq!(hit.path, vec![Side::Left]);
+        let hit = hit_split(13, 4, &ui).expect("inner bar slop");
+        assert_eq!(hit.path, vec![Side::Right, Side::Left]);
+    }
+
+    #[test]
+    fn zoom_draws_only_the_selected_slot_without_touching_the_layout() {
+        use ratatui::{Terminal, backend::TestBackend};
+
+        let (mut app, _dir) = test_editor("zoom");
+        let before = format!("{:?}", app.root);
+        app.selected_path = vec![Side::Right];
+        app.zoomed = true;
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 30)).unwrap();
+        let mut meta = UiMeta::default();
+        terminal.draw(|f| meta = app.draw(f)).unwrap();
+
+        assert_eq!(meta.leaf_hits.len(), 1);
+        assert_eq!(meta.leaf_hits[0].path, vec![Side::Right]);
+        assert!(meta.split_hits.is_empty());
+        assert_eq!(format!("{:?}", app.root), before);
+    }
+
+    #[test]
+    fn list_view_draws_one_row_per_slot_in_launch_order() {
+        use ratatui::{Terminal, backend::TestBackend};
+
+        let (mut app, _dir) = test_editor("list");
+        app.toggle_list_view();
+        assert!(app.list_view);
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 30)).unwrap();
+        let mut meta = UiMeta::default();
+        terminal.draw(|f| meta = app.draw(f)).unwrap();
+        let paths: Vec<_> = meta.leaf_hits.iter().map(|hit| hit.path.clone()).collect();
+        assert_eq!(paths, leaf_paths(&app.root));
+        assert!(meta.leaf_hits.iter().all(|hit| hit.rect.height == 1));
+        assert!(meta.split_hits.is_empty());
+
+        // Splitting the other way makes a grid, which drops back to the layout view.
+        app.split_selected(SplitDirection::Vertical);
+        assert!(!app.list_view);
+        terminal.draw(|f| meta = app.draw(f)).unwrap();
+        assert!(!meta.split_hits.is_empty());
+    }
 }
@@ -6,17 +6,21 @@ use std::{
 };
 
 use anyhow::{Context, Result};
-use crossterm::{
-    cursor::{Hide, Show},
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyEventKind},
-    execute,
-    terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
-};
-use ratatui::{Terminal, backend::CrosstermBackend, widgets::TableState};
+use crossterm::event::{Event, KeyEventKind};
+use ratatui::{Terminal, backend::CrosstermBackend, layout::Rect, widgets::TableState};
 
 use crate::{
-    config::AppConfig,
+    config::{AppConfig, TabConfig},
+    keymap::KeyMap,
     state::{AppState, default_state_path},
+    ui::{
+        clock::local_time_hm,
+        editor::EditorEntry,
+        selection_preview::SelectionPreview,
+        status::MessageExpiry,
+        terminal::{TerminalSession, is_interrupt, next_event},
+        window_counts::WindowCounts,
+    },
 };
 
 mod actions;
@@ -24,16 +28,28 @@ mod actions_selection;
 mod actions_tabs;
 mod actions_worksets;
 mod events;
+#[cfg(test)]
+pub(crate) mod harness;
 mod render;
+#[cfg(test)]
+mod snapshots;
 mod tabs;
 
 const ALL_TAB_ID: &str = "all";
+const UNASSIGNED_VIEW_ID: &str = "unassigned";
 
 #[derive(Debug, Clone)]
 pub enum HomeExit {
     Quit,
-    Run { id: String, preconfirm_clean: bool },
-    Edit(String),
+    Run {
+        id: String,
+        preconfirm_clean: bool,
+        launch_here: bool,
+    },
+    Edit {
+        id: String,
+        entry: EditorEntry,
+    },
 }
 
 #[derive(Debug, Clone)]
@@ -64,25 +80,21 @@ pub fn run_home(
     initial_selected_id: Option<String>,
 ) -> Result<HomeExit> {
     let mut app = HomeApp::new(cfg, config_path.to_path_buf(), initial_selected_id);
-    let backend = CrosstermBackend::new(io::stdout());
-    let mut terminal = Terminal::new(backend)?;
-
-    enable_raw_mode()?;
-    execute!(io::stdout(), EnterAlternateScreen, EnableMouseCapture, Hide)?;
-    let result = app.event_loop(&mut terminal);
-    disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture,
-        Show
-    )?;
-
+    let mut session = TerminalSession::new(true)?;
+    let result = app.event_loop(session.terminal());
+    session.restore()?;
+    // A launch switches to its own workspace; anything else returns to where the user was.
+    if !matches!(result, Ok(HomeExit::Run { .. }))
+        && let Err(err) = app.selection_preview.restore()
+    {
+        eprintln!("warning: {err:#}");
+    }
     result
 }
 
 struct HomeApp {
     cfg: AppConfig,
+    keymap: KeyMap,
     config_path: PathBuf,
     state_path: PathBuf,
     state: AppState,
@@ -93,24 +105,50 @@ struct HomeApp {
     last_click: Option<LastClick>,
     hover_toolbar: Option<ToolbarAction>,
     hover_tab: Option<TabHitKind>,
+    /// The mouse rests on a list row, so the status line shows its full description.
+    hover_row: bool,
     numeric_input: Option<NumericInput>,
+    launch_here_id: Option<String>,
     tabs: Vec<ResolvedTab>,
     active_tab_idx: usize,
+    /// The "+ Add" button holds keyboard focus in the tab bar (one past the last tab); the
+    /// active tab's list stays on screen and Enter opens the New Tab dialog.
+    add_tab_focused: bool,
+    /// Last empty tab deleted without confirmation and its position, restored by `undo`.
+    deleted_tab: Option<(usize, TabConfig)>,
+    /// Read-only list of worksets not referenced by any tab; shadows the active tab while set.
+    unassigned_view: Option<ResolvedTab>,
     tab_selected_ids: HashMap<String, String>,
     workset_tab_labels: HashMap<String, String>,
+    tab_warnings: Vec<String>,
+    warnings_shown: bool,
+    message_expiry: MessageExpiry,
+    window_counts: WindowCounts,
+    selection_preview: SelectionPreview,
 }
 
 #[derive(Debug, Clone)]
 enum Mode {
     Normal,
-    ConfirmDelete { idx: usize },
+    ConfirmDelete {
+        idx: usize,
+    },
     ConfirmRun(ConfirmRunState),
     NewDialog(NewDialogState),
     TabNew(TabForm),
-    TabRename(TabRenameState),
-    ConfirmTabDelete { tab_id: String, tab_label: String },
+    TabEdit(TabEditState),
+    ConfirmTabDelete {
+        tab_id: String,
+        tab_label: String,
+        members: usize,
+    },
     TabAssign(TabAssignState),
+    TabPlace(TabPlaceState),
     TabMenu(TabMenuState),
+    Warnings {
+        scroll: usize,
+    },
+    ContextMenu(ContextMenuState),
 }
 
 #[derive(Debug, Clone)]
@@ -119,6 +157,8 @@ struct NewDialogState {
     name: String,
     desc: String,
     selected_tab: Option<String>,
+    /// Id of the template workset to copy, if any.
+    template: Option<String>,
     focus: DialogField,
 }
 
@@ -128,6 +168,7 @@ enum DialogField {
     Desc,
     Id,
     Tab,
+    Template,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -137,12 +178,25 @@ enum TabFormField {
     IncludeUnassigned,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TabEditField {
+    Label,
+    IncludeUnassigned,
+}
+
 #[derive(Debug, Clone)]
 struct ConfirmRunState {
     id: String,
+    launch_here: bool,
     workset_name: String,
     workspace_name: String,
     window_count: usize,
+    window_breakdown: String,
+    is_special: bool,
+    /// `y` closes the workspace's windows; false for a plain "launch anyway?" from `confirm`.
+    close_windows: bool,
+    /// Slot count when the layout is over `slot_warning_threshold`.
+    many_slots: Option<usize>,
 }
 
 #[derive(Debug, Clone)]
@@ -165,6 +219,24 @@ enum ToolbarAction {
     TabMenu,
 }
 
+impl ToolbarAction {
+    /// One-line help shown in the status bar while the pointer rests on the button.
+    fn help(self) -> &'static str {
+        match self {
+            Self::Run => "Launch the selected workset",
+            Self::Edit => "Open the selected workset in the layout editor",
+            Self::New => "Create a new workset",
+            Self::Clone => "Copy the selected workset under a new id",
+            Self::Delete => "Delete the selected workset",
+            Self::MoveUp => "Move the selected workset up in this tab",
+            Self::MoveDown => "Move the selected workset down in this tab",
+            Self::Quit => "Quit HyprSets",
+            Self::TabAssign => "Assign the selected workset to a tab",
+            Self::TabMenu => "Add, edit, delete or reorder tabs",
+        }
+    }
+}
+
 #[derive(Default, Debug, Clone)]
 struct UiMeta {
     data_start_y: u16,
@@ -172,6 +244,9 @@ struct UiMeta {
     visible_offset: usize,
     toolbar_hits: Vec<ButtonHit>,
     tab_hits: Vec<TabHit>,
+    context_menu: Option<Rect>,
+    /// Tab rows that fit in the assign modal, for paging.
+    tab_assign_rows: usize,
 }
 
 #[derive(Debug, Clone)]
@@ -194,21 +269,89 @@ struct TabForm {
     label: String,
     include_unassigned: bool,
     focus: TabFormField,
+    /// Workset to move into the tab once it is created (set when opened from tab-assign).
+    assign_workset: Option<String>,
 }
 
 #[derive(Debug, Clone)]
-struct TabRenameState {
+struct TabEditState {
     tab_id: String,
     label: String,
+    include_unassigned: bool,
+    focus: TabEditField,
 }
 
 #[derive(Debug, Clone)]
 struct TabAssignState {
     workset_id: String,
     cursor: usize,
+    /// First row drawn when the tabs do not all fit in the modal.
+    scroll: usize,
     memberships: Vec<TabAssignRow>,
 }
 
+impl TabAssignState {
+    /// The trailing "+ New tab…" row sits just past the existing tabs.
+    fn on_new_tab_row(&self) -> bool {
+        self.cursor == self.memberships.len()
+    }
+
+    fn row_count(&self) -> usize {
+        self.memberships.len() + 1
+    }
+
+    /// Moves the cursor by `delta` rows, clamped to the list, and scrolls just enough to keep
+    /// it among the `visible` rows.
+    fn move_cursor(&mut self, delta: isize, visible: usize) {
+        self.scroll = self.scroll_for(visible);
+        self.cursor = self
+            .cursor
+            .saturating_add_signed(delta)
+            .min(self.row_count() - 1);
+        self.scroll = self.scroll_for(visible);
+    }
+
+    /// The first row to draw with `visible` rows of room: the stored offset, nudged so the
+    /// cursor is on screen and no space is wasted past the last row.
+    fn scroll_for(&self, visible: usize) -> usize {
+        let visible = visible.max(1);
+        let scroll = if self.cursor < self.scroll {
+            self.cursor
+        } else if self.cursor >= self.scroll + visible {
+            self.cursor + 1 - visible
+        } else {
+            self.scroll
+        };
+        scroll.min(self.row_count().saturating_sub(visible))
+    }
+}
+
+/// Where a workset lands when it joins a tab it wasn't in before.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TabPosition {
+    Top,
+    Bottom,
+    /// Right after the workset last selected in the target tab; the bottom if it has none.
+    AfterCurrent,
+}
+
+const TAB_POSITIONS: [(TabPosition, &str); 3] = [
+    (TabPosition::Top, "Top"),
+    (TabPosition::Bottom, "Bottom"),
+    (
+        TabPosition::AfterCurrent,
+        "After the tab's selected workset",
+    ),
+];
+
+/// Follow-up to the assign modal asking where in `target_tab` the workset should go.
+#[derive(Debug, Clone)]
+struct TabPlaceState {
+    assign: TabAssignState,
+    target_tab: String,
+    cursor: usize,
+}
+
 #[derive(Debug, Clone)]
 struct TabAssignRow {
     tab_id: String,
@@ -221,11 +364,28 @@ struct TabMenuState {
     cursor: usize,
 }
 
+/// Right-click menu for a workset row, anchored at the click position.
+#[derive(Debug, Clone)]
+struct ContextMenuState {
+    idx: usize,
+    cursor: usize,
+    anchor: (u16, u16),
+}
+
+const CONTEXT_MENU_ITEMS: [(ToolbarAction, &str); 5] = [
+    (ToolbarAction::Run, "Run"),
+    (ToolbarAction::Edit, "Edit"),
+    (ToolbarAction::Clone, "Clone"),
+    (ToolbarAction::Delete, "Delete"),
+    (ToolbarAction::TabAssign, "Assign tab"),
+];
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum TabMenuItem {
     Add,
-    Rename,
+    Edit,
     Delete,
+    Duplicate,
     MoveLeft,
     MoveRight,
     Exit,
@@ -235,10 +395,21 @@ const NUMERIC_INPUT_TIMEOUT_MS: u64 = 900;
 
 impl HomeApp {
     fn new(cfg: AppConfig, config_path: PathBuf, initial_selected_id: Option<String>) -> Self {
+        Self::with_state_path(cfg, config_path, default_state_path(), initial_selected_id)
+    }
+
+    /// `new` with the tab selection state kept at `state_path` instead of the user's.
+    fn with_state_path(
+        cfg: AppConfig,
+        config_path: PathBuf,
+        state_path: PathBuf,
+        initial_selected_id: Option<String>,
+    ) -> Self {
         let mut app = Self {
+            keymap: KeyMap::from_config(&cfg.keys).0,
             cfg,
             config_path,
-            state_path: default_state_path(),
+            state_path,
             state: AppState::default(),
             table_state: TableState::default(),
             scroll: 0,
@@ -247,16 +418,30 @@ impl HomeApp {
             last_click: None,
             hover_toolbar: None,
             hover_tab: None,
+            hover_row: false,
             numeric_input: None,
+            launch_here_id: None,
             tabs: Vec::new(),
             active_tab_idx: 0,
+            add_tab_focused: false,
+            deleted_tab: None,
+            unassigned_view: None,
             tab_selected_ids: HashMap::new(),
             workset_tab_labels: HashMap::new(),
+            tab_warnings: Vec::new(),
+            warnings_shown: false,
+            message_expiry: MessageExpiry::default(),
+            window_counts: WindowCounts::default(),
+            selection_preview: SelectionPreview::default(),
         };
         app.load_and_apply_tabs(initial_selected_id);
         app
     }
 
+    fn status_clock(&self) -> Option<String> {
+        self.cfg.show_clock.then(local_time_hm)
+    }
+
     fn save(&self) -> Result<()> {
         self.cfg
             .save(&self.config_path)
@@ -267,26 +452,59 @@ impl HomeApp {
         &mut self,
         terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     ) -> Result<HomeExit> {
+        let mut ui_meta = UiMeta::default();
+        let mut redraw = true;
+        let mut drawn_clock = None;
         loop {
-            let mut ui_meta = UiMeta::default();
-            terminal.draw(|f| {
-                ui_meta = self.draw(f);
-            })?;
+            if redraw {
+                if self.cfg.show_window_counts {
+                    self.window_counts.refresh();
+                }
+                drawn_clock = self.status_clock();
+                terminal.draw(|f| {
+                    ui_meta = self.draw(f);
+                })?;
+            }
 
-            match event::read()? {
+            let Some(event) = next_event()? else {
+                // Idle tick: only redraw when something time-based changed on screen.
+                let expired = self
+                    .message_expiry
+                    .tick(&mut self.message, self.cfg.message_timeout());
+                redraw = expired
+                    || self.status_clock() != drawn_clock
+                    || (self.cfg.show_window_counts && self.window_counts.refresh());
+                if self.cfg.preview_workspace_on_select
+                    && let Err(err) = self.selection_preview.tick(&self.cfg)
+                {
+                    self.message = Some(format!("Workspace preview failed: {err:#}"));
+                    redraw = true;
+                }
+                continue;
+            };
+            redraw = true;
+            match event {
                 Event::Key(key) if key.kind == KeyEventKind::Press => {
+                    if is_interrupt(&key) {
+                        return Ok(HomeExit::Quit);
+                    }
                     if let Some(exit) = self.handle_key(key, &ui_meta)? {
+                        self.record_run(&exit);
                         return Ok(exit);
                     }
                 }
                 Event::Mouse(mouse) => {
                     if let Some(exit) = self.handle_mouse(mouse, &ui_meta)? {
+                        self.record_run(&exit);
                         return Ok(exit);
                     }
                 }
                 Event::Resize(_, _) => {}
                 _ => {}
             }
+            if self.cfg.preview_workspace_on_select {
+                self.selection_preview.track(self.current_id());
+            }
         }
     }
 }
@@ -298,6 +516,7 @@ impl Default for NewDialogState {
             name: String::new(),
             desc: String::new(),
             selected_tab: None,
+            template: None,
             focus: DialogField::Name,
         }
     }
@@ -330,3 +549,18 @@ fn cycle_new_dialog_tab(
         tabs.get(next - 1).map(|t| t.id.clone())
     };
 }
+
+/// Steps through `<none>` followed by every template workset id.
+fn cycle_new_dialog_template(form: &mut NewDialogState, templates: &[String], delta: isize) {
+    let options_len = templates.len() as isize + 1;
+    let current_idx = form
+        .template
+        .as_ref()
+        .and_then(|id| templates.iter().position(|t| t == id))
+        .map(|idx| idx as isize + 1)
+        .unwrap_or(0);
+    let next = (current_idx + delta).rem_euclid(options_len) as usize;
+    form.template = next
+        .checked_sub(1)
+        .and_then(|idx| templates.get(idx).cloned());
+}
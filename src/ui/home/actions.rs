@@ -22,16 +22,42 @@ impl HomeApp {
             name: String::new(),
             desc: String::new(),
             selected_tab: self.default_new_tab(),
+            template: None,
             focus: super::DialogField::Name,
         }
     }
 
+    pub(super) fn template_ids(&self) -> Vec<String> {
+        self.cfg
+            .worksets
+            .iter()
+            .filter(|ws| ws.template)
+            .map(|ws| ws.id.clone())
+            .collect()
+    }
+
     pub(super) fn default_new_tab(&self) -> Option<String> {
+        if self.unassigned_view.is_some() {
+            return None;
+        }
         self.current_tab_id()
             .filter(|id| *id != ALL_TAB_ID)
             .map(str::to_string)
     }
 
+    pub(super) fn is_dense(&self) -> bool {
+        self.state.dense.unwrap_or(self.cfg.dense)
+    }
+
+    pub(super) fn toggle_dense(&mut self) {
+        let dense = !self.is_dense();
+        self.state.dense = Some(dense);
+        self.message = Some(if dense { "Dense view" } else { "Full view" }.into());
+        if let Err(err) = self.state.save(&self.state_path) {
+            self.message = Some(format!("failed to save view state: {err}"));
+        }
+    }
+
     pub(super) fn hide_cursor(&self) -> Result<()> {
         execute!(io::stdout(), Hide)?;
         Ok(())
@@ -35,6 +35,21 @@ impl HomeApp {
             .map(|hit| hit.kind)
     }
 
+    /// The `No` shown for each row of the current tab, in display order, with the row's index
+    /// into `cfg.worksets`. The table and numeric quick-select both read this list, so typing a
+    /// number always lands on the row labeled with it.
+    pub(super) fn numbered_rows(&self) -> Vec<(usize, usize)> {
+        self.current_tab()
+            .map(|tab| {
+                tab.indices
+                    .iter()
+                    .enumerate()
+                    .map(|(local_idx, global_idx)| (local_idx + 1, *global_idx))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
     pub(super) fn ensure_offset(&mut self, visible_rows: usize) {
         if let Some(selected) = self.table_state.selected() {
             if selected < self.scroll {
@@ -58,9 +73,10 @@ impl HomeApp {
         let len_isize = len as isize;
         let current = self.table_state.selected().map(|i| i as isize).unwrap_or(0);
 
-        let next = if delta == 1 && current == len_isize - 1 {
+        let wrap = self.cfg.wrap_navigation;
+        let next = if wrap && delta == 1 && current == len_isize - 1 {
             0
-        } else if delta == -1 && current == 0 {
+        } else if wrap && delta == -1 && current == 0 {
             len_isize - 1
         } else {
             let mut next = current + delta;
@@ -118,8 +134,11 @@ impl HomeApp {
             }
         };
 
-        let idx = value - 1;
-        if idx < self.current_tab_len() {
+        if let Some(idx) = self
+            .numbered_rows()
+            .iter()
+            .position(|(number, _)| *number == value)
+        {
             self.select_index(idx, visible_rows);
         } else {
             self.message = Some(format!("Workset {} not available in this tab", value));
@@ -5,27 +5,37 @@ use crossterm::{cursor::Show, execute};
 use rand::{Rng, distributions::Alphanumeric};
 
 use super::{
-    ALL_TAB_ID, HomeApp, ResolvedTab, TabAssignRow, TabAssignState, TabForm, TabFormField,
-    TabRenameState,
+    ALL_TAB_ID, HomeApp, ResolvedTab, TabAssignRow, TabAssignState, TabEditField, TabEditState,
+    TabForm, TabFormField, TabPlaceState, TabPosition,
 };
 
 impl HomeApp {
     pub(super) fn start_tab_new(&mut self) -> Result<()> {
+        self.open_tab_new(None)
+    }
+
+    /// Open the New Tab dialog from tab-assign; saving moves `workset_id` into the new tab.
+    pub(super) fn start_tab_new_for_workset(&mut self, workset_id: String) -> Result<()> {
+        self.open_tab_new(Some(workset_id))
+    }
+
+    fn open_tab_new(&mut self, assign_workset: Option<String>) -> Result<()> {
         execute!(io::stdout(), Show)?;
         let form = TabForm {
             id: self.generate_tab_id(),
             label: String::new(),
             include_unassigned: false,
             focus: TabFormField::Label,
+            assign_workset,
         };
         self.mode = super::Mode::TabNew(form);
         Ok(())
     }
 
-    pub(super) fn start_tab_rename(&mut self) -> Result<()> {
+    pub(super) fn start_tab_edit(&mut self) -> Result<()> {
         execute!(io::stdout(), Show)?;
         let Some(tab_id) = self.current_tab_id().filter(|id| *id != ALL_TAB_ID) else {
-            self.message = Some("All tab cannot be renamed".into());
+            self.message = Some("All tab cannot be edited".into());
             self.hide_cursor()?;
             return Ok(());
         };
@@ -34,30 +44,60 @@ impl HomeApp {
             self.hide_cursor()?;
             return Ok(());
         };
-        let form = TabRenameState {
+        let form = TabEditState {
             tab_id: tab.id.clone(),
             label: tab.label.clone(),
+            include_unassigned: tab.include_unassigned,
+            focus: TabEditField::Label,
         };
-        self.mode = super::Mode::TabRename(form);
+        self.mode = super::Mode::TabEdit(form);
         Ok(())
     }
 
     pub(super) fn start_tab_delete(&mut self) -> Result<()> {
-        if let Some(tab_id) = self.current_tab_id().filter(|id| *id != ALL_TAB_ID) {
-            let tab_label = self
-                .cfg
-                .tabs
-                .iter()
-                .find(|t| t.id == tab_id)
-                .map(|t| t.label.clone())
-                .unwrap_or_else(|| tab_id.to_string());
-            self.mode = super::Mode::ConfirmTabDelete {
-                tab_id: tab_id.to_string(),
-                tab_label,
-            };
-        } else {
+        let Some(tab_id) = self.current_tab_id().filter(|id| *id != ALL_TAB_ID) else {
             self.message = Some("All tab cannot be deleted".into());
+            return Ok(());
+        };
+        let tab_id = tab_id.to_string();
+        let Some(idx) = self.cfg.tabs.iter().position(|t| t.id == tab_id) else {
+            self.message = Some("Tab not found".into());
+            return Ok(());
+        };
+        let tab = &self.cfg.tabs[idx];
+        let members = tab.worksets.len();
+        if members == 0 && !self.cfg.confirm_empty_tab_delete {
+            let tab = tab.clone();
+            self.delete_tab(&tab_id)?;
+            self.message = Some(format!("Deleted empty tab: {} (z to undo)", tab.label));
+            self.deleted_tab = Some((idx, tab));
+            return Ok(());
+        }
+        self.mode = super::Mode::ConfirmTabDelete {
+            tab_label: tab.label.clone(),
+            tab_id,
+            members,
+        };
+        Ok(())
+    }
+
+    /// Puts back the last empty tab deleted without confirmation, at its old position.
+    pub(super) fn undo_tab_delete(&mut self) -> Result<()> {
+        let Some((idx, tab)) = self.deleted_tab.take() else {
+            self.message = Some("Nothing to undo".into());
+            return Ok(());
+        };
+        if self.cfg.tabs.iter().any(|t| t.id == tab.id) {
+            self.message = Some(format!("Cannot restore tab: id '{}' is taken", tab.id));
+            return Ok(());
         }
+        self.message = Some(format!("Restored tab: {}", tab.label));
+        let tab_id = tab.id.clone();
+        let idx = idx.min(self.cfg.tabs.len());
+        self.cfg.tabs.insert(idx, tab);
+        let selection = self.current_id();
+        self.save()?;
+        self.apply_tabs(Some(tab_id), selection);
         Ok(())
     }
 
@@ -66,10 +106,6 @@ impl HomeApp {
             self.message = Some("Select a workset first".into());
             return Ok(());
         };
-        if self.cfg.tabs.is_empty() {
-            self.message = Some("No tabs to assign. Create a tab first.".into());
-            return Ok(());
-        }
         let mut memberships: Vec<TabAssignRow> = self
             .cfg
             .tabs
@@ -100,6 +136,7 @@ impl HomeApp {
         self.mode = super::Mode::TabAssign(TabAssignState {
             workset_id: ws.id,
             cursor,
+            scroll: 0,
             memberships,
         });
         Ok(())
@@ -125,21 +162,46 @@ impl HomeApp {
     }
 
     pub(super) fn move_tab(&mut self, delta: isize) -> Result<()> {
-        if self.tabs.len() <= 1 {
+        if self.unassigned_view.is_some() {
+            self.toggle_unassigned_view();
             return Ok(());
         }
-        let len = self.tabs.len() as isize;
-        let mut next = self.active_tab_idx as isize + delta;
+        if self.tabs.is_empty() {
+            return Ok(());
+        }
+        // The "+ Add" button is the last stop, right after the last tab.
+        let len = self.tabs.len() as isize + 1;
+        let add_idx = len - 1;
+        let current = if self.add_tab_focused {
+            add_idx
+        } else {
+            self.active_tab_idx as isize
+        };
+        let mut next = current + delta;
         if next < 0 {
-            next = len - 1;
+            next = if self.cfg.wrap_navigation { len - 1 } else { 0 };
         } else if next >= len {
-            next = 0;
+            next = if self.cfg.wrap_navigation { 0 } else { len - 1 };
+        }
+        if next == add_idx {
+            self.add_tab_focused = true;
+            self.message = Some("Enter: add a tab".into());
+        } else {
+            self.switch_tab(next as usize)?;
         }
-        self.switch_tab(next as usize)?;
         Ok(())
     }
 
     pub(super) fn switch_tab(&mut self, idx: usize) -> Result<()> {
+        self.add_tab_focused = false;
+        if self.unassigned_view.is_some() && idx < self.tabs.len() {
+            self.unassigned_view = None;
+            self.message = None;
+            if idx == self.active_tab_idx {
+                self.apply_selection_for_active_tab(None);
+                return Ok(());
+            }
+        }
         if idx >= self.tabs.len() || idx == self.active_tab_idx {
             return Ok(());
         }
@@ -154,6 +216,9 @@ impl HomeApp {
     }
 
     pub(super) fn persist_last_tab(&mut self) {
+        if self.unassigned_view.is_some() {
+            return;
+        }
         if let Some(id) = self.current_tab_id() {
             self.state.last_tab_id = Some(id.to_string());
             if let Err(err) = self.state.save(&self.state_path) {
@@ -163,7 +228,9 @@ impl HomeApp {
     }
 
     pub(super) fn current_tab(&self) -> Option<&ResolvedTab> {
-        self.tabs.get(self.active_tab_idx)
+        self.unassigned_view
+            .as_ref()
+            .or_else(|| self.tabs.get(self.active_tab_idx))
     }
 
     pub(super) fn current_tab_id(&self) -> Option<&str> {
@@ -197,7 +264,7 @@ impl HomeApp {
 
     pub(super) fn current_user_tab_index(&self) -> Option<usize> {
         let id = self.current_tab_id()?;
-        if id == ALL_TAB_ID {
+        if id == ALL_TAB_ID || self.unassigned_view.is_some() {
             None
         } else {
             self.cfg.tabs.iter().position(|t| t.id == id)
@@ -246,25 +313,94 @@ impl HomeApp {
             label: label.to_string(),
             worksets: Vec::new(),
             include_unassigned: form.include_unassigned,
+            pinned: false,
+            default_cleanup: None,
+            unassigned_order: Default::default(),
         });
-        self.message = Some(format!("Tab added: {id}"));
+        let mut selection = self.current_id();
+        if let Some(workset_id) = &form.assign_workset {
+            self.assign_workset_to_tab(workset_id, Some(id), None);
+            self.message = Some(format!("Tab added: {id} (assigned {workset_id})"));
+            selection = Some(workset_id.clone());
+        } else {
+            self.message = Some(format!("Tab added: {id}"));
+        }
         self.save()?;
-        self.apply_tabs(Some(id.to_string()), self.current_id());
+        self.apply_tabs(Some(id.to_string()), selection);
         Ok(true)
     }
 
-    pub(super) fn rename_tab(&mut self, form: &TabRenameState) -> Result<bool> {
+    /// Copies the current tab next to itself, with a copy of every member workset, and saves
+    /// once at the end.
+    pub(super) fn duplicate_current_tab(&mut self) -> Result<()> {
+        let Some(idx) = self.current_user_tab_index() else {
+            self.message = Some("Only user tabs can be duplicated".into());
+            return Ok(());
+        };
+        let tab = self.cfg.tabs[idx].clone();
+        let mut new_id = format!("{}-copy", tab.id);
+        let mut n = 2;
+        while self.cfg.tabs.iter().any(|t| t.id == new_id) {
+            new_id = format!("{}-copy-{n}", tab.id);
+            n += 1;
+        }
+        let mut members = Vec::with_capacity(tab.worksets.len());
+        for id in &tab.worksets {
+            // Missing members are config warnings already; there is nothing to copy.
+            if let Some(ws_idx) = self.cfg.worksets.iter().position(|ws| &ws.id == id)
+                && let Some(copy_id) = self.push_workset_copy(ws_idx)
+            {
+                members.push(copy_id);
+            }
+        }
+        let label = format!("{} (copy)", tab.label);
+        self.message = Some(format!(
+            "Tab duplicated: {label} ({} workset(s))",
+            members.len()
+        ));
+        let selection = members.first().cloned();
+        self.cfg.tabs.insert(
+            idx + 1,
+            crate::config::TabConfig {
+                id: new_id.clone(),
+                label,
+                worksets: members,
+                // Only the first such tab receives unassigned worksets anyway.
+                include_unassigned: false,
+                pinned: false,
+                ..tab
+            },
+        );
+        self.save()?;
+        self.apply_tabs(Some(new_id), selection);
+        Ok(())
+    }
+
+    pub(super) fn update_tab(&mut self, form: &TabEditState) -> Result<bool> {
         let label = form.label.trim();
         if label.is_empty() {
             self.message = Some("Tab label is required".into());
             return Ok(false);
         }
+        // Unassigned worksets only ever land in the first such tab, so a second one stays empty.
+        let other_unassigned = self
+            .cfg
+            .tabs
+            .iter()
+            .find(|t| t.include_unassigned && t.id != form.tab_id)
+            .map(|t| t.label.clone());
         let Some(tab) = self.cfg.tabs.iter_mut().find(|t| t.id == form.tab_id) else {
             self.message = Some("Tab not found".into());
             return Ok(false);
         };
         tab.label = label.to_string();
-        self.message = Some("Tab renamed".into());
+        tab.include_unassigned = form.include_unassigned;
+        self.message = Some(match other_unassigned {
+            Some(other) if form.include_unassigned => format!(
+                "Tab saved; warning: '{other}' already includes unassigned worksets, so this tab gets none"
+            ),
+            _ => "Tab saved".into(),
+        });
         self.save()?;
         self.apply_tabs(Some(form.tab_id.clone()), self.current_id());
         Ok(true)
@@ -284,7 +420,14 @@ impl HomeApp {
         Ok(())
     }
 
-    pub(super) fn assign_workset_to_tab(&mut self, workset_id: &str, tab_id: Option<&str>) -> bool {
+    /// Moves the workset into `tab_id` (or out of every tab). Without a `position` it keeps its
+    /// place when it stays in the same tab and goes to the end otherwise.
+    pub(super) fn assign_workset_to_tab(
+        &mut self,
+        workset_id: &str,
+        tab_id: Option<&str>,
+        position: Option<TabPosition>,
+    ) -> bool {
         if self.cfg.tabs.is_empty() {
             return true;
         }
@@ -302,11 +445,18 @@ impl HomeApp {
             tab.worksets.retain(|id| id != workset_id);
         }
         if let Some(target) = tab_id {
+            let anchor = self.tab_selected_ids.get(target).cloned();
             if let Some(tab) = self.cfg.tabs.iter_mut().find(|t| t.id == target) {
-                let insert_at = if previous_tab_id.as_deref() == Some(target) {
-                    previous_pos.unwrap_or(tab.worksets.len())
-                } else {
-                    tab.worksets.len()
+                let insert_at = match position {
+                    Some(TabPosition::Top) => 0,
+                    Some(TabPosition::Bottom) => tab.worksets.len(),
+                    Some(TabPosition::AfterCurrent) => anchor
+                        .and_then(|anchor| tab.worksets.iter().position(|id| *id == anchor))
+                        .map_or(tab.worksets.len(), |pos| pos + 1),
+                    None if previous_tab_id.as_deref() == Some(target) => {
+                        previous_pos.unwrap_or(tab.worksets.len())
+                    }
+                    None => tab.worksets.len(),
                 }
                 .min(tab.worksets.len());
                 tab.worksets.insert(insert_at, workset_id.to_string());
@@ -327,15 +477,59 @@ impl HomeApp {
         }
     }
 
-    pub(super) fn apply_tab_assignment(&mut self, state: &TabAssignState) -> Result<()> {
+    /// Applies the assign modal, or asks where the workset goes first when it joins a
+    /// different tab that already has worksets.
+    pub(super) fn confirm_tab_assignment(
+        &mut self,
+        state: TabAssignState,
+        visible_rows: usize,
+    ) -> Result<()> {
+        let target_tab = state
+            .memberships
+            .iter()
+            .find(|row| row.member)
+            .map(|row| row.tab_id.clone());
+        let joins_other_tab = target_tab.as_deref().is_some_and(|target| {
+            self.cfg.tabs.iter().any(|t| {
+                t.id == target && !t.worksets.is_empty() && !t.worksets.contains(&state.workset_id)
+            })
+        });
+        match target_tab {
+            Some(target_tab) if joins_other_tab => {
+                self.mode = super::Mode::TabPlace(TabPlaceState {
+                    assign: state,
+                    target_tab,
+                    cursor: 1,
+                });
+            }
+            _ => {
+                self.mode = super::Mode::Normal;
+                self.apply_tab_assignment(&state, None, visible_rows)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub(super) fn apply_tab_assignment(
+        &mut self,
+        state: &TabAssignState,
+        position: Option<TabPosition>,
+        visible_rows: usize,
+    ) -> Result<()> {
         self.ensure_tab_version();
+        let previous_tab = self
+            .cfg
+            .tabs
+            .iter()
+            .find(|tab| tab.worksets.contains(&state.workset_id))
+            .map(|tab| tab.id.clone());
         let target_tab = state
             .memberships
             .iter()
             .find(|row| row.member)
             .map(|row| row.tab_id.clone());
 
-        self.assign_workset_to_tab(&state.workset_id, target_tab.as_deref());
+        self.assign_workset_to_tab(&state.workset_id, target_tab.as_deref(), position);
         if self.message.is_none() {
             if target_tab.is_some() {
                 self.message = Some("Tab assignment updated".into());
@@ -343,9 +537,13 @@ impl HomeApp {
                 self.message = Some("Workset unassigned from tabs".into());
             }
         }
-        let preferred_tab = self.current_tab_id().map(str::to_string);
         self.save()?;
-        self.apply_tabs(preferred_tab, Some(state.workset_id.clone()));
+        let touched: Vec<&str> = previous_tab
+            .iter()
+            .chain(target_tab.iter())
+            .map(String::as_str)
+            .collect();
+        self.update_tabs_in_place(&touched, &state.workset_id, visible_rows);
         Ok(())
     }
 
@@ -398,74 +596,624 @@ impl HomeApp {
 
 #[cfg(test)]
 mod tests {
-    use super::super::Mode;
-    use super::HomeApp;
-    use crate::{
-        config::{AppConfig, TabConfig, Workset},
-        state::AppState,
+    use super::super::harness::{
+        Harness, navigation_app, sample_workset, tab, tab_order, test_app,
     };
-    use ratatui::widgets::TableState;
-    use std::path::PathBuf;
-
-    fn sample_workset(id: &str) -> Workset {
-        Workset {
-            id: id.to_string(),
-            name: format!("Workset {id}"),
-            desc: String::new(),
-            workspace: None,
-            commands: vec![],
-            cwd: None,
-            env: Default::default(),
-            layout: None,
-        }
-    }
-
-    fn test_app(cfg: AppConfig) -> HomeApp {
-        HomeApp {
-            cfg,
-            config_path: PathBuf::new(),
-            state_path: PathBuf::new(),
-            state: AppState::default(),
-            table_state: TableState::default(),
-            scroll: 0,
-            mode: Mode::Normal,
-            message: None,
-            last_click: None,
-            hover_toolbar: None,
-            hover_tab: None,
-            numeric_input: None,
-            tabs: Vec::new(),
-            active_tab_idx: 0,
-            tab_selected_ids: Default::default(),
-            workset_tab_labels: Default::default(),
-        }
-    }
+    use super::super::{
+        ALL_TAB_ID, Mode, ResolvedTab, TabAssignRow, TabAssignState, TabEditField, TabEditState,
+        TabForm, TabFormField, TabPosition,
+    };
+    use super::HomeApp;
+    use crate::config::{AllTabPosition, AppConfig, TabConfig, UnassignedOrder, Workset};
+    use std::collections::BTreeMap;
 
     #[test]
     fn assign_workset_to_tab_preserves_order_when_same_tab() {
         let cfg = AppConfig {
             version: 2,
-            default_tab: None,
-            show_all_tab: None,
-            all_tab_position: None,
             tabs: vec![TabConfig {
-                id: "tabA".into(),
                 label: "A".into(),
-                worksets: vec!["w1".into(), "w2".into(), "w3".into()],
-                include_unassigned: false,
+                ..tab("tabA", &["w1", "w2", "w3"], false)
             }],
             worksets: vec![
                 sample_workset("w1"),
                 sample_workset("w2"),
                 sample_workset("w3"),
             ],
+            ..Default::default()
         };
 
         let mut app = test_app(cfg);
-        let ok = app.assign_workset_to_tab("w2", Some("tabA"));
+        let ok = app.assign_workset_to_tab("w2", Some("tabA"), None);
         assert!(ok);
 
         let tab = app.cfg.tabs.iter().find(|t| t.id == "tabA").unwrap();
         assert_eq!(tab.worksets, vec!["w1", "w2", "w3"]);
     }
+
+    #[test]
+    fn apply_tabs_keeps_all_warnings_and_opens_overlay_once() {
+        let cfg = AppConfig {
+            version: 2,
+            tabs: vec![TabConfig {
+                label: "A".into(),
+                ..tab("tabA", &["w1", "ghost1", "ghost2"], false)
+            }],
+            worksets: vec![sample_workset("w1")],
+            ..Default::default()
+        };
+
+        let mut app = test_app(cfg);
+        app.apply_tabs(None, None);
+        assert_eq!(app.tab_warnings.len(), 2);
+        assert!(app.tab_warnings[0].contains("ghost1"));
+        assert!(app.tab_warnings[1].contains("ghost2"));
+        assert!(matches!(app.mode, Mode::Warnings { scroll: 0 }));
+
+        app.mode = Mode::Normal;
+        app.apply_tabs(None, None);
+        assert!(matches!(app.mode, Mode::Normal));
+    }
+
+    #[test]
+    fn build_tabs_sorts_pinned_first_and_keeps_manual_order() {
+        let cfg = AppConfig {
+            version: 2,
+            tabs: vec![
+                tab("a", &["w1"], false),
+                tab("b", &["w2"], true),
+                tab("c", &["w3"], false),
+                tab("d", &[], true),
+            ],
+            worksets: vec![
+                sample_workset("w1"),
+                sample_workset("w2"),
+                sample_workset("w3"),
+            ],
+            ..Default::default()
+        };
+
+        let mut app = test_app(cfg);
+        app.apply_tabs(None, None);
+        assert_eq!(tab_order(&app), vec!["all", "b", "d", "a", "c"]);
+
+        app.cfg.all_tab_position = Some(AllTabPosition::Last);
+        app.apply_tabs(None, None);
+        assert_eq!(tab_order(&app), vec!["b", "d", "a", "c", "all"]);
+    }
+
+    #[test]
+    fn pinned_order_still_restores_last_tab_by_id() {
+        let cfg = AppConfig {
+            version: 2,
+            all_tab_position: Some(AllTabPosition::Last),
+            tabs: vec![tab("a", &["w1"], false), tab("b", &["w2"], true)],
+            worksets: vec![sample_workset("w1"), sample_workset("w2")],
+            ..Default::default()
+        };
+
+        let mut app = test_app(cfg);
+        app.state.last_tab_id = Some("a".into());
+        app.apply_tabs(None, None);
+        assert_eq!(app.current_tab_id(), Some("a"));
+        assert_eq!(app.state.last_tab_id.as_deref(), Some("a"));
+        assert_eq!(app.current_id().as_deref(), Some("w1"));
+    }
+
+    #[test]
+    fn include_unassigned_orders_extras_by_name_or_config() {
+        let mut app = navigation_app(true);
+        app.cfg.worksets = ["c", "a", "b"].into_iter().map(sample_workset).collect();
+        let mut extras_tab = tab("t", &[], false);
+        extras_tab.include_unassigned = true;
+        app.cfg.tabs = vec![extras_tab];
+
+        let ids = |app: &mut HomeApp| {
+            app.apply_tabs(Some("t".into()), None);
+            app.numbered_rows()
+                .into_iter()
+                .map(|(_, idx)| app.cfg.worksets[idx].id.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids(&mut app), vec!["a", "b", "c"]);
+        app.cfg.tabs[0].unassigned_order = UnassignedOrder::ByConfigOrder;
+        assert_eq!(ids(&mut app), vec!["c", "a", "b"]);
+        let in_tab: Vec<&str> = app
+            .cfg
+            .worksets_in_tab("t", &app.state.last_run)
+            .unwrap()
+            .iter()
+            .map(|ws| ws.id.as_str())
+            .collect();
+        assert_eq!(in_tab, vec!["c", "a", "b"]);
+
+        let at = |secs| Some(std::time::UNIX_EPOCH + std::time::Duration::from_secs(secs));
+        app.cfg.worksets[0].modified = at(100);
+        app.cfg.worksets[2].modified = at(200);
+        app.cfg.tabs[0].unassigned_order = UnassignedOrder::ByModified;
+        assert_eq!(ids(&mut app), vec!["b", "c", "a"]);
+        let in_tab: Vec<&str> = app
+            .cfg
+            .worksets_in_tab("t", &app.state.last_run)
+            .unwrap()
+            .iter()
+            .map(|ws| ws.id.as_str())
+            .collect();
+        assert_eq!(in_tab, vec!["b", "c", "a"]);
+    }
+
+    #[test]
+    fn include_unassigned_orders_extras_by_last_run() {
+        let mut app = navigation_app(true);
+        app.cfg.worksets = ["c", "a", "b", "d"]
+            .into_iter()
+            .map(sample_workset)
+            .collect();
+        let mut extras_tab = tab("t", &[], false);
+        extras_tab.include_unassigned = true;
+        extras_tab.unassigned_order = UnassignedOrder::ByLastRun;
+        app.cfg.tabs = vec![extras_tab];
+
+        let at = |secs| std::time::UNIX_EPOCH + std::time::Duration::from_secs(secs);
+        app.state.last_run = [("a".to_string(), at(100)), ("d".to_string(), at(300))].into();
+        // Stale entries for worksets that no longer exist are ignored.
+        app.state.last_run.insert("gone".into(), at(500));
+        app.apply_tabs(Some("t".into()), None);
+        let ids: Vec<String> = app
+            .numbered_rows()
+            .into_iter()
+            .map(|(_, idx)| app.cfg.worksets[idx].id.clone())
+            .collect();
+        assert_eq!(ids, vec!["d", "a", "b", "c"]);
+        let in_tab: Vec<&str> = app
+            .cfg
+            .worksets_in_tab("t", &app.state.last_run)
+            .unwrap()
+            .iter()
+            .map(|ws| ws.id.as_str())
+            .collect();
+        assert_eq!(in_tab, vec!["d", "a", "b", "c"]);
+    }
+
+    #[test]
+    fn enter_on_the_focused_add_button_opens_the_new_tab_dialog() {
+        let mut app = navigation_app(true);
+        app.switch_tab(2).unwrap();
+        app.move_tab(1).unwrap();
+        assert!(app.add_tab_focused);
+        let enter = crossterm::event::KeyEvent::from(crossterm::event::KeyCode::Enter);
+        assert!(
+            app.handle_key(enter, &super::super::UiMeta::default())
+                .unwrap()
+                .is_none()
+        );
+        assert!(matches!(app.mode, Mode::TabNew(_)));
+        assert!(!app.add_tab_focused);
+        assert_eq!(app.current_tab_id(), Some("b"));
+    }
+
+    #[test]
+    fn unassigned_view_lists_unlisted_worksets_and_tracks_assignment() {
+        let cfg = AppConfig {
+            version: 2,
+            tabs: vec![tab("a", &["w2"], false), tab("rest", &[], false)],
+            worksets: vec![
+                sample_workset("w1"),
+                sample_workset("w2"),
+                sample_workset("w3"),
+            ],
+            ..Default::default()
+        };
+        let mut app = test_app(cfg);
+        app.cfg.tabs[1].include_unassigned = true;
+        app.apply_tabs(Some("a".into()), None);
+
+        app.toggle_unassigned_view();
+        let ids = |app: &HomeApp| -> Vec<String> {
+            (0..app.current_tab_len())
+                .filter_map(|i| app.workset_at_tab_index(i).map(|ws| ws.id.clone()))
+                .collect()
+        };
+        assert_eq!(ids(&app), vec!["w1", "w3"]);
+        assert_eq!(app.current_id().as_deref(), Some("w1"));
+
+        app.assign_workset_to_tab("w1", Some("a"), None);
+        app.apply_tabs(app.current_tab_id().map(str::to_string), Some("w1".into()));
+        assert_eq!(ids(&app), vec!["w3"]);
+
+        app.move_tab(1).unwrap();
+        assert!(app.unassigned_view.is_none());
+        assert_eq!(app.current_tab_id(), Some("a"));
+    }
+
+    #[test]
+    fn tab_edit_toggles_include_unassigned_and_persists() {
+        let cfg = AppConfig {
+            tabs: vec![tab("a", &["w1"], false), tab("b", &[], false)],
+            worksets: vec![sample_workset("w1"), sample_workset("w2")],
+            ..navigation_app(true).cfg
+        };
+        let mut h = Harness::new(cfg);
+        let app = &mut h.app;
+        app.cfg.tabs[1].include_unassigned = true;
+        app.apply_tabs(Some("a".into()), None);
+
+        let form = TabEditState {
+            tab_id: "a".into(),
+            label: "Renamed".into(),
+            include_unassigned: true,
+            focus: TabEditField::IncludeUnassigned,
+        };
+        assert!(app.update_tab(&form).unwrap());
+        assert!(
+            app.message
+                .as_deref()
+                .unwrap()
+                .contains("'B' already includes")
+        );
+
+        let saved = AppConfig::load(&app.config_path).unwrap().unwrap();
+        assert_eq!(saved.tabs[0].label, "Renamed");
+        assert!(saved.tabs[0].include_unassigned);
+        assert_eq!(app.current_tab_id(), Some("a"));
+    }
+
+    #[test]
+    fn duplicate_tab_copies_every_member_with_fresh_ids() {
+        let mut cfg = navigation_app(true).cfg;
+        cfg.worksets.push(sample_workset("w3"));
+        cfg.tabs[0].worksets = vec!["w1".into(), "w3".into(), "gone".into()];
+        let mut h = Harness::new(cfg);
+        let app = &mut h.app;
+        app.apply_tabs(Some("a".into()), None);
+
+        app.duplicate_current_tab().unwrap();
+        let tab_ids: Vec<&str> = app.cfg.tabs.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(tab_ids, vec!["a", "a-copy", "b"]);
+        let copy = &app.cfg.tabs[1];
+        assert_eq!(copy.label, "A (copy)");
+        assert_eq!(copy.worksets.len(), 2);
+        assert_eq!(app.cfg.worksets.len(), 5);
+        let mut ids: Vec<&str> = app.cfg.worksets.iter().map(|ws| ws.id.as_str()).collect();
+        ids.sort_unstable();
+        ids.dedup();
+        assert_eq!(ids.len(), 5, "workset ids stay unique");
+        assert!(
+            copy.worksets
+                .iter()
+                .all(|id| !["w1", "w3"].contains(&id.as_str()))
+        );
+        assert_eq!(app.current_tab_id(), Some("a-copy"));
+
+        let saved = AppConfig::load(&app.config_path).unwrap().unwrap();
+        assert_eq!(saved.tabs[1].worksets, app.cfg.tabs[1].worksets);
+
+        app.apply_tabs(Some("a".into()), None);
+        app.duplicate_current_tab().unwrap();
+        assert_eq!(app.cfg.tabs[1].id, "a-copy-2");
+    }
+
+    #[test]
+    fn tab_assign_pages_and_scrolls_past_the_visible_rows() {
+        let mut state = TabAssignState {
+            workset_id: "w".into(),
+            cursor: 0,
+            scroll: 0,
+            memberships: (0..20)
+                .map(|i| TabAssignRow {
+                    tab_id: format!("t{i}"),
+                    label: format!("T{i}"),
+                    member: false,
+                })
+                .collect(),
+        };
+        // 21 rows including "+ New tab…", 5 visible.
+        state.move_cursor(5, 5);
+        assert_eq!((state.cursor, state.scroll), (5, 1));
+        state.move_cursor(-1, 5);
+        assert_eq!((state.cursor, state.scroll), (4, 1));
+        state.move_cursor(21, 5);
+        assert_eq!((state.cursor, state.scroll), (20, 16));
+        assert!(state.on_new_tab_row());
+        state.move_cursor(5, 5);
+        assert_eq!((state.cursor, state.scroll), (20, 16));
+        state.move_cursor(-5, 5);
+        assert_eq!((state.cursor, state.scroll), (15, 15));
+        state.move_cursor(-21, 5);
+        assert_eq!((state.cursor, state.scroll), (0, 0));
+
+        // A cursor opened far down the list is scrolled into view, and a taller modal never
+        // leaves blank rows after the last tab.
+        state.cursor = 12;
+        assert_eq!(state.scroll_for(5), 8);
+        state.scroll = 18;
+        assert_eq!(state.scroll_for(10), 11);
+    }
+
+    #[test]
+    fn new_tab_from_assign_moves_workset_into_it() {
+        let cfg = AppConfig {
+            tabs: vec![tab("a", &["w1", "w2"], false)],
+            worksets: vec![sample_workset("w1"), sample_workset("w2")],
+            ..navigation_app(true).cfg
+        };
+        let mut h = Harness::new(cfg);
+        let app = &mut h.app;
+        app.apply_tabs(Some("a".into()), Some("w2".into()));
+
+        let form = TabForm {
+            id: "fresh".into(),
+            label: "Fresh".into(),
+            include_unassigned: false,
+            focus: TabFormField::Label,
+            assign_workset: Some("w2".into()),
+        };
+        assert!(app.create_tab(&form).unwrap());
+
+        assert_eq!(app.cfg.tabs[0].worksets, vec!["w1"]);
+        assert_eq!(app.cfg.tabs[1].worksets, vec!["w2"]);
+        assert_eq!(app.current_tab_id(), Some("fresh"));
+        assert_eq!(app.current_id().as_deref(), Some("w2"));
+    }
+
+    #[test]
+    fn workset_joining_a_tab_can_go_to_the_top() {
+        let cfg = AppConfig {
+            tabs: vec![
+                tab("a", &["w1", "w2", "w3"], false),
+                tab("b", &["w4"], false),
+            ],
+            worksets: ["w1", "w2", "w3", "w4"]
+                .into_iter()
+                .map(sample_workset)
+                .collect(),
+            ..navigation_app(true).cfg
+        };
+        let mut h = Harness::new(cfg);
+        let app = &mut h.app;
+        app.apply_tabs(Some("b".into()), Some("w4".into()));
+
+        app.start_tab_assign().unwrap();
+        let Mode::TabAssign(mut assign) = app.mode.clone() else {
+            panic!("assign modal not open");
+        };
+        for row in assign.memberships.iter_mut() {
+            row.member = row.tab_id == "a";
+        }
+        app.confirm_tab_assignment(assign, 10).unwrap();
+        let Mode::TabPlace(place) = app.mode.clone() else {
+            panic!("placement prompt not shown");
+        };
+        assert_eq!(place.target_tab, "a");
+
+        app.mode = Mode::Normal;
+        app.apply_tab_assignment(&place.assign, Some(TabPosition::Top), 10)
+            .unwrap();
+        assert_eq!(app.cfg.tabs[0].worksets, vec!["w4", "w1", "w2", "w3"]);
+        assert!(app.cfg.tabs[1].worksets.is_empty());
+
+        app.tab_selected_ids.insert("a".into(), "w2".into());
+        app.assign_workset_to_tab("w4", Some("a"), Some(TabPosition::AfterCurrent));
+        assert_eq!(app.cfg.tabs[0].worksets, vec!["w1", "w2", "w4", "w3"]);
+    }
+
+    #[test]
+    fn empty_tab_deletes_at_once_and_undo_restores_it() {
+        let cfg = AppConfig {
+            tabs: vec![
+                tab("a", &["w1"], false),
+                tab("b", &[], false),
+                tab("c", &["w2"], false),
+            ],
+            worksets: vec![sample_workset("w1"), sample_workset("w2")],
+            ..navigation_app(true).cfg
+        };
+        let mut h = Harness::new(cfg);
+        let app = &mut h.app;
+
+        app.apply_tabs(Some("c".into()), None);
+        app.start_tab_delete().unwrap();
+        assert!(matches!(
+            app.mode,
+            Mode::ConfirmTabDelete { members: 1, .. }
+        ));
+        app.mode = Mode::Normal;
+
+        app.apply_tabs(Some("b".into()), None);
+        app.start_tab_delete().unwrap();
+        assert!(matches!(app.mode, Mode::Normal));
+        assert_eq!(app.cfg.tabs.len(), 2);
+        assert!(app.message.as_deref().unwrap().contains("z to undo"));
+
+        app.undo_tab_delete().unwrap();
+        let ids: Vec<&str> = app.cfg.tabs.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, vec!["a", "b", "c"]);
+        assert_eq!(app.current_tab_id(), Some("b"));
+        let saved = AppConfig::load(&app.config_path).unwrap().unwrap();
+        assert_eq!(saved.tabs.len(), 3);
+
+        app.cfg.confirm_empty_tab_delete = true;
+        app.start_tab_delete().unwrap();
+        assert!(matches!(
+            app.mode,
+            Mode::ConfirmTabDelete { members: 0, .. }
+        ));
+    }
+
+    fn assert_tabs_match_full_rebuild(app: &HomeApp) {
+        let (tabs, _, labels) = app.build_tabs();
+        let flat = |tabs: &[ResolvedTab]| -> Vec<(String, Vec<usize>)> {
+            tabs.iter()
+                .map(|tab| (tab.id.clone(), tab.indices.clone()))
+                .collect()
+        };
+        assert_eq!(flat(&app.tabs), flat(&tabs));
+        assert_eq!(app.workset_tab_labels, labels);
+    }
+
+    #[test]
+    fn assign_and_reorder_patch_tabs_in_place_and_keep_the_scroll() {
+        let ids = ["w1", "w2", "w3", "w4", "w5", "w6", "w7", "w8"];
+        let cfg = AppConfig {
+            tabs: vec![
+                tab("a", &ids[..6], false),
+                tab("b", &["w7", "w8"], true),
+                tab("c", &[], false),
+            ],
+            worksets: ids.into_iter().map(sample_workset).collect(),
+            ..navigation_app(true).cfg
+        };
+        let mut h = Harness::new(cfg);
+        let app = &mut h.app;
+        app.apply_tabs(Some("a".into()), None);
+        app.select_index(4, 3);
+        assert_eq!((app.current_id().as_deref(), app.scroll), (Some("w5"), 2));
+
+        app.move_workset(-1, 3).unwrap();
+        assert_eq!(
+            app.cfg.tabs[0].worksets,
+            ["w1", "w2", "w3", "w5", "w4", "w6"]
+        );
+        assert_tabs_match_full_rebuild(app);
+        assert_eq!(app.current_id().as_deref(), Some("w5"));
+        assert_eq!(app.scroll, 2, "a full rebuild would jump back to the top");
+
+        app.start_tab_assign().unwrap();
+        let Mode::TabAssign(mut assign) = app.mode.clone() else {
+            panic!("assign modal not open");
+        };
+        for row in assign.memberships.iter_mut() {
+            row.member = row.tab_id == "c";
+        }
+        app.mode = Mode::Normal;
+        app.apply_tab_assignment(&assign, None, 3).unwrap();
+        assert_tabs_match_full_rebuild(app);
+        assert_eq!(app.current_tab_id(), Some("a"));
+        assert_eq!((app.current_id().as_deref(), app.scroll), (Some("w4"), 2));
+
+        app.apply_tabs(Some(ALL_TAB_ID.into()), Some("w7".into()));
+        app.move_workset(1, 3).unwrap();
+        assert_tabs_match_full_rebuild(app);
+        assert_eq!(app.current_id().as_deref(), Some("w7"));
+
+        // An absorbing tab makes the resolved lists differ from the config, so edits fall
+        // back to the full rebuild.
+        app.cfg.tabs[2].include_unassigned = true;
+        app.apply_tabs(Some("b".into()), Some("w8".into()));
+        app.move_workset(-1, 3).unwrap();
+        assert_tabs_match_full_rebuild(app);
+    }
+
+    #[test]
+    fn build_tabs_resolves_duplicates_missing_ids_and_unassigned_extras() {
+        let mut dup_id = sample_workset("w2");
+        dup_id.name = "Second w2".into();
+        let mut modified = sample_workset("w6");
+        modified.modified = Some(std::time::UNIX_EPOCH + std::time::Duration::from_secs(60));
+        let mut absorb = tab("rest", &["w5"], false);
+        absorb.include_unassigned = true;
+        let mut absorb_again = tab("more", &[], true);
+        absorb_again.include_unassigned = true;
+        let worksets = vec![
+            sample_workset("w4"),
+            sample_workset("w1"),
+            sample_workset("w2"),
+            sample_workset("w3"),
+            dup_id,
+            sample_workset("w5"),
+            modified,
+            sample_workset("w0"),
+        ];
+        for (order, extras) in [
+            (UnassignedOrder::ByName, [5, 7, 3, 0, 6]),
+            (UnassignedOrder::ByConfigOrder, [5, 0, 3, 6, 7]),
+            (UnassignedOrder::ByModified, [5, 6, 7, 3, 0]),
+        ] {
+            let mut absorb = absorb.clone();
+            absorb.unassigned_order = order;
+            let cfg = AppConfig {
+                tabs: vec![
+                    tab("a", &["w1", "w1", "ghost"], false),
+                    tab("b", &["w2", "w1"], true),
+                    absorb,
+                    absorb_again.clone(),
+                    tab("b", &["w3"], false),
+                ],
+                worksets: worksets.clone(),
+                ..navigation_app(true).cfg
+            };
+            let (tabs, warnings, labels) = test_app(cfg).build_tabs();
+            let tabs: Vec<(&str, &[usize])> = tabs
+                .iter()
+                .map(|tab| (tab.id.as_str(), tab.indices.as_slice()))
+                .collect();
+            assert_eq!(
+                tabs,
+                vec![
+                    ("all", &[0, 1, 2, 3, 4, 5, 6, 7][..]),
+                    ("b", &[4]),
+                    ("more", &[]),
+                    ("b", &[]),
+                    ("a", &[1]),
+                    ("rest", &extras),
+                ],
+                "{order:?}"
+            );
+            assert_eq!(
+                warnings,
+                vec![
+                    "workset 'w1' already assigned to another tab; skipped in 'a'",
+                    "tab 'a' references missing workset 'ghost'",
+                    "workset 'w1' already assigned to another tab; skipped in 'b'",
+                    "workset 'w3' already assigned to another tab; skipped in 'b'",
+                ]
+            );
+            let labels: BTreeMap<&str, &str> = labels
+                .iter()
+                .map(|(id, label)| (id.as_str(), label.as_str()))
+                .collect();
+            assert_eq!(
+                labels,
+                BTreeMap::from([
+                    ("w0", "REST"),
+                    ("w1", "A"),
+                    ("w2", "B"),
+                    ("w3", "REST"),
+                    ("w4", "REST"),
+                    ("w5", "REST"),
+                    ("w6", "REST"),
+                ])
+            );
+        }
+    }
+
+    /// Timing for `build_tabs` on a large config; run with
+    /// `cargo test --release build_tabs_timing -- --ignored --nocapture`.
+    #[test]
+    #[ignore]
+    fn build_tabs_timing_on_a_large_config() {
+        let worksets: Vec<Workset> = (0..2000)
+            .map(|n| sample_workset(&format!("w{n}")))
+            .collect();
+        let mut tabs: Vec<TabConfig> = (0..100)
+            .map(|t| {
+                let ids: Vec<String> = (t * 15..t * 15 + 15).map(|n| format!("w{n}")).collect();
+                let ids: Vec<&str> = ids.iter().map(String::as_str).collect();
+                tab(&format!("t{t}"), &ids, t % 7 == 0)
+            })
+            .collect();
+        tabs[50].include_unassigned = true;
+        tabs[99].include_unassigned = true;
+        let app = test_app(AppConfig {
+            tabs,
+            worksets,
+            ..navigation_app(true).cfg
+        });
+        let runs = 200;
+        let start = std::time::Instant::now();
+        for _ in 0..runs {
+            std::hint::black_box(app.build_tabs());
+        }
+        println!("build_tabs: {:?} per call", start.elapsed() / runs);
+    }
 }
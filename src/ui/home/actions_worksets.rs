@@ -1,11 +1,18 @@
-use std::collections::HashMap;
+use std::time::SystemTime;
 
 use anyhow::Result;
 use rand::{Rng, distributions::Alphanumeric};
 
-use crate::{config::Workset, run::workspace_cleanup_status};
+use crate::{
+    config::{CleanupMode, Workset},
+    run::{
+        active_workspace, count_slots, effective_command_count, ensure_hyprland_session,
+        launch_cwd, open_in_file_manager, scratchpad_toggle_ready, workset_for_workspace,
+        workspace_cleanup_status,
+    },
+};
 
-use super::HomeApp;
+use super::{ALL_TAB_ID, HomeApp};
 
 impl HomeApp {
     pub(super) fn begin_run_selected(&mut self) -> Result<Option<super::HomeExit>> {
@@ -15,26 +22,172 @@ impl HomeApp {
         self.begin_run(ws)
     }
 
+    pub(super) fn rerun_last(&mut self) -> Result<Option<super::HomeExit>> {
+        let Some(id) = self.state.last_run_id.clone() else {
+            self.message = Some("Nothing launched yet".into());
+            return Ok(None);
+        };
+        let Some(ws) = self.cfg.worksets.iter().find(|ws| ws.id == id).cloned() else {
+            self.message = Some(format!("Last launched workset no longer exists: {id}"));
+            return Ok(None);
+        };
+        self.begin_run(ws)
+    }
+
+    /// Remembers the workset behind a `Run` exit so `.` can launch it again next time.
+    pub(super) fn record_run(&mut self, exit: &super::HomeExit) {
+        if let super::HomeExit::Run { id, .. } = exit {
+            self.state.last_run_id = Some(id.clone());
+            self.state.last_run.insert(id.clone(), SystemTime::now());
+            // Best effort: the TUI is closing, so there is nowhere left to show an error.
+            let _ = self.state.save(&self.state_path);
+        }
+    }
+
+    /// Selects the workset whose `workspace` is the one Hyprland has active right now.
+    pub(super) fn jump_to_active_workspace(&mut self, visible_rows: usize) -> Result<()> {
+        let active = match active_workspace() {
+            Ok(active) => active,
+            Err(err) => {
+                self.message = Some(err.to_string());
+                return Ok(());
+            }
+        };
+        match workset_for_workspace(&self.cfg.worksets, &active) {
+            Some(idx) => self.reveal_workset(idx, visible_rows)?,
+            None => {
+                self.message = Some(format!(
+                    "No workset for this workspace ({})",
+                    active.label()
+                ))
+            }
+        }
+        Ok(())
+    }
+
+    /// Selects the workset at `global_idx`, switching to the first tab that lists it when the
+    /// current one doesn't. User tabs win over All.
+    pub(super) fn reveal_workset(&mut self, global_idx: usize, visible_rows: usize) -> Result<()> {
+        let Some(id) = self.cfg.worksets.get(global_idx).map(|ws| ws.id.clone()) else {
+            return Ok(());
+        };
+        if self.index_in_current_tab(&id).is_none() {
+            let tab_idx = self
+                .tabs
+                .iter()
+                .position(|tab| tab.id != ALL_TAB_ID && tab.indices.contains(&global_idx))
+                .or_else(|| self.tab_index_by_id(ALL_TAB_ID));
+            if let Some(tab_idx) = tab_idx {
+                self.switch_tab(tab_idx)?;
+            }
+        }
+        if let Some(row) = self.index_in_current_tab(&id) {
+            self.select_index(row, visible_rows);
+        }
+        Ok(())
+    }
+
     pub(super) fn begin_run(&mut self, ws: Workset) -> Result<Option<super::HomeExit>> {
-        let status = workspace_cleanup_status(Some(&ws))?;
+        if effective_command_count(&ws) == 0 {
+            self.message = Some(format!("Workset '{}' has no commands to launch", ws.id));
+            return Ok(None);
+        }
+        // Keep the list usable outside Hyprland; only launching needs the compositor.
+        if let Err(err) = ensure_hyprland_session() {
+            self.message = Some(err.to_string());
+            return Ok(None);
+        }
+        let launch_here = self.launch_here_id.as_deref() == Some(ws.id.as_str());
+        // Only the interactive `ask` mode, or a workset marked `confirm`, needs the modal.
+        let ask_cleanup = self.cfg.cleanup_mode_for(&ws) == CleanupMode::Ask;
+        let many_slots = self.many_slots(&ws);
+        let ask_anyway = ws.confirm || many_slots.is_some();
+        if (!ask_cleanup && !ask_anyway) || (!launch_here && scratchpad_toggle_ready(&ws)?) {
+            return Ok(Some(super::HomeExit::Run {
+                id: ws.id,
+                preconfirm_clean: false,
+                launch_here,
+            }));
+        }
+        let status = workspace_cleanup_status((!launch_here).then_some(&ws))?;
         let id = ws.id.clone();
-        let name = ws.name.clone();
-        if status.closable_windows == 0 {
+        let name = self.cfg.primary_label.describe(&ws);
+        let close_windows = ask_cleanup && status.closable_windows > 0;
+        if !close_windows && !ask_anyway {
             return Ok(Some(super::HomeExit::Run {
                 id,
                 preconfirm_clean: false,
+                launch_here,
             }));
         }
 
         self.mode = super::Mode::ConfirmRun(super::ConfirmRunState {
             id,
+            launch_here,
             workset_name: name,
-            workspace_name: status.workspace_name,
             window_count: status.closable_windows,
+            window_breakdown: status.breakdown(),
+            is_special: status.is_special,
+            close_windows,
+            workspace_name: status.workspace_name,
+            many_slots,
         });
         Ok(None)
     }
 
+    /// Layout slot count when it exceeds `slot_warning_threshold`.
+    pub(super) fn many_slots(&self, ws: &Workset) -> Option<usize> {
+        let threshold = self.cfg.slot_warning_threshold()?;
+        let slots = ws.layout.as_ref().map(count_slots)?;
+        (slots > threshold).then_some(slots)
+    }
+
+    pub(super) fn open_selected_cwd(&mut self) {
+        let Some(ws) = self.current_workset() else {
+            return;
+        };
+        self.message = Some(match launch_cwd(&ws, None) {
+            None => format!("Workset '{}' has no cwd set", ws.id),
+            Some(dir) => match open_in_file_manager(dir, self.cfg.detach_commands) {
+                Ok(()) => format!("Opened {}", dir.display()),
+                Err(err) => err.to_string(),
+            },
+        });
+    }
+
+    pub(super) fn toggle_launch_here(&mut self) {
+        let Some(id) = self.current_id() else {
+            return;
+        };
+        if self.launch_here_id.as_deref() == Some(id.as_str()) {
+            self.launch_here_id = None;
+            self.message = Some("Next run uses the configured workspace".into());
+        } else {
+            self.message = Some(format!(
+                "Next run of {id} launches on the current workspace"
+            ));
+            self.launch_here_id = Some(id);
+        }
+    }
+
+    /// Adds the selected workset to the `hyprsets startup` batch or takes it out, and saves.
+    pub(super) fn toggle_selected_startup(&mut self) -> Result<()> {
+        let Some(ws) = self
+            .selected_global_index()
+            .and_then(|idx| self.cfg.worksets.get_mut(idx))
+        else {
+            return Ok(());
+        };
+        ws.startup = !ws.startup;
+        ws.touch();
+        self.message = Some(if ws.startup {
+            format!("{} launches at startup", ws.id)
+        } else {
+            format!("{} no longer launches at startup", ws.id)
+        });
+        self.save()
+    }
+
     pub(super) fn delete_at(&mut self, idx: usize) -> Result<()> {
         let Some(global_idx) = self
             .current_tab()
@@ -68,14 +221,9 @@ impl HomeApp {
         let Some(sel) = self.selected_global_index() else {
             return Ok(());
         };
-        let Some(ws) = self.cfg.worksets.get(sel) else {
+        let Some(new_id) = self.push_workset_copy(sel) else {
             return Ok(());
         };
-        let new_id = self.generate_id();
-        let mut new_ws = ws.clone();
-        new_ws.id = new_id.clone();
-        new_ws.name = format!("{} (copy)", ws.name);
-        self.cfg.worksets.push(new_ws);
         self.message = Some(format!("Duplicated: {}", new_id));
         self.save()?;
         let tab_id = self.current_tab_id().map(str::to_string);
@@ -83,7 +231,24 @@ impl HomeApp {
         Ok(())
     }
 
-    pub(super) fn move_workset(&mut self, delta: isize, _visible_rows: usize) -> Result<()> {
+    /// Appends a copy of the workset at `idx` with a fresh id and a "(copy)" name, without
+    /// saving. Returns the new id.
+    pub(super) fn push_workset_copy(&mut self, idx: usize) -> Option<String> {
+        let ws = self.cfg.worksets.get(idx)?;
+        let new_id = self.generate_id();
+        let mut new_ws = ws.clone();
+        new_ws.id = new_id.clone();
+        new_ws.name = format!("{} (copy)", ws.name);
+        new_ws.touch();
+        self.cfg.worksets.push(new_ws);
+        Some(new_id)
+    }
+
+    pub(super) fn move_workset(&mut self, delta: isize, visible_rows: usize) -> Result<()> {
+        if self.unassigned_view.is_some() {
+            self.message = Some("Unassigned view is read-only; press u to go back".into());
+            return Ok(());
+        }
         let Some(selected_idx) = self.table_state.selected() else {
             return Ok(());
         };
@@ -102,22 +267,25 @@ impl HomeApp {
         }
 
         let selected_global = tab.indices[selected_idx];
-        let selected_id = self
+        let Some(selected_id) = self
             .cfg
             .worksets
             .get(selected_global)
-            .map(|ws| ws.id.clone());
+            .map(|ws| ws.id.clone())
+        else {
+            return Ok(());
+        };
 
         if self.is_all_tab_active() || !self.has_user_tabs() {
             let target_global = tab.indices[target as usize];
             self.reorder_in_all_tab(selected_global, target_global)?;
+            self.save()?;
+            self.update_tabs_after_swap(selected_global, target_global, &selected_id, visible_rows);
         } else {
             self.reorder_in_tab(selected_idx, target as usize, &tab)?;
+            self.save()?;
+            self.update_tabs_in_place(&[tab.id.as_str()], &selected_id, visible_rows);
         }
-
-        self.save()?;
-        let tab_id = self.current_tab_id().map(str::to_string);
-        self.apply_tabs(tab_id, selected_id);
         Ok(())
     }
 
@@ -140,8 +308,11 @@ impl HomeApp {
             self.message = Some("Please enter an id".into());
             return Ok(false);
         }
-        if self.cfg.worksets.iter().any(|existing| existing.id == id) {
-            self.message = Some("ID already exists".into());
+        if let Some(existing) = self.cfg.worksets.iter().find(|existing| existing.id == id) {
+            self.message = Some(format!(
+                "ID already exists: {id} is used by '{}'",
+                existing.name
+            ));
             return Ok(false);
         }
         if name.is_empty() {
@@ -149,18 +320,18 @@ impl HomeApp {
             return Ok(false);
         }
 
-        let ws = Workset {
-            id: id.to_string(),
-            name: name.to_string(),
-            desc: desc.to_string(),
-            workspace: None,
-            commands: vec![],
-            cwd: None,
-            env: HashMap::new(),
-            layout: None,
+        let template = form
+            .template
+            .as_deref()
+            .and_then(|tpl| self.cfg.worksets.iter().find(|ws| ws.id == tpl));
+        let mut ws = if let Some(template) = template {
+            Workset::from_template(template, id, name, desc)
+        } else {
+            Workset::blank(id, name, desc)
         };
+        ws.touch();
         self.cfg.worksets.push(ws);
-        let assigned = self.assign_workset_to_tab(id, form.selected_tab.as_deref());
+        let assigned = self.assign_workset_to_tab(id, form.selected_tab.as_deref(), None);
         if assigned {
             if let Some(tab_id) = form.selected_tab.as_deref() {
                 self.message = Some(format!("Added: {} (tab: {})", id, tab_id));
@@ -204,3 +375,122 @@ impl HomeApp {
         self.current_workset().map(|ws| ws.id)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::super::{
+        ALL_TAB_ID, HomeExit, NewDialogState,
+        harness::{Harness, navigation_app, sample_workset, test_app},
+    };
+    use crate::{config::AppConfig, state::AppState};
+
+    #[test]
+    fn toggling_startup_flips_the_flag_and_saves() {
+        let cfg = AppConfig {
+            worksets: vec![sample_workset("w1"), sample_workset("w2")],
+            ..navigation_app(true).cfg
+        };
+        let mut h = Harness::new(cfg);
+        let app = &mut h.app;
+        app.apply_tabs(None, Some("w2".into()));
+
+        app.toggle_selected_startup().unwrap();
+        assert!(app.cfg.worksets[1].startup);
+        let saved = AppConfig::load(&app.config_path).unwrap().unwrap();
+        assert_eq!(saved.startup_worksets().len(), 1);
+        assert_eq!(saved.startup_worksets()[0].id, "w2");
+
+        app.toggle_selected_startup().unwrap();
+        let saved = AppConfig::load(&app.config_path).unwrap().unwrap();
+        assert!(saved.startup_worksets().is_empty());
+    }
+
+    #[test]
+    fn duplicate_id_on_create_names_the_existing_workset() {
+        let mut cfg = navigation_app(true).cfg;
+        cfg.worksets[0].name = "Daily notes".into();
+        let id = cfg.worksets[0].id.clone();
+        let mut app = test_app(cfg);
+        let form = NewDialogState {
+            id: id.clone(),
+            name: "Other".into(),
+            ..app.new_dialog_state()
+        };
+
+        assert!(!app.create_new(&form).unwrap());
+        assert_eq!(
+            app.message.as_deref(),
+            Some(format!("ID already exists: {id} is used by 'Daily notes'").as_str())
+        );
+    }
+
+    #[test]
+    fn many_slots_only_reports_layouts_over_the_threshold() {
+        let mut app = navigation_app(true);
+        let mut ws = sample_workset("big");
+        assert_eq!(app.many_slots(&ws), None);
+        let leaf = |slot_id| {
+            crate::config::LayoutNode::Leaf(crate::config::WindowSlot::new(slot_id, "kitty"))
+        };
+        ws.layout = Some(crate::config::LayoutNode::Split(crate::config::SplitNode {
+            direction: crate::config::SplitDirection::Horizontal,
+            ratio: 1.0,
+            left: Box::new(leaf(1)),
+            right: Box::new(leaf(2)),
+        }));
+        let slots = 2;
+
+        app.cfg.slot_warning_threshold = Some(slots);
+        assert_eq!(app.many_slots(&ws), None);
+        app.cfg.slot_warning_threshold = Some(slots - 1);
+        assert_eq!(app.many_slots(&ws), Some(slots));
+        app.cfg.slot_warning_threshold = Some(0);
+        assert_eq!(app.many_slots(&ws), None);
+    }
+
+    #[test]
+    fn rerun_last_reports_missing_history_and_records_runs() {
+        let mut h = Harness::new(navigation_app(true).cfg);
+        let app = &mut h.app;
+
+        assert!(app.rerun_last().unwrap().is_none());
+        assert_eq!(app.message.as_deref(), Some("Nothing launched yet"));
+
+        app.record_run(&HomeExit::Run {
+            id: "gone".into(),
+            preconfirm_clean: false,
+            launch_here: false,
+        });
+        assert_eq!(
+            AppState::load(&app.state_path).last_run_id.as_deref(),
+            Some("gone")
+        );
+        assert!(app.rerun_last().unwrap().is_none());
+        assert_eq!(
+            app.message.as_deref(),
+            Some("Last launched workset no longer exists: gone")
+        );
+    }
+
+    #[test]
+    fn reveal_workset_switches_to_a_tab_that_lists_it() {
+        let mut app = navigation_app(true);
+        app.cfg.worksets.push(sample_workset("w3"));
+        app.apply_tabs(Some("a".into()), None);
+        assert_eq!(app.current_tab_id(), Some("a"));
+
+        app.reveal_workset(1, 10).unwrap();
+        assert_eq!(app.current_tab_id(), Some("b"));
+        assert_eq!(app.current_id().as_deref(), Some("w2"));
+
+        // Listed in no user tab: only All shows it.
+        app.reveal_workset(2, 10).unwrap();
+        assert_eq!(app.current_tab_id(), Some(ALL_TAB_ID));
+        assert_eq!(app.current_id().as_deref(), Some("w3"));
+
+        // The current tab is kept when it already lists the workset.
+        app.reveal_workset(1, 10).unwrap();
+        assert_eq!(app.current_tab_id(), Some(ALL_TAB_ID));
+        assert_eq!(app.current_id().as_deref(), Some("w2"));
+    }
+}
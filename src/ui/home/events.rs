@@ -3,47 +3,63 @@ use std::time::Instant;
 use anyhow::Result;
 use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
 
+use crate::{config::ConfirmDefault, keymap::HomeAction, ui::editor::EditorEntry};
+
 use super::{
-    DialogField, HomeApp, HomeExit, LastClick, Mode, TabFormField, TabHitKind, TabMenuItem,
-    ToolbarAction, UiMeta, cycle_new_dialog_tab,
+    CONTEXT_MENU_ITEMS, ContextMenuState, DialogField, HomeApp, HomeExit, LastClick, Mode,
+    TAB_POSITIONS, TabEditField, TabFormField, TabHitKind, TabMenuItem, ToolbarAction, UiMeta,
+    cycle_new_dialog_tab, cycle_new_dialog_template,
 };
 
+/// `y` and `n`/`Esc` always answer; a bare Enter picks `default` when one is configured.
+fn confirm_answer(code: KeyCode, default: Option<ConfirmDefault>) -> Option<bool> {
+    match code {
+        KeyCode::Char('y') => Some(true),
+        KeyCode::Char('n') | KeyCode::Esc => Some(false),
+        KeyCode::Enter => default.map(|default| default == ConfirmDefault::Yes),
+        _ => None,
+    }
+}
+
 impl HomeApp {
     pub(super) fn handle_key(&mut self, key: KeyEvent, ui: &UiMeta) -> Result<Option<HomeExit>> {
+        self.hover_row = false;
         match self.mode.clone() {
             Mode::Normal => self.handle_key_normal(key, ui),
             Mode::ConfirmDelete { idx } => {
-                match key.code {
-                    KeyCode::Char('y') => {
+                match confirm_answer(key.code, self.cfg.delete_confirm_default) {
+                    Some(true) => {
                         self.mode = Mode::Normal;
                         self.delete_at(idx)?;
                     }
-                    KeyCode::Char('n') | KeyCode::Esc => {
+                    Some(false) => {
                         self.mode = Mode::Normal;
                         self.message = Some("Deletion cancelled".into());
                     }
-                    _ => {}
+                    None => {}
                 }
                 Ok(None)
             }
-            Mode::ConfirmRun(state) => match key.code {
-                KeyCode::Char('y') => {
+            Mode::ConfirmRun(state) => match confirm_answer(key.code, self.cfg.run_confirm_default)
+            {
+                Some(true) => {
                     self.mode = Mode::Normal;
                     Ok(Some(HomeExit::Run {
                         id: state.id,
-                        preconfirm_clean: true,
+                        preconfirm_clean: state.close_windows,
+                        launch_here: state.launch_here,
                     }))
                 }
-                KeyCode::Char('n') | KeyCode::Esc => {
+                Some(false) => {
                     self.mode = Mode::Normal;
                     self.message = Some("Launch cancelled".into());
                     Ok(None)
                 }
-                _ => Ok(None),
+                None => Ok(None),
             },
             Mode::NewDialog(_) => self.handle_key_new_dialog(key),
             Mode::TabNew(_) => self.handle_key_tab_new(key),
-            Mode::TabRename(_) => self.handle_key_tab_rename(key),
+            Mode::TabEdit(_) => self.handle_key_tab_edit(key),
             Mode::ConfirmTabDelete { tab_id, .. } => {
                 match key.code {
                     KeyCode::Char('y') => {
@@ -58,70 +74,160 @@ impl HomeApp {
                 }
                 Ok(None)
             }
-            Mode::TabAssign(_) => self.handle_key_tab_assign(key),
+            Mode::TabAssign(_) => self.handle_key_tab_assign(key, ui),
+            Mode::TabPlace(_) => self.handle_key_tab_place(key, ui),
             Mode::TabMenu(_) => self.handle_key_tab_menu(key),
+            Mode::ContextMenu(mut state) => match key.code {
+                KeyCode::Esc | KeyCode::Char('q') => {
+                    self.mode = Mode::Normal;
+                    Ok(None)
+                }
+                KeyCode::Up | KeyCode::Char('k') => {
+                    state.cursor = state.cursor.saturating_sub(1);
+                    self.mode = Mode::ContextMenu(state);
+                    Ok(None)
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    state.cursor = (state.cursor + 1).min(CONTEXT_MENU_ITEMS.len() - 1);
+                    self.mode = Mode::ContextMenu(state);
+                    Ok(None)
+                }
+                KeyCode::Enter => self.trigger_context_item(state.idx, state.cursor, ui),
+                _ => Ok(None),
+            },
+            Mode::Warnings { scroll } => {
+                match key.code {
+                    KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') | KeyCode::Char('!') => {
+                        self.mode = Mode::Normal;
+                    }
+                    KeyCode::Up | KeyCode::Char('k') => {
+                        self.mode = Mode::Warnings {
+                            scroll: scroll.saturating_sub(1),
+                        };
+                    }
+                    KeyCode::Down | KeyCode::Char('j') => {
+                        let max = self.tab_warnings.len().saturating_sub(1);
+                        self.mode = Mode::Warnings {
+                            scroll: (scroll + 1).min(max),
+                        };
+                    }
+                    _ => {}
+                }
+                Ok(None)
+            }
         }
     }
 
     fn handle_key_normal(&mut self, key: KeyEvent, ui: &UiMeta) -> Result<Option<HomeExit>> {
         self.reset_numeric_input_if_stale();
+        let tab_key = matches!(
+            key.code,
+            KeyCode::Left | KeyCode::Right | KeyCode::Tab | KeyCode::BackTab
+        );
+        if self.add_tab_focused && !tab_key {
+            // Focus falls back to the list for anything but tab-bar movement.
+            self.add_tab_focused = false;
+            if key.code == KeyCode::Enter {
+                self.start_tab_new()?;
+                return Ok(None);
+            }
+        }
 
+        if let Some(action) = self.keymap.home(&key) {
+            return self.handle_home_action(action, ui);
+        }
         match key.code {
-            KeyCode::Char('q') | KeyCode::Esc => return Ok(Some(HomeExit::Quit)),
+            KeyCode::Esc => return Ok(Some(HomeExit::Quit)),
             KeyCode::Enter => {
                 return self.begin_run_selected();
             }
-            KeyCode::Char('e') => {
+            KeyCode::Char(ch @ '0'..='9') => {
+                self.handle_numeric_selection(ch, ui.visible_rows);
+            }
+            KeyCode::Left => self.move_tab(-1)?,
+            KeyCode::Right => self.move_tab(1)?,
+            KeyCode::Tab => self.move_tab(1)?,
+            KeyCode::BackTab => self.move_tab(-1)?,
+            KeyCode::Up => self.move_selection(-1, ui.visible_rows),
+            KeyCode::Down => self.move_selection(1, ui.visible_rows),
+            KeyCode::PageUp => self.move_selection(-(ui.visible_rows as isize), ui.visible_rows),
+            KeyCode::PageDown => self.move_selection(ui.visible_rows as isize, ui.visible_rows),
+            KeyCode::Home => self.select_index(0, ui.visible_rows),
+            KeyCode::End if self.current_tab_len() > 0 => {
+                self.select_index(self.current_tab_len() - 1, ui.visible_rows);
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn handle_home_action(&mut self, action: HomeAction, ui: &UiMeta) -> Result<Option<HomeExit>> {
+        match action {
+            HomeAction::Quit => return Ok(Some(HomeExit::Quit)),
+            HomeAction::Edit => {
                 if let Some(id) = self.current_id() {
-                    return Ok(Some(HomeExit::Edit(id)));
+                    return Ok(Some(HomeExit::Edit {
+                        id,
+                        entry: EditorEntry::Normal,
+                    }));
                 }
             }
-            KeyCode::Char(ch @ '0'..='9') => {
-                self.handle_numeric_selection(ch, ui.visible_rows);
+            HomeAction::EditCommand => {
+                if let Some(id) = self.current_id() {
+                    return Ok(Some(HomeExit::Edit {
+                        id,
+                        entry: EditorEntry::FirstSlotCommand,
+                    }));
+                }
             }
-            KeyCode::Char('n') => {
+            HomeAction::New => {
                 self.start_new_dialog()?;
-                return Ok(None);
             }
-            KeyCode::Char('c') => {
+            HomeAction::NewTab => {
+                self.start_tab_new()?;
+            }
+            HomeAction::Undo => {
+                self.undo_tab_delete()?;
+            }
+            HomeAction::Clone => {
                 self.clone_selected()?;
             }
-            KeyCode::Char('d') => {
+            HomeAction::Delete => {
                 if let Some(idx) = self.table_state.selected() {
                     self.mode = Mode::ConfirmDelete { idx };
                 }
             }
-            KeyCode::Char('a') => {
+            HomeAction::AssignTab => {
                 self.start_tab_assign()?;
             }
-            KeyCode::Char('t') => {
+            HomeAction::TabMenu => {
                 self.start_tab_menu()?;
             }
-            KeyCode::Left => self.move_tab(-1)?,
-            KeyCode::Right => self.move_tab(1)?,
-            KeyCode::Tab => self.move_tab(1)?,
-            KeyCode::BackTab => self.move_tab(-1)?,
-            KeyCode::Up => self.move_selection(-1, ui.visible_rows),
-            KeyCode::Down => self.move_selection(1, ui.visible_rows),
-            KeyCode::Char('k') => self.move_selection(-1, ui.visible_rows),
-            KeyCode::Char('j') => self.move_selection(1, ui.visible_rows),
-            KeyCode::Char('K') => self.move_workset(-1, ui.visible_rows)?,
-            KeyCode::Char('J') => self.move_workset(1, ui.visible_rows)?,
-            KeyCode::PageUp => self.move_selection(-(ui.visible_rows as isize), ui.visible_rows),
-            KeyCode::PageDown => self.move_selection(ui.visible_rows as isize, ui.visible_rows),
-            KeyCode::Home => self.select_index(0, ui.visible_rows),
-            KeyCode::End => {
-                if self.current_tab_len() > 0 {
-                    self.select_index(self.current_tab_len() - 1, ui.visible_rows);
+            HomeAction::LaunchHere => self.toggle_launch_here(),
+            HomeAction::Dense => self.toggle_dense(),
+            HomeAction::Unassigned => self.toggle_unassigned_view(),
+            HomeAction::Warnings => {
+                if self.tab_warnings.is_empty() {
+                    self.message = Some("No config warnings".into());
+                } else {
+                    self.mode = Mode::Warnings { scroll: 0 };
                 }
             }
-            _ => {}
+            HomeAction::RerunLast => return self.rerun_last(),
+            HomeAction::OpenCwd => self.open_selected_cwd(),
+            HomeAction::ToggleStartup => self.toggle_selected_startup()?,
+            HomeAction::JumpToWorkspace => self.jump_to_active_workspace(ui.visible_rows)?,
+            HomeAction::Up => self.move_selection(-1, ui.visible_rows),
+            HomeAction::Down => self.move_selection(1, ui.visible_rows),
+            HomeAction::MoveUp => self.move_workset(-1, ui.visible_rows)?,
+            HomeAction::MoveDown => self.move_workset(1, ui.visible_rows)?,
         }
         Ok(None)
     }
 
     fn handle_key_new_dialog(&mut self, key: KeyEvent) -> Result<Option<HomeExit>> {
         let tabs_snapshot = self.cfg.tabs.clone();
+        let templates = self.template_ids();
         let Mode::NewDialog(ref mut form) = self.mode else {
             return Ok(None);
         };
@@ -137,15 +243,17 @@ impl HomeApp {
                     DialogField::Name => DialogField::Desc,
                     DialogField::Desc => DialogField::Id,
                     DialogField::Id => DialogField::Tab,
-                    DialogField::Tab => DialogField::Name,
+                    DialogField::Tab => DialogField::Template,
+                    DialogField::Template => DialogField::Name,
                 };
             }
             KeyCode::BackTab => {
                 form.focus = match form.focus {
-                    DialogField::Name => DialogField::Tab,
+                    DialogField::Name => DialogField::Template,
                     DialogField::Desc => DialogField::Name,
                     DialogField::Id => DialogField::Desc,
                     DialogField::Tab => DialogField::Id,
+                    DialogField::Template => DialogField::Tab,
                 };
             }
             KeyCode::Backspace => match form.focus {
@@ -158,7 +266,7 @@ impl HomeApp {
                 DialogField::Desc => {
                     form.desc.pop();
                 }
-                DialogField::Tab => {}
+                DialogField::Tab | DialogField::Template => {}
             },
             KeyCode::Enter => {
                 let form_clone = form.clone();
@@ -177,16 +285,24 @@ impl HomeApp {
                         cycle_new_dialog_tab(form, &tabs_snapshot, delta);
                     }
                 }
-            },
-            KeyCode::Left => {
-                if form.focus == DialogField::Tab {
-                    cycle_new_dialog_tab(form, &tabs_snapshot, -1);
+                DialogField::Template => {
+                    if matches!(ch, ' ' | 'l' | 'L' | 'h' | 'H') {
+                        let delta = if matches!(ch, 'h' | 'H') { -1 } else { 1 };
+                        cycle_new_dialog_template(form, &templates, delta);
+                    }
                 }
+            },
+            KeyCode::Left if form.focus == DialogField::Tab => {
+                cycle_new_dialog_tab(form, &tabs_snapshot, -1);
             }
-            KeyCode::Right => {
-                if form.focus == DialogField::Tab {
-                    cycle_new_dialog_tab(form, &tabs_snapshot, 1);
-                }
+            KeyCode::Right if form.focus == DialogField::Tab => {
+                cycle_new_dialog_tab(form, &tabs_snapshot, 1);
+            }
+            KeyCode::Left if form.focus == DialogField::Template => {
+                cycle_new_dialog_template(form, &templates, -1);
+            }
+            KeyCode::Right if form.focus == DialogField::Template => {
+                cycle_new_dialog_template(form, &templates, 1);
             }
             _ => {}
         }
@@ -199,9 +315,13 @@ impl HomeApp {
         };
         match key.code {
             KeyCode::Esc => {
+                let from_assign = form.assign_workset.is_some();
                 self.mode = Mode::Normal;
                 self.hide_cursor()?;
                 self.message = Some("Tab creation cancelled".into());
+                if from_assign {
+                    self.start_tab_assign()?;
+                }
             }
             KeyCode::Tab => {
                 form.focus = match form.focus {
@@ -247,50 +367,65 @@ impl HomeApp {
         Ok(None)
     }
 
-    fn handle_key_tab_rename(&mut self, key: KeyEvent) -> Result<Option<HomeExit>> {
-        let Mode::TabRename(ref mut form) = self.mode else {
+    fn handle_key_tab_edit(&mut self, key: KeyEvent) -> Result<Option<HomeExit>> {
+        let Mode::TabEdit(ref mut form) = self.mode else {
             return Ok(None);
         };
         match key.code {
             KeyCode::Esc => {
                 self.mode = Mode::Normal;
                 self.hide_cursor()?;
-                self.message = Some("Tab rename cancelled".into());
+                self.message = Some("Tab edit cancelled".into());
             }
-            KeyCode::Backspace => {
+            KeyCode::Tab | KeyCode::BackTab => {
+                form.focus = match form.focus {
+                    TabEditField::Label => TabEditField::IncludeUnassigned,
+                    TabEditField::IncludeUnassigned => TabEditField::Label,
+                };
+            }
+            KeyCode::Backspace if form.focus == TabEditField::Label => {
                 form.label.pop();
             }
             KeyCode::Enter => {
                 let form_clone = form.clone();
-                if self.rename_tab(&form_clone)? {
+                if self.update_tab(&form_clone)? {
                     self.mode = Mode::Normal;
                     self.hide_cursor()?;
                 }
             }
-            KeyCode::Char(ch) => form.label.push(ch),
+            KeyCode::Char(ch) => match form.focus {
+                TabEditField::Label => form.label.push(ch),
+                TabEditField::IncludeUnassigned => {
+                    if ch == ' ' {
+                        form.include_unassigned = !form.include_unassigned;
+                    }
+                }
+            },
             _ => {}
         }
         Ok(None)
     }
 
-    fn handle_key_tab_assign(&mut self, key: KeyEvent) -> Result<Option<HomeExit>> {
+    fn handle_key_tab_assign(&mut self, key: KeyEvent, ui: &UiMeta) -> Result<Option<HomeExit>> {
         let Mode::TabAssign(ref mut state) = self.mode else {
             return Ok(None);
         };
+        let page = ui.tab_assign_rows.max(1) as isize;
+        let all = state.row_count() as isize;
         match key.code {
             KeyCode::Esc => {
                 self.mode = Mode::Normal;
                 self.message = Some("Tab assignment cancelled".into());
             }
-            KeyCode::Up | KeyCode::Char('k') => {
-                if state.cursor > 0 {
-                    state.cursor -= 1;
-                }
-            }
-            KeyCode::Down | KeyCode::Char('j') => {
-                if state.cursor + 1 < state.memberships.len() {
-                    state.cursor += 1;
-                }
+            KeyCode::Up | KeyCode::Char('k') => state.move_cursor(-1, ui.tab_assign_rows),
+            KeyCode::Down | KeyCode::Char('j') => state.move_cursor(1, ui.tab_assign_rows),
+            KeyCode::PageUp => state.move_cursor(-page, ui.tab_assign_rows),
+            KeyCode::PageDown => state.move_cursor(page, ui.tab_assign_rows),
+            KeyCode::Home => state.move_cursor(-all, ui.tab_assign_rows),
+            KeyCode::End => state.move_cursor(all, ui.tab_assign_rows),
+            KeyCode::Char(' ') | KeyCode::Char('x') | KeyCode::Enter if state.on_new_tab_row() => {
+                let workset_id = state.workset_id.clone();
+                self.start_tab_new_for_workset(workset_id)?;
             }
             KeyCode::Char(' ') | KeyCode::Char('x') => {
                 let already_member = state
@@ -313,8 +448,32 @@ impl HomeApp {
             }
             KeyCode::Enter => {
                 let state_clone = state.clone();
-                self.apply_tab_assignment(&state_clone)?;
+                self.confirm_tab_assignment(state_clone, ui.visible_rows)?;
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn handle_key_tab_place(&mut self, key: KeyEvent, ui: &UiMeta) -> Result<Option<HomeExit>> {
+        let Mode::TabPlace(ref mut state) = self.mode else {
+            return Ok(None);
+        };
+        match key.code {
+            KeyCode::Esc => {
+                self.mode = Mode::TabAssign(state.assign.clone());
+            }
+            KeyCode::Up | KeyCode::Char('k') if state.cursor > 0 => {
+                state.cursor -= 1;
+            }
+            KeyCode::Down | KeyCode::Char('j') if state.cursor + 1 < TAB_POSITIONS.len() => {
+                state.cursor += 1;
+            }
+            KeyCode::Enter => {
+                let position = TAB_POSITIONS[state.cursor].0;
+                let assign = state.assign.clone();
                 self.mode = Mode::Normal;
+                self.apply_tab_assignment(&assign, Some(position), ui.visible_rows)?;
             }
             _ => {}
         }
@@ -327,8 +486,9 @@ impl HomeApp {
         };
         let items = [
             TabMenuItem::Add,
-            TabMenuItem::Rename,
+            TabMenuItem::Edit,
             TabMenuItem::Delete,
+            TabMenuItem::Duplicate,
             TabMenuItem::MoveLeft,
             TabMenuItem::MoveRight,
             TabMenuItem::Exit,
@@ -338,29 +498,32 @@ impl HomeApp {
                 self.mode = Mode::Normal;
                 self.message = Some("Tab menu closed".into());
             }
-            KeyCode::Up | KeyCode::Char('k') => {
-                if state.cursor > 0 {
-                    state.cursor -= 1;
-                }
+            KeyCode::Up | KeyCode::Char('k') if state.cursor > 0 => {
+                state.cursor -= 1;
             }
-            KeyCode::Down | KeyCode::Char('j') => {
-                if state.cursor + 1 < items.len() {
-                    state.cursor += 1;
-                }
+            KeyCode::Down | KeyCode::Char('j') if state.cursor + 1 < items.len() => {
+                state.cursor += 1;
             }
+            // The menu always fits, so a page is the whole list.
+            KeyCode::Home | KeyCode::PageUp => state.cursor = 0,
+            KeyCode::End | KeyCode::PageDown => state.cursor = items.len() - 1,
             KeyCode::Enter => match items.get(state.cursor).copied() {
                 Some(TabMenuItem::Add) => {
                     self.mode = Mode::Normal;
                     self.start_tab_new()?;
                 }
-                Some(TabMenuItem::Rename) => {
+                Some(TabMenuItem::Edit) => {
                     self.mode = Mode::Normal;
-                    self.start_tab_rename()?;
+                    self.start_tab_edit()?;
                 }
                 Some(TabMenuItem::Delete) => {
                     self.mode = Mode::Normal;
                     self.start_tab_delete()?;
                 }
+                Some(TabMenuItem::Duplicate) => {
+                    self.mode = Mode::Normal;
+                    self.duplicate_current_tab()?;
+                }
                 Some(TabMenuItem::MoveLeft) => {
                     self.move_active_tab(-1)?;
                 }
@@ -381,9 +544,15 @@ impl HomeApp {
         mouse: MouseEvent,
         ui: &UiMeta,
     ) -> Result<Option<HomeExit>> {
+        if let Mode::ContextMenu(state) = &self.mode {
+            return self.handle_context_menu_mouse(mouse, state.clone(), ui);
+        }
         if !matches!(self.mode, Mode::Normal) {
             return Ok(None);
         }
+        if matches!(mouse.kind, MouseEventKind::Down(_)) {
+            self.add_tab_focused = false;
+        }
 
         match mouse.kind {
             MouseEventKind::ScrollUp => {
@@ -396,9 +565,11 @@ impl HomeApp {
                 let tab_hit = self.hit_tab(mouse.column, mouse.row, ui);
                 self.hover_tab = tab_hit;
 
-                if let Some(idx) = self.row_from_y(mouse.row, ui) {
+                let row = self.row_from_y(mouse.row, ui);
+                if let Some(idx) = row {
                     self.select_index(idx, ui.visible_rows);
                 }
+                self.hover_row = row.is_some();
                 let hit = self.hit_toolbar(mouse.column, mouse.row, ui);
                 if hit != self.hover_toolbar {
                     self.hover_toolbar = hit;
@@ -431,11 +602,74 @@ impl HomeApp {
                     self.hover_toolbar = None;
                 }
             }
+            MouseEventKind::Down(MouseButton::Right) => {
+                if let Some(idx) = self.row_from_y(mouse.row, ui) {
+                    self.select_index(idx, ui.visible_rows);
+                    self.mode = Mode::ContextMenu(ContextMenuState {
+                        idx,
+                        cursor: 0,
+                        anchor: (mouse.column, mouse.row),
+                    });
+                }
+            }
             _ => {}
         }
         Ok(None)
     }
 
+    fn handle_context_menu_mouse(
+        &mut self,
+        mouse: MouseEvent,
+        mut state: ContextMenuState,
+        ui: &UiMeta,
+    ) -> Result<Option<HomeExit>> {
+        // Rows inside the border map to menu items.
+        let item_at = |column: u16, row: u16| {
+            let rect = ui.context_menu?;
+            let inside = column > rect.x
+                && column + 1 < rect.right()
+                && row > rect.y
+                && row + 1 < rect.bottom();
+            inside
+                .then(|| (row - rect.y - 1) as usize)
+                .filter(|item| *item < CONTEXT_MENU_ITEMS.len())
+        };
+        match mouse.kind {
+            MouseEventKind::Moved => {
+                if let Some(item) = item_at(mouse.column, mouse.row) {
+                    state.cursor = item;
+                    self.mode = Mode::ContextMenu(state);
+                }
+            }
+            MouseEventKind::Down(MouseButton::Left) => {
+                return match item_at(mouse.column, mouse.row) {
+                    Some(item) => self.trigger_context_item(state.idx, item, ui),
+                    None => {
+                        self.mode = Mode::Normal;
+                        Ok(None)
+                    }
+                };
+            }
+            MouseEventKind::Down(_) => self.mode = Mode::Normal,
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn trigger_context_item(
+        &mut self,
+        idx: usize,
+        item: usize,
+        ui: &UiMeta,
+    ) -> Result<Option<HomeExit>> {
+        self.mode = Mode::Normal;
+        let Some((action, _)) = CONTEXT_MENU_ITEMS.get(item).copied() else {
+            return Ok(None);
+        };
+        self.select_index(idx, ui.visible_rows);
+        self.trigger_toolbar(action, ui.visible_rows)
+    }
+
     fn trigger_toolbar(
         &mut self,
         action: ToolbarAction,
@@ -447,7 +681,10 @@ impl HomeApp {
             }
             ToolbarAction::Edit => {
                 if let Some(id) = self.current_id() {
-                    return Ok(Some(HomeExit::Edit(id)));
+                    return Ok(Some(HomeExit::Edit {
+                        id,
+                        entry: EditorEntry::Normal,
+                    }));
                 }
             }
             ToolbarAction::New => {
@@ -475,3 +712,114 @@ impl HomeApp {
         Ok(None)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::super::harness::{Harness, navigation_app, sample_workset, tab, tab_order};
+    use crate::config::AppConfig;
+
+    #[test]
+    fn hovering_a_row_shows_its_description_below_messages_and_toolbar_help() {
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseEventKind};
+
+        let mut described = sample_workset("w2");
+        described.desc = "A description far too long for its column".into();
+        let cfg = AppConfig {
+            tabs: vec![tab("a", &["w1", "w2"], false)],
+            worksets: vec![sample_workset("w1"), described],
+            ..navigation_app(true).cfg
+        };
+        let mut h = Harness::new(cfg);
+        let app = &mut h.app;
+        app.apply_tabs(None, None);
+        let ui = super::super::UiMeta {
+            data_start_y: 3,
+            visible_rows: 5,
+            ..Default::default()
+        };
+        let moved = |row| MouseEvent {
+            kind: MouseEventKind::Moved,
+            column: 4,
+            row,
+            modifiers: KeyModifiers::NONE,
+        };
+
+        app.handle_mouse(moved(3), &ui).unwrap();
+        assert_eq!(app.status_message(), None, "empty descriptions are skipped");
+        app.handle_mouse(moved(4), &ui).unwrap();
+        assert_eq!(
+            app.status_message().as_deref(),
+            Some("A description far too long for its column")
+        );
+
+        app.message = Some("Saved".into());
+        assert_eq!(app.status_message().as_deref(), Some("Saved"));
+        app.message = None;
+
+        app.handle_key(KeyEvent::from(KeyCode::Null), &ui).unwrap();
+        assert_eq!(
+            app.status_message(),
+            None,
+            "keys hide the hover description"
+        );
+    }
+
+    #[test]
+    fn numeric_selection_picks_the_row_labeled_with_the_number() {
+        let mut app = navigation_app(true);
+        app.cfg.worksets = ["w1", "w2", "w3", "w4"]
+            .into_iter()
+            .map(sample_workset)
+            .collect();
+        // The tab lists worksets in an order unrelated to the config order.
+        app.cfg.tabs = vec![tab("t", &["w4", "w2", "w3", "w1"], false)];
+        app.apply_tabs(Some("t".into()), None);
+        assert_eq!(app.current_tab_id(), Some("t"));
+
+        app.handle_numeric_selection('3', 10);
+        let selected = app.table_state.selected().unwrap();
+        let (number, global_idx) = app.numbered_rows()[selected];
+        assert_eq!(number, 3);
+        assert_eq!(app.cfg.worksets[global_idx].id, "w3");
+    }
+
+    #[test]
+    fn navigation_wraps_at_boundaries_by_default() {
+        let mut app = navigation_app(true);
+        assert_eq!(tab_order(&app), vec!["all", "a", "b"]);
+        app.move_tab(-1).unwrap();
+        assert!(app.add_tab_focused);
+        assert_eq!(app.current_tab_id(), Some("all"));
+        app.move_tab(-1).unwrap();
+        assert!(!app.add_tab_focused);
+        assert_eq!(app.current_tab_id(), Some("b"));
+        app.move_tab(1).unwrap();
+        assert!(app.add_tab_focused);
+        app.move_tab(1).unwrap();
+        assert_eq!(app.current_tab_id(), Some("all"));
+
+        app.select_index(1, 10);
+        app.move_selection(1, 10);
+        assert_eq!(app.table_state.selected(), Some(0));
+        app.move_selection(-1, 10);
+        assert_eq!(app.table_state.selected(), Some(1));
+    }
+
+    #[test]
+    fn navigation_clamps_at_boundaries_when_wrap_disabled() {
+        let mut app = navigation_app(false);
+        app.move_tab(-1).unwrap();
+        assert_eq!(app.current_tab_id(), Some("all"));
+        app.switch_tab(2).unwrap();
+        app.move_tab(1).unwrap();
+        assert_eq!(app.current_tab_id(), Some("b"));
+
+        app.switch_tab(0).unwrap();
+        app.select_index(1, 10);
+        app.move_selection(1, 10);
+        assert_eq!(app.table_state.selected(), Some(1));
+        app.select_index(0, 10);
+        app.move_selection(-1, 10);
+        assert_eq!(app.table_state.selected(), Some(0));
+    }
+}
@@ -0,0 +1,306 @@
+//! Drives a `HomeApp` end to end the way `event_loop` does: every key is handled against the
+//! `UiMeta` of a frame drawn into a `TestBackend` just before it.
+
+use std::{
+    path::PathBuf,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{Terminal, backend::TestBackend};
+
+use super::{HomeApp, HomeExit, UiMeta};
+use crate::config::{AppConfig, TabConfig, Workset};
+
+pub(super) const WIDTH: u16 = 80;
+pub(super) const HEIGHT: u16 = 20;
+
+pub(super) struct Harness {
+    pub(super) app: HomeApp,
+    terminal: Terminal<TestBackend>,
+    /// Used instead of the drawn frame's layout while set, for hit-testing without a draw.
+    ui_override: Option<UiMeta>,
+    dir: PathBuf,
+}
+
+impl Harness {
+    /// A home screen over `cfg`, with its config and state files in a fresh temp dir.
+    pub(super) fn new(cfg: AppConfig) -> Self {
+        Self::build(|_| cfg)
+    }
+
+    /// A home screen over the starter config a first run writes.
+    pub(super) fn starter() -> Self {
+        Self::build(|path| AppConfig::load_or_init(path).unwrap())
+    }
+
+    fn build(cfg: impl FnOnce(&std::path::Path) -> AppConfig) -> Self {
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+        let dir = std::env::temp_dir().join(format!(
+            "hyprsets_harness_{}_{}",
+            std::process::id(),
+            NEXT.fetch_add(1, Ordering::Relaxed)
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        let config_path = dir.join("config.toml");
+        let app =
+            HomeApp::with_state_path(cfg(&config_path), config_path, dir.join("state.toml"), None);
+        Self {
+            app,
+            terminal: Terminal::new(TestBackend::new(WIDTH, HEIGHT)).unwrap(),
+            ui_override: None,
+            dir,
+        }
+    }
+
+    pub(super) fn set_ui(&mut self, ui: UiMeta) {
+        self.ui_override = Some(ui);
+    }
+
+    /// Draws a frame and returns the layout the next key is handled against.
+    pub(super) fn draw(&mut self) -> UiMeta {
+        let mut ui = UiMeta::default();
+        let app = &mut self.app;
+        self.terminal.draw(|f| ui = app.draw(f)).unwrap();
+        self.ui_override.clone().unwrap_or(ui)
+    }
+
+    pub(super) fn press(&mut self, code: KeyCode) -> Option<HomeExit> {
+        let ui = self.draw();
+        self.app.handle_key(KeyEvent::from(code), &ui).unwrap()
+    }
+
+    pub(super) fn type_text(&mut self, text: &str) {
+        for ch in text.chars() {
+            assert!(self.press(KeyCode::Char(ch)).is_none(), "{ch:?} left home");
+        }
+    }
+
+    /// The last drawn frame, one line per row with trailing blanks trimmed.
+    pub(super) fn screen(&self) -> String {
+        let buffer = self.terminal.backend().buffer();
+        (0..buffer.area.height)
+            .map(|y| {
+                let row: String = (0..buffer.area.width)
+                    .map(|x| buffer.get(x, y).symbol())
+                    .collect();
+                row.trim_end().to_string()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl Drop for Harness {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+pub(super) fn sample_workset(id: &str) -> Workset {
+    Workset {
+        id: id.to_string(),
+        name: format!("Workset {id}"),
+        ..Default::default()
+    }
+}
+
+/// A home screen over `cfg` whose tab state is read from and written to `/dev/null`.
+pub(super) fn test_app(cfg: AppConfig) -> HomeApp {
+    HomeApp::with_state_path(cfg, PathBuf::new(), PathBuf::from("/dev/null"), None)
+}
+
+pub(crate) fn tab(id: &str, worksets: &[&str], pinned: bool) -> TabConfig {
+    TabConfig {
+        id: id.into(),
+        label: id.to_uppercase(),
+        worksets: worksets.iter().map(|w| w.to_string()).collect(),
+        include_unassigned: false,
+        pinned,
+        default_cleanup: None,
+        unassigned_order: Default::default(),
+    }
+}
+
+pub(super) fn tab_order(app: &HomeApp) -> Vec<&str> {
+    app.tabs.iter().map(|t| t.id.as_str()).collect()
+}
+
+/// Tabs `a` (listing `w1`) and `b` (listing `w2`), already applied.
+pub(super) fn navigation_app(wrap_navigation: bool) -> HomeApp {
+    let cfg = AppConfig {
+        version: 2,
+        wrap_navigation,
+        tabs: vec![tab("a", &["w1"], false), tab("b", &["w2"], false)],
+        worksets: vec![sample_workset("w1"), sample_workset("w2")],
+        ..Default::default()
+    };
+    let mut app = test_app(cfg);
+    app.apply_tabs(None, None);
+    app
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::home::Mode;
+
+    #[test]
+    fn n_opens_the_dialog_and_enter_creates_the_workset() {
+        let mut h = Harness::starter();
+        let before = h.app.cfg.worksets.len();
+
+        h.press(KeyCode::Char('n'));
+        assert!(matches!(h.app.mode, Mode::NewDialog(_)));
+        h.type_text("Notes");
+        h.press(KeyCode::Tab);
+        h.press(KeyCode::Tab);
+        for _ in 0..32 {
+            h.press(KeyCode::Backspace);
+        }
+        h.type_text("notes");
+        assert!(h.press(KeyCode::Enter).is_none());
+
+        assert!(matches!(h.app.mode, Mode::Normal), "{:?}", h.app.message);
+        assert_eq!(h.app.cfg.worksets.len(), before + 1);
+        assert_eq!(h.app.current_id().as_deref(), Some("notes"));
+        let saved = AppConfig::load(&h.app.config_path).unwrap().unwrap();
+        assert!(
+            saved
+                .worksets
+                .iter()
+                .any(|ws| ws.id == "notes" && ws.name == "Notes")
+        );
+        h.draw();
+        assert!(h.screen().contains("Notes"));
+    }
+
+    #[test]
+    fn esc_cancels_the_dialog_without_touching_the_config() {
+        let mut h = Harness::starter();
+        let before = std::fs::read_to_string(&h.app.config_path).unwrap();
+
+        h.press(KeyCode::Char('n'));
+        h.type_text("Scratch");
+        h.press(KeyCode::Esc);
+
+        assert!(matches!(h.app.mode, Mode::Normal));
+        assert_eq!(h.app.message.as_deref(), Some("Creation cancelled"));
+        assert_eq!(std::fs::read_to_string(&h.app.config_path).unwrap(), before);
+    }
+
+    #[test]
+    fn q_quits_and_delete_asks_before_removing() {
+        let mut h = Harness::starter();
+        let id = h.app.current_id().unwrap();
+
+        h.press(KeyCode::Char('d'));
+        assert!(matches!(h.app.mode, Mode::ConfirmDelete { .. }));
+        h.press(KeyCode::Char('n'));
+        assert_eq!(h.app.message.as_deref(), Some("Deletion cancelled"));
+        assert!(h.app.cfg.worksets.iter().any(|ws| ws.id == id));
+
+        assert!(matches!(h.press(KeyCode::Char('q')), Some(HomeExit::Quit)));
+    }
+
+    #[test]
+    fn enter_answers_confirmations_only_when_a_default_is_set() {
+        use crate::config::ConfirmDefault;
+        use crate::ui::home::ConfirmRunState;
+
+        let mut h = Harness::starter();
+        let id = h.app.current_id().unwrap();
+        h.press(KeyCode::Char('d'));
+        h.press(KeyCode::Enter);
+        assert!(matches!(h.app.mode, Mode::ConfirmDelete { .. }));
+        h.draw();
+        assert!(h.screen().contains("[y Yes]   [n No]"));
+
+        h.app.cfg.delete_confirm_default = Some(ConfirmDefault::No);
+        h.draw();
+        assert!(h.screen().contains("[y Yes]   [N No]"));
+        h.press(KeyCode::Enter);
+        assert_eq!(h.app.message.as_deref(), Some("Deletion cancelled"));
+        assert!(h.app.cfg.worksets.iter().any(|ws| ws.id == id));
+
+        h.app.cfg.run_confirm_default = Some(ConfirmDefault::Yes);
+        h.app.mode = Mode::ConfirmRun(ConfirmRunState {
+            id: id.clone(),
+            launch_here: false,
+            workset_name: id.clone(),
+            workspace_name: "1".into(),
+            window_count: 0,
+            window_breakdown: String::new(),
+            is_special: false,
+            close_windows: false,
+            many_slots: None,
+        });
+        h.draw();
+        assert!(h.screen().contains("[Y Yes]   [n No]"));
+        assert!(matches!(
+            h.press(KeyCode::Enter),
+            Some(HomeExit::Run { id: launched, .. }) if launched == id
+        ));
+    }
+
+    #[test]
+    fn injected_ui_meta_drives_mouse_hit_testing() {
+        use crossterm::event::{KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+
+        let mut h = Harness::starter();
+        h.set_ui(UiMeta {
+            data_start_y: 10,
+            visible_rows: 1,
+            ..Default::default()
+        });
+        let ui = h.draw();
+        let click = MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Right),
+            column: 2,
+            row: 10,
+            modifiers: KeyModifiers::NONE,
+        };
+        h.app.handle_mouse(click, &ui).unwrap();
+        assert!(matches!(h.app.mode, Mode::ContextMenu(_)));
+    }
+
+    #[test]
+    fn right_switches_tabs_and_the_list_follows() {
+        let cfg: AppConfig = toml::from_str(
+            r#"
+            default_tab = "work"
+
+            [[tab]]
+            id = "work"
+            label = "Work"
+            worksets = ["mail"]
+
+            [[tab]]
+            id = "play"
+            label = "Play"
+            worksets = ["games"]
+
+            [[workset]]
+            id = "mail"
+            name = "Mail client"
+            desc = ""
+
+            [[workset]]
+            id = "games"
+            name = "Game launcher"
+            desc = ""
+            "#,
+        )
+        .unwrap();
+        let mut h = Harness::new(cfg);
+
+        h.draw();
+        assert!(h.screen().contains("Mail client"));
+        assert!(!h.screen().contains("Game launcher"));
+        h.press(KeyCode::Right);
+        h.draw();
+        assert_eq!(h.app.current_id().as_deref(), Some("games"));
+        assert!(h.screen().contains("Game launcher"));
+        assert!(!h.screen().contains("Mail client"));
+    }
+}
@@ -3,18 +3,43 @@ use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span, Text},
-    widgets::{Block, Borders, Cell, Clear, Padding, Paragraph, Row, Table, Tabs},
+    widgets::{
+        Block, Borders, Cell, Clear, HighlightSpacing, Padding, Paragraph, Row, Table, Tabs,
+    },
 };
+use std::time::SystemTime;
 use unicode_width::UnicodeWidthStr;
 
+use crate::{
+    config::{ConfirmDefault, ListColumn, PrimaryLabel},
+    run::{
+        SPECIAL_WORKSPACE_GLYPH, SPECIAL_WORKSPACE_WARNING, effective_command_count,
+        workspace_glyph,
+    },
+    ui::{clock::relative_age, selection::Selection, terminal::draw_too_small},
+};
+
 use super::{
-    ButtonHit, ConfirmRunState, DialogField, HomeApp, Mode, NewDialogState, TabAssignState,
-    TabForm, TabFormField, TabHit, TabHitKind, TabMenuItem, TabMenuState, TabRenameState,
-    ToolbarAction, UiMeta,
+    ButtonHit, CONTEXT_MENU_ITEMS, ConfirmRunState, ContextMenuState, DialogField, HomeApp, Mode,
+    NewDialogState, TAB_POSITIONS, TabAssignState, TabEditField, TabEditState, TabForm,
+    TabFormField, TabHit, TabHitKind, TabMenuItem, TabMenuState, TabPlaceState, ToolbarAction,
+    UiMeta,
 };
 
+/// Tab bar, the list's `Min(8)`, status and toolbar rows; narrower than this the toolbar and
+/// columns stop making sense.
+const MIN_WIDTH: u16 = 40;
+const MIN_HEIGHT: u16 = 3 + 8 + 1 + 1;
+
 impl HomeApp {
     pub(super) fn draw(&mut self, f: &mut Frame) -> UiMeta {
+        if draw_too_small(f, MIN_WIDTH, MIN_HEIGHT) {
+            return UiMeta {
+                visible_rows: 1,
+                visible_offset: self.scroll,
+                ..UiMeta::default()
+            };
+        }
         let area = f.size();
         let chunks = Layout::default()
             .direction(Direction::Vertical)
@@ -31,11 +56,13 @@ impl HomeApp {
         let status_area = chunks[2];
         let toolbar_area = chunks[3];
 
-        let visible_rows = list_area.height.saturating_sub(2) as usize; // header + bottom border
+        // Dense mode drops the header row, so data starts at the top of the list area.
+        let header_rows = if self.is_dense() { 0 } else { 1 };
+        let visible_rows = list_area.height.saturating_sub(header_rows + 1) as usize; // + bottom border
         let mut ui_meta = UiMeta {
             visible_rows: visible_rows.max(1),
             visible_offset: self.scroll,
-            data_start_y: list_area.y.saturating_add(1), // header row
+            data_start_y: list_area.y.saturating_add(header_rows),
             ..UiMeta::default()
         };
 
@@ -57,18 +84,36 @@ impl HomeApp {
             Mode::TabNew(form) => {
                 self.render_tab_new_modal(f, area, form);
             }
-            Mode::TabRename(form) => {
-                self.render_tab_rename_modal(f, area, form);
+            Mode::TabEdit(form) => {
+                self.render_tab_edit_modal(f, area, form);
             }
-            Mode::ConfirmTabDelete { tab_id, tab_label } => {
-                self.render_tab_delete_modal(f, area, tab_id, tab_label);
+            Mode::ConfirmTabDelete {
+                tab_id,
+                tab_label,
+                members,
+            } => {
+                self.render_tab_delete_modal(f, area, tab_id, tab_label, *members);
             }
             Mode::TabAssign(state) => {
-                self.render_tab_assign_modal(f, area, state);
+                ui_meta.tab_assign_rows = self.render_tab_assign_modal(f, area, state);
+            }
+            Mode::TabPlace(state) => {
+                self.render_tab_place_modal(f, area, state);
             }
             Mode::TabMenu(state) => {
                 self.render_tab_menu_modal(f, area, state);
             }
+            Mode::Warnings { scroll } => {
+                self.render_warnings_modal(f, area, *scroll);
+            }
+            Mode::ContextMenu(state) => {
+                ui_meta.context_menu = Some(render_context_menu(
+                    f,
+                    area,
+                    state,
+                    &Selection::from_config(&self.cfg),
+                ));
+            }
             Mode::Normal => {}
         }
 
@@ -141,14 +186,13 @@ impl HomeApp {
         let tabs = Tabs::new(titles)
             .block(tab_block)
             .divider(Span::raw(divider))
-            .select(self.active_tab_idx)
+            .select(if self.add_tab_focused {
+                self.tabs.len()
+            } else {
+                self.active_tab_idx
+            })
             .style(Style::default())
-            .highlight_style(
-                Style::default()
-                    .bg(Color::Cyan)
-                    .fg(Color::Black)
-                    .add_modifier(Modifier::BOLD),
-            );
+            .highlight_style(Selection::from_config(&self.cfg).style);
 
         f.render_widget(tabs, area);
         if area.width > 0 && area.height > 0 {
@@ -192,72 +236,131 @@ impl HomeApp {
         self.ensure_offset(visible_rows);
 
         let start = self.scroll;
+        let dense = self.is_dense();
+        let columns = if dense {
+            vec![ListColumn::Name, ListColumn::Workspace]
+        } else {
+            self.cfg.list_columns()
+        };
 
         let rows: Vec<Row> = self
-            .current_tab()
+            .numbered_rows()
             .into_iter()
-            .flat_map(|tab| {
-                tab.indices
-                    .iter()
-                    .enumerate()
-                    .skip(start)
-                    .take(visible_rows)
-                    .filter_map(|(local_idx, global_idx)| {
-                        self.cfg.worksets.get(*global_idx).map(|ws| {
-                            let workspace = ws
-                                .workspace
-                                .as_deref()
-                                .map(str::trim)
-                                .filter(|w| !w.is_empty())
-                                .unwrap_or("")
-                                .to_string();
-                            let tab_label = self
-                                .workset_tab_labels
-                                .get(&ws.id)
-                                .cloned()
-                                .unwrap_or_default();
-                            Row::new(vec![
-                                Cell::from(format!("{:>3}", local_idx + 1)),
-                                Cell::from(ws.name.clone()),
-                                Cell::from(ws.desc.clone()),
-                                Cell::from(tab_label),
-                                Cell::from(workspace),
-                            ])
+            .skip(start)
+            .take(visible_rows)
+            .filter_map(|(number, global_idx)| {
+                self.cfg.worksets.get(global_idx).map(|ws| {
+                    let cells: Vec<Cell> = columns
+                        .iter()
+                        .map(|column| match column {
+                            ListColumn::No => Cell::from(format!("{number:>3}")),
+                            ListColumn::Startup => Cell::from(if ws.startup { "•" } else { "" }),
+                            ListColumn::Modified => Cell::from(
+                                ws.modified
+                                    .map(|at| relative_age(at, SystemTime::now()))
+                                    .unwrap_or_default(),
+                            ),
+                            ListColumn::Name => match self.cfg.primary_label {
+                                PrimaryLabel::Name => Cell::from(ws.name.clone()),
+                                PrimaryLabel::Id => Cell::from(Line::from(vec![
+                                    Span::raw(ws.id.clone()),
+                                    Span::styled(
+                                        format!("  {}", ws.name),
+                                        Style::default().fg(Color::DarkGray),
+                                    ),
+                                ])),
+                            },
+                            ListColumn::Description => Cell::from(ws.desc.clone()),
+                            ListColumn::Tab => Cell::from(
+                                self.workset_tab_labels
+                                    .get(&ws.id)
+                                    .cloned()
+                                    .unwrap_or_default(),
+                            ),
+                            ListColumn::Workspace => {
+                                let workspace = ws
+                                    .workspace
+                                    .as_deref()
+                                    .map(str::trim)
+                                    .filter(|w| !w.is_empty())
+                                    .unwrap_or("");
+                                let count = self
+                                    .cfg
+                                    .show_window_counts
+                                    .then(|| self.window_counts.for_workset(ws))
+                                    .flatten();
+                                let icon = if self.cfg.workspace_icons {
+                                    workspace_icon_prefix(workspace)
+                                } else {
+                                    String::new()
+                                };
+                                Cell::from(match count {
+                                    Some(count) => format!("{icon}{workspace} ({count})"),
+                                    None => format!("{icon}{workspace}"),
+                                })
+                            }
                         })
-                    })
+                        .collect();
+                    // Nothing would launch; dim the row so it does not look runnable.
+                    if effective_command_count(ws) == 0 {
+                        Row::new(cells).style(
+                            Style::default()
+                                .fg(Color::DarkGray)
+                                .add_modifier(Modifier::ITALIC),
+                        )
+                    } else {
+                        Row::new(cells)
+                    }
+                })
             })
             .collect();
 
-        let header = Row::new(vec!["No", "Name", "Description", "Tab", "Workspace"]).style(
+        let header = Row::new(columns.iter().map(|column| match column {
+            ListColumn::Name if self.cfg.primary_label == PrimaryLabel::Id => "Id",
+            _ => column_title(*column),
+        }))
+        .style(
             Style::default()
                 .fg(Color::Yellow)
                 .add_modifier(Modifier::BOLD),
         );
 
-        let widths = [
-            Constraint::Length(5),
-            Constraint::Length(20),
-            Constraint::Min(10),
-            Constraint::Length(14),
-            Constraint::Length(18),
-        ];
+        let widths: Vec<Constraint> = columns.iter().map(|column| column_width(*column)).collect();
 
+        let selection = Selection::from_config(&self.cfg);
         let table = Table::new(rows, widths)
-            .header(header)
             .block(Block::default().borders(Borders::LEFT | Borders::RIGHT | Borders::BOTTOM))
-            .highlight_style(
-                Style::default()
-                    .bg(Color::Cyan)
-                    .fg(Color::Black)
-                    .add_modifier(Modifier::BOLD),
-            )
-            .highlight_symbol("> ")
-            .column_spacing(1);
+            .highlight_style(selection.style);
+        let table = if dense {
+            table.column_spacing(0)
+        } else {
+            // Reserve the symbol's width on every row so columns never shift with the selection.
+            table
+                .header(header)
+                .highlight_symbol(selection.symbol.as_str())
+                .highlight_spacing(HighlightSpacing::Always)
+                .column_spacing(1)
+        };
 
         *self.table_state.offset_mut() = self.scroll;
         f.render_stateful_widget(table, area, &mut self.table_state);
     }
 
+    /// Toolbar help wins over messages, which win over the hovered row's description.
+    pub(super) fn status_message(&self) -> Option<String> {
+        if let Some(action) = self.hover_toolbar {
+            return Some(action.help().to_string());
+        }
+        if let Some(message) = &self.message {
+            return Some(message.clone());
+        }
+        self.hover_row
+            .then(|| self.current_workset())
+            .flatten()
+            .map(|ws| ws.desc)
+            .filter(|desc| !desc.trim().is_empty())
+    }
+
     fn render_status(&self, f: &mut Frame, area: Rect) {
         let total = self.current_tab_len();
         let pos = if let Some(selected) = self.table_state.selected() {
@@ -270,11 +373,23 @@ impl HomeApp {
             .current_tab()
             .map(|t| t.label.as_str())
             .unwrap_or("All");
-        let msg_body = self.message.as_deref().unwrap_or("").to_string();
-        let left = if msg_body.is_empty() {
+        let msg_body = self.status_message().unwrap_or_default();
+        let pos = match self.status_clock() {
+            Some(clock) => format!("{pos}  {clock}"),
+            None => pos,
+        };
+        let here = self
+            .current_id()
+            .is_some_and(|id| self.launch_here_id.as_deref() == Some(id.as_str()));
+        let tab_part = if here {
+            format!("Tab: {tab_label} → here")
+        } else {
             format!("Tab: {tab_label}")
+        };
+        let left = if msg_body.is_empty() {
+            tab_part
         } else {
-            format!("Tab: {tab_label} — {msg_body}")
+            format!("{tab_part} — {msg_body}")
         };
         let left_width = UnicodeWidthStr::width(left.as_str());
         let pos_width = UnicodeWidthStr::width(pos.as_str());
@@ -354,7 +469,7 @@ impl HomeApp {
             Line::from(format!("Delete \"{}\" (id: {}) ?", ws.name, ws.id)),
             Line::from("Are you sure you want to delete this workset?"),
             Line::from(" "),
-            Line::from("[y Yes]   [n No]"),
+            Line::from(yes_no_hint(self.cfg.delete_confirm_default)),
         ];
         f.render_widget(Clear, popup_area);
         f.render_widget(block, popup_area);
@@ -428,10 +543,32 @@ impl HomeApp {
             Span::raw(format!("Tab: {}", tab_value))
         };
 
+        let template_value = match form.template.as_deref() {
+            Some(id) => self
+                .cfg
+                .worksets
+                .iter()
+                .find(|ws| ws.id == id)
+                .map(|ws| format!("{} ({id})", ws.name))
+                .unwrap_or_else(|| id.to_string()),
+            None if self.cfg.worksets.iter().any(|ws| ws.template) => "<none>".to_string(),
+            None => "(no templates)".to_string(),
+        };
+        let template_label = if form.focus == DialogField::Template {
+            Span::styled(
+                format!("Template: {}", template_value),
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            )
+        } else {
+            Span::raw(format!("Template: {}", template_value))
+        };
+
         let help = Line::from(vec![
             Span::raw("Enter: Create and return to list  "),
             Span::raw("Tab/Shift+Tab: Switch field  "),
-            Span::raw("Left/Right/Space: Change tab  "),
+            Span::raw("Left/Right/Space: Change tab/template  "),
             Span::raw("Esc: Cancel"),
         ]);
 
@@ -440,6 +577,7 @@ impl HomeApp {
             Line::from(name_label),
             Line::from(desc_label),
             Line::from(tab_label),
+            Line::from(template_label),
             Line::from(""),
             help,
         ];
@@ -469,27 +607,74 @@ impl HomeApp {
                 let text_w = UnicodeWidthStr::width(tab_value.as_str()) as u16;
                 (inner.x + prefix_w + text_w, inner.y + 3)
             }
+            DialogField::Template => {
+                let prefix_w = UnicodeWidthStr::width("Template: ") as u16;
+                let text_w = UnicodeWidthStr::width(template_value.as_str()) as u16;
+                (inner.x + prefix_w + text_w, inner.y + 4)
+            }
         };
         f.set_cursor(cursor_x, cursor_y);
     }
 
     fn render_run_modal(&self, f: &mut Frame, area: Rect, state: &ConfirmRunState) {
+        if !state.close_windows {
+            self.render_confirm_launch_modal(f, area, state);
+            return;
+        }
         let block = Block::default()
             .title("Close existing windows?")
             .borders(Borders::ALL)
             .style(Style::default().bg(Color::Black));
         let popup_area = centered_rect(65, 40, area);
         let inner = block.inner(popup_area);
-        let lines = vec![
+        let mut lines = vec![
             Line::from(format!("Workset: {}", state.workset_name)),
             Line::from(format!(
-                "Workspace '{}' has {} window(s).",
-                state.workspace_name, state.window_count
+                "Workspace '{}' has {} window(s): {}.",
+                state.workspace_name, state.window_count, state.window_breakdown
             )),
-            Line::from("Close all of them before launching this workset?"),
+        ];
+        if state.is_special {
+            lines.push(Line::styled(
+                SPECIAL_WORKSPACE_WARNING,
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ));
+        }
+        lines.extend(many_slots_warning(state));
+        lines.extend([
+            Line::from(if state.launch_here {
+                "Close all of them before launching this workset here?"
+            } else {
+                "Close all of them before launching this workset?"
+            }),
             Line::from(" "),
-            Line::from("[y Yes]   [n No]"),
+            Line::from(yes_no_hint(self.cfg.run_confirm_default)),
+        ]);
+        f.render_widget(Clear, popup_area);
+        f.render_widget(block, popup_area);
+        f.render_widget(Paragraph::new(Text::from(lines)), inner);
+    }
+
+    /// Shown for `confirm = true` worksets when there is nothing to close.
+    fn render_confirm_launch_modal(&self, f: &mut Frame, area: Rect, state: &ConfirmRunState) {
+        let block = Block::default()
+            .title("Confirm launch")
+            .borders(Borders::ALL)
+            .style(Style::default().bg(Color::Black));
+        let popup_area = centered_rect(55, 30, area);
+        let inner = block.inner(popup_area);
+        let mut lines = vec![
+            Line::styled(
+                format!("Really launch '{}'?", state.workset_name),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Line::from(format!("Target workspace: {}", state.workspace_name)),
         ];
+        lines.extend(many_slots_warning(state));
+        lines.extend([
+            Line::from(" "),
+            Line::from(yes_no_hint(self.cfg.run_confirm_default)),
+        ]);
         f.render_widget(Clear, popup_area);
         f.render_widget(block, popup_area);
         f.render_widget(Paragraph::new(Text::from(lines)), inner);
@@ -583,29 +768,58 @@ impl HomeApp {
         f.set_cursor(cursor_x, cursor_y);
     }
 
-    fn render_tab_rename_modal(&self, f: &mut Frame, area: Rect, form: &TabRenameState) {
+    fn render_tab_edit_modal(&self, f: &mut Frame, area: Rect, form: &TabEditState) {
         let block = Block::default()
-            .title("Rename Tab")
+            .title("Edit Tab")
             .borders(Borders::ALL)
             .style(Style::default().bg(Color::Black));
         let popup_area = centered_rect(60, 40, area);
         let inner = block.inner(popup_area);
+        let focused = Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD);
+        let field_style = |field: TabEditField| {
+            if form.focus == field {
+                focused
+            } else {
+                Style::default()
+            }
+        };
+        let include = if form.include_unassigned { "yes" } else { "no" };
         let lines = vec![
             Line::from(format!("ID: {}", form.tab_id)),
-            Line::from(format!("Label: {}", form.label)),
+            Line::styled(
+                format!("Label: {}", form.label),
+                field_style(TabEditField::Label),
+            ),
+            Line::styled(
+                format!("Include unassigned: {include}"),
+                field_style(TabEditField::IncludeUnassigned),
+            ),
             Line::from(""),
-            Line::from("Enter: Save   Esc: Cancel"),
+            Line::from("Enter: Save   Tab: Switch field   Space: Toggle include   Esc: Cancel"),
         ];
         f.render_widget(Clear, popup_area);
         f.render_widget(block, popup_area);
         f.render_widget(Paragraph::new(Text::from(lines)), inner);
 
-        let prefix_w = UnicodeWidthStr::width("Label: ") as u16;
-        let text_w = UnicodeWidthStr::width(form.label.as_str()) as u16;
-        f.set_cursor(inner.x + prefix_w + text_w, inner.y + 1);
+        let (prefix, text, row) = match form.focus {
+            TabEditField::Label => ("Label: ", form.label.as_str(), 1),
+            TabEditField::IncludeUnassigned => ("Include unassigned: ", include, 2),
+        };
+        let prefix_w = UnicodeWidthStr::width(prefix) as u16;
+        let text_w = UnicodeWidthStr::width(text) as u16;
+        f.set_cursor(inner.x + prefix_w + text_w, inner.y + row);
     }
 
-    fn render_tab_delete_modal(&self, f: &mut Frame, area: Rect, tab_id: &str, tab_label: &str) {
+    fn render_tab_delete_modal(
+        &self,
+        f: &mut Frame,
+        area: Rect,
+        tab_id: &str,
+        tab_label: &str,
+        members: usize,
+    ) {
         let block = Block::default()
             .title("Delete tab?")
             .borders(Borders::ALL)
@@ -614,7 +828,11 @@ impl HomeApp {
         let inner = block.inner(popup_area);
         let lines = vec![
             Line::from(format!("Delete tab '{tab_label}' (id: {tab_id})?")),
-            Line::from("Are you sure you want to remove this tab?"),
+            Line::from(match members {
+                0 => "Are you sure you want to remove this tab?".to_string(),
+                1 => "1 workset will become unassigned.".to_string(),
+                n => format!("{n} worksets will become unassigned."),
+            }),
             Line::from(" "),
             Line::from("[y Yes]   [n No]"),
         ];
@@ -623,7 +841,8 @@ impl HomeApp {
         f.render_widget(Paragraph::new(Text::from(lines)), inner);
     }
 
-    fn render_tab_assign_modal(&self, f: &mut Frame, area: Rect, state: &TabAssignState) {
+    /// Returns how many tab rows fit below the header lines.
+    fn render_tab_assign_modal(&self, f: &mut Frame, area: Rect, state: &TabAssignState) -> usize {
         let block = Block::default()
             .title("Assign to tabs")
             .borders(Borders::ALL)
@@ -633,10 +852,14 @@ impl HomeApp {
 
         let mut lines: Vec<Line> = Vec::new();
         lines.push(Line::from(format!("Workset: {}", state.workset_id)));
-        lines.push(Line::from("Space: toggle  Enter: save  Esc: cancel"));
+        lines.push(Line::from(
+            "Space: toggle  Enter: save  Esc: cancel  PgUp/PgDn/Home/End: jump",
+        ));
         lines.push(Line::from(""));
 
-        for (idx, row) in state.memberships.iter().enumerate() {
+        let rows = (inner.height as usize).saturating_sub(lines.len()).max(1);
+        let start = state.scroll_for(rows);
+        for (idx, row) in state.memberships.iter().enumerate().skip(start).take(rows) {
             let marker = if row.member { "[x]" } else { "[ ]" };
             let text = format!("{marker} {}", row.label);
             let style = if idx == state.cursor {
@@ -648,6 +871,56 @@ impl HomeApp {
             };
             lines.push(Line::styled(text, style));
         }
+        let new_tab_style = if state.on_new_tab_row() {
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+        if start + rows > state.memberships.len() {
+            lines.push(Line::styled("+ New tab…", new_tab_style));
+        }
+
+        f.render_widget(Clear, popup_area);
+        f.render_widget(block, popup_area);
+        f.render_widget(Paragraph::new(Text::from(lines)), inner);
+        rows
+    }
+
+    fn render_tab_place_modal(&self, f: &mut Frame, area: Rect, state: &TabPlaceState) {
+        let block = Block::default()
+            .title("Place in tab")
+            .borders(Borders::ALL)
+            .style(Style::default().bg(Color::Black));
+        let popup_area = centered_rect(50, 35, area);
+        let inner = block.inner(popup_area);
+
+        let tab_label = self
+            .cfg
+            .tabs
+            .iter()
+            .find(|t| t.id == state.target_tab)
+            .map_or(state.target_tab.as_str(), |t| t.label.as_str());
+        let mut lines = Vec::new();
+        lines.push(Line::from(format!(
+            "Where should {} go in '{tab_label}'?",
+            state.assign.workset_id
+        )));
+        lines.push(Line::from("Enter: place  Esc: back"));
+        lines.push(Line::from(""));
+        let selection = Selection::from_config(&self.cfg);
+        for (idx, (_, label)) in TAB_POSITIONS.iter().enumerate() {
+            let prefix = selection.prefix(idx == state.cursor);
+            let style = if idx == state.cursor {
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            lines.push(Line::styled(format!("{prefix}{label}"), style));
+        }
 
         f.render_widget(Clear, popup_area);
         f.render_widget(block, popup_area);
@@ -664,8 +937,9 @@ impl HomeApp {
 
         let items = [
             (TabMenuItem::Add, "Add tab"),
-            (TabMenuItem::Rename, "Rename tab"),
+            (TabMenuItem::Edit, "Edit tab"),
             (TabMenuItem::Delete, "Delete tab"),
+            (TabMenuItem::Duplicate, "Duplicate tab"),
             (TabMenuItem::MoveLeft, "Move tab left"),
             (TabMenuItem::MoveRight, "Move tab right"),
             (TabMenuItem::Exit, "Close menu"),
@@ -676,8 +950,9 @@ impl HomeApp {
             "Use ↑/↓ or j/k to select, Enter to confirm, Esc to close",
         ));
         lines.push(Line::from(""));
+        let selection = Selection::from_config(&self.cfg);
         for (idx, (_, label)) in items.iter().enumerate() {
-            let prefix = if idx == state.cursor { "> " } else { "  " };
+            let prefix = selection.prefix(idx == state.cursor);
             let style = if idx == state.cursor {
                 Style::default()
                     .fg(Color::Yellow)
@@ -692,6 +967,58 @@ impl HomeApp {
         f.render_widget(block, popup_area);
         f.render_widget(Paragraph::new(Text::from(lines)), inner);
     }
+
+    fn render_warnings_modal(&self, f: &mut Frame, area: Rect, scroll: usize) {
+        let block = Block::default()
+            .title(format!("Config warnings ({})", self.tab_warnings.len()))
+            .borders(Borders::ALL)
+            .style(Style::default().bg(Color::Black));
+        let popup_area = centered_rect(80, 60, area);
+        let inner = block.inner(popup_area);
+
+        let mut lines = Vec::new();
+        lines.push(Line::from(
+            "Fix these entries in the config file. ↑/↓ or j/k to scroll, Esc to close",
+        ));
+        lines.push(Line::from(""));
+        for warning in self.tab_warnings.iter().skip(scroll) {
+            lines.push(Line::styled(
+                format!("- {warning}"),
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+
+        f.render_widget(Clear, popup_area);
+        f.render_widget(block, popup_area);
+        f.render_widget(Paragraph::new(Text::from(lines)), inner);
+    }
+}
+
+fn many_slots_warning(state: &ConfirmRunState) -> Option<Line<'static>> {
+    state.many_slots.map(|slots| {
+        Line::styled(
+            format!("This layout opens {slots} windows; it may be cramped on a small monitor."),
+            Style::default().fg(Color::Yellow),
+        )
+    })
+}
+
+/// The `[y Yes]   [n No]` prompt line, with the key Enter picks in upper case.
+pub(super) fn yes_no_hint(default: Option<ConfirmDefault>) -> &'static str {
+    match default {
+        None => "[y Yes]   [n No]",
+        Some(ConfirmDefault::Yes) => "[Y Yes]   [n No]",
+        Some(ConfirmDefault::No) => "[y Yes]   [N No]",
+    }
+}
+
+/// The workspace glyph and a space, or blanks of the same width so plain workspaces line up
+/// with marked ones.
+fn workspace_icon_prefix(workspace: &str) -> String {
+    match workspace_glyph(workspace) {
+        Some(glyph) => format!("{glyph} "),
+        None => " ".repeat(UnicodeWidthStr::width(SPECIAL_WORKSPACE_GLYPH) + 1),
+    }
 }
 
 fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
@@ -721,3 +1048,71 @@ fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
 
     horizontal[1]
 }
+
+fn column_title(column: ListColumn) -> &'static str {
+    match column {
+        ListColumn::No => "No",
+        ListColumn::Startup => "",
+        ListColumn::Name => "Name",
+        ListColumn::Description => "Description",
+        ListColumn::Tab => "Tab",
+        ListColumn::Workspace => "Workspace",
+        ListColumn::Modified => "Modified",
+    }
+}
+
+fn column_width(column: ListColumn) -> Constraint {
+    match column {
+        ListColumn::No => Constraint::Length(5),
+        ListColumn::Startup => Constraint::Length(1),
+        ListColumn::Name => Constraint::Length(20),
+        ListColumn::Description => Constraint::Min(10),
+        ListColumn::Tab => Constraint::Length(14),
+        ListColumn::Workspace => Constraint::Length(18),
+        ListColumn::Modified => Constraint::Length(9),
+    }
+}
+
+/// Draws the row context menu next to its anchor, shifted left/up when it would overflow.
+fn render_context_menu(
+    f: &mut Frame,
+    area: Rect,
+    state: &ContextMenuState,
+    selection: &Selection,
+) -> Rect {
+    let label_width = CONTEXT_MENU_ITEMS
+        .iter()
+        .map(|(_, label)| UnicodeWidthStr::width(*label) as u16)
+        .max()
+        .unwrap_or(0);
+    let prefix_width = UnicodeWidthStr::width(selection.symbol.as_str()) as u16;
+    let width = (label_width + prefix_width + 4).min(area.width);
+    let height = (CONTEXT_MENU_ITEMS.len() as u16 + 2).min(area.height);
+    let (x, y) = state.anchor;
+    let rect = Rect {
+        x: x.min(area.right().saturating_sub(width)),
+        y: y.min(area.bottom().saturating_sub(height)),
+        width,
+        height,
+    };
+
+    let lines: Vec<Line> = CONTEXT_MENU_ITEMS
+        .iter()
+        .enumerate()
+        .map(|(idx, (_, label))| {
+            let selected = idx == state.cursor;
+            let line = format!("{}{label}", selection.prefix(selected));
+            if selected {
+                Line::styled(line, selection.style)
+            } else {
+                Line::from(line)
+            }
+        })
+        .collect();
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .style(Style::default().bg(Color::Black));
+    f.render_widget(Clear, rect);
+    f.render_widget(Paragraph::new(Text::from(lines)).block(block), rect);
+    rect
+}
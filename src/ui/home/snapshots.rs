@@ -0,0 +1,145 @@
+//! Whole-frame snapshots of key home screens at the harness's fixed 80x20 size. They pin down
+//! layout math (column widths, the tab bar divider, modal placement), so a deliberate layout
+//! change means updating the expected frames here.
+
+use crossterm::event::KeyCode;
+
+use super::{Mode, harness::Harness};
+
+const CONFIG: &str = r#"
+    version = 2
+    default_tab = "work"
+
+    [[tab]]
+    id = "work"
+    label = "Work"
+    worksets = ["mail", "code"]
+
+    [[tab]]
+    id = "empty"
+    label = "Empty"
+
+    [[workset]]
+    id = "mail"
+    name = "Mail client"
+    desc = "Inbox and calendar"
+    workspace = "2"
+    commands = ["thunderbird"]
+
+    [[workset]]
+    id = "code"
+    name = "Editor"
+    desc = "Editor and terminal"
+    workspace = "name:dev"
+    commands = ["code", "kitty"]
+"#;
+
+fn harness() -> Harness {
+    Harness::new(toml::from_str(CONFIG).unwrap())
+}
+
+#[track_caller]
+fn assert_screen(h: &Harness, expected: &[&str]) {
+    assert_eq!(h.screen(), expected.join("\n"));
+}
+
+#[test]
+fn populated_list_with_the_first_row_selected() {
+    let mut h = harness();
+    h.draw();
+    assert_screen(
+        &h,
+        &[
+            "┌──────────────────────────────────────────────────────────────────────────────┐",
+            "│   All  │  Work  │  Empty  │  + Add                                           │",
+            "│──────────────────────────────────────────────────────────────────────────────│",
+            "│  No      Name                 Description   Tab            Workspace         │",
+            "│>   1     Mail client          Inbox and cal Work           2                 │",
+            "│    2     Editor               Editor and te Work           name:dev          │",
+            "│                                                                              │",
+            "│                                                                              │",
+            "│                                                                              │",
+            "│                                                                              │",
+            "│                                                                              │",
+            "│                                                                              │",
+            "│                                                                              │",
+            "│                                                                              │",
+            "│                                                                              │",
+            "│                                                                              │",
+            "│                                                                              │",
+            "└──────────────────────────────────────────────────────────────────────────────┘",
+            "Tab: Work                                                                   1/2",
+            "[↵ Run] [e Edit] [n New] [c Copy] [Shift+K MoveUp] [Shift+J MoveDown] [d Delete]",
+        ],
+    );
+}
+
+#[test]
+fn empty_tab_shows_the_create_hint() {
+    let mut h = harness();
+    h.press(KeyCode::Right);
+    h.draw();
+    assert_eq!(h.app.current_tab_id(), Some("empty"));
+    assert_screen(
+        &h,
+        &[
+            "┌──────────────────────────────────────────────────────────────────────────────┐",
+            "│   All  │  Work  │  Empty  │  + Add                                           │",
+            "│──────────────────────────────────────────────────────────────────────────────│",
+            "│                 No worksets in this tab. Press 'n' to create.                │",
+            "│                                                                              │",
+            "│                                                                              │",
+            "│                                                                              │",
+            "│                                                                              │",
+            "│                                                                              │",
+            "│                                                                              │",
+            "│                                                                              │",
+            "│                                                                              │",
+            "│                                                                              │",
+            "│                                                                              │",
+            "│                                                                              │",
+            "│                                                                              │",
+            "│                                                                              │",
+            "└──────────────────────────────────────────────────────────────────────────────┘",
+            "Tab: Empty                                                                  0/0",
+            "[↵ Run] [e Edit] [n New] [c Copy] [Shift+K MoveUp] [Shift+J MoveDown] [d Delete]",
+        ],
+    );
+}
+
+#[test]
+fn new_workset_modal_over_the_list() {
+    let mut h = harness();
+    h.press(KeyCode::Char('n'));
+    // The suggested id is random; pin it so the frame is stable.
+    let Mode::NewDialog(form) = &mut h.app.mode else {
+        panic!("n did not open the New Workset dialog");
+    };
+    form.id = "ws-notes".into();
+    h.draw();
+    assert_screen(
+        &h,
+        &[
+            "┌──────────────────────────────────────────────────────────────────────────────┐",
+            "│   All  │  Work  │  Empty  │  + Add                                           │",
+            "│──────────────────────────────────────────────────────────────────────────────│",
+            "│  No      Name                 Description   Tab            Workspace         │",
+            "│>   1     Mail client          Inbox and cal Work           2                 │",
+            "│    2     E┌New Workset───────────────────────────────────────────┐v          │",
+            "│           │ID: ws-notes                                          │           │",
+            "│           │Name:                                                 │           │",
+            "│           │Desc:                                                 │           │",
+            "│           │Tab: Work                                             │           │",
+            "│           │Template: (no templates)                              │           │",
+            "│           │                                                      │           │",
+            "│           │Enter: Create and return to list  Tab/Shift+Tab: Switc│           │",
+            "│           │                                                      │           │",
+            "│           └──────────────────────────────────────────────────────┘           │",
+            "│                                                                              │",
+            "│                                                                              │",
+            "└──────────────────────────────────────────────────────────────────────────────┘",
+            "Tab: Work                                                                   1/2",
+            "[↵ Run] [e Edit] [n New] [c Copy] [Shift+K MoveUp] [Shift+J MoveDown] [d Delete]",
+        ],
+    );
+}
@@ -1,8 +1,8 @@
 use std::collections::{HashMap, HashSet};
 
-use crate::state::AppState;
+use crate::{config::AllTabPosition, state::AppState};
 
-use super::{ALL_TAB_ID, HomeApp, ResolvedTab};
+use super::{ALL_TAB_ID, HomeApp, ResolvedTab, UNASSIGNED_VIEW_ID};
 
 impl HomeApp {
     pub(super) fn initialize_tabs(&mut self, initial_selected_id: Option<String>) {
@@ -17,12 +17,24 @@ impl HomeApp {
     ) {
         let (tabs, warnings, labels) = self.build_tabs();
         self.tabs = tabs;
+        if self.unassigned_view.is_some() {
+            self.unassigned_view = Some(self.build_unassigned_view());
+        }
         self.workset_tab_labels = labels;
         self.hover_tab = None;
-        if let Some(warning) = warnings.last()
-            && self.message.is_none()
+        if !warnings.is_empty() && self.message.is_none() {
+            self.message = Some(format!(
+                "{} config warning(s); press ! to view",
+                warnings.len()
+            ));
+        }
+        self.tab_warnings = warnings;
+        if !self.tab_warnings.is_empty()
+            && !self.warnings_shown
+            && matches!(self.mode, super::Mode::Normal)
         {
-            self.message = Some(warning.clone());
+            self.warnings_shown = true;
+            self.mode = super::Mode::Warnings { scroll: 0 };
         }
         if self.tabs.is_empty() {
             self.active_tab_idx = 0;
@@ -38,80 +50,182 @@ impl HomeApp {
     }
 
     pub(super) fn build_tabs(&self) -> (Vec<ResolvedTab>, Vec<String>, HashMap<String, String>) {
-        let mut warnings = Vec::new();
         let mut labels: HashMap<String, String> = HashMap::new();
         let all_tab = ResolvedTab {
             id: ALL_TAB_ID.to_string(),
             label: "All".to_string(),
             indices: (0..self.cfg.worksets.len()).collect(),
         };
-        let mut tabs = vec![all_tab];
 
         let tab_enabled = self.cfg.version >= 2 || !self.cfg.tabs.is_empty();
         if self.cfg.tabs.is_empty() || !tab_enabled {
-            return (tabs, warnings, labels);
+            return (vec![all_tab], Vec::new(), labels);
+        }
+
+        let (resolved, warnings) = self.cfg.resolve_tabs(&self.state.last_run);
+        for msg in &warnings {
+            eprintln!("warning: {msg}");
+        }
+        let mut tabs = Vec::with_capacity(resolved.len());
+        for (tab, indices) in self.cfg.tabs.iter().zip(resolved) {
+            for &idx in &indices {
+                labels.insert(self.cfg.worksets[idx].id.clone(), tab.label.clone());
+            }
+            tabs.push(ResolvedTab {
+                id: tab.id.clone(),
+                label: tab.label.clone(),
+                indices,
+            });
         }
 
-        let index_map: HashMap<String, usize> = self
+        // pinned tabs first; sort_by_key is stable so manual order is kept within each group.
+        // Sorting after resolution keeps workset ownership tied to config order.
+        let pinned: HashSet<&str> = self
             .cfg
-            .worksets
+            .tabs
             .iter()
-            .enumerate()
-            .map(|(idx, ws)| (ws.id.clone(), idx))
+            .filter(|tab| tab.pinned)
+            .map(|tab| tab.id.as_str())
             .collect();
+        tabs.sort_by_key(|tab| !pinned.contains(tab.id.as_str()));
+
+        match self.cfg.all_tab_position {
+            Some(AllTabPosition::Last) => tabs.push(all_tab),
+            Some(AllTabPosition::First) | None => tabs.insert(0, all_tab),
+        }
 
-        let mut assigned: HashSet<String> = HashSet::new();
+        (tabs, warnings, labels)
+    }
 
-        for tab in &self.cfg.tabs {
-            let mut indices = Vec::new();
-            let mut seen: HashSet<String> = HashSet::new();
+    /// With no absorbing tabs, duplicate tab ids or warnings, every resolved tab is exactly its
+    /// config list, so edits that only move ids between lists can be mirrored tab by tab.
+    fn tabs_patchable(&self) -> bool {
+        let mut ids = HashSet::new();
+        self.tab_warnings.is_empty()
+            && self
+                .cfg
+                .tabs
+                .iter()
+                .all(|tab| !tab.include_unassigned && ids.insert(tab.id.as_str()))
+    }
+
+    /// Re-resolves just `tab_ids` after an assign or in-tab reorder instead of a full
+    /// `apply_tabs`, so the selection and scroll stay put; `workset_id` stays selected while
+    /// it is in the current tab. Falls back to `apply_tabs` when the tabs aren't patchable.
+    pub(super) fn update_tabs_in_place(
+        &mut self,
+        tab_ids: &[&str],
+        workset_id: &str,
+        visible_rows: usize,
+    ) {
+        if !self.tabs_patchable() {
+            let tab = self.current_tab_id().map(str::to_string);
+            self.apply_tabs(tab, Some(workset_id.to_string()));
+            return;
+        }
+        let index_map: HashMap<&str, usize> = self
+            .cfg
+            .worksets
+            .iter()
+            .enumerate()
+            .map(|(idx, ws)| (ws.id.as_str(), idx))
+            .collect();
+        self.workset_tab_labels.remove(workset_id);
+        for tab in self
+            .cfg
+            .tabs
+            .iter()
+            .filter(|tab| tab_ids.contains(&tab.id.as_str()))
+        {
             for id in &tab.worksets {
-                if let Some(idx) = index_map.get(id) {
-                    if assigned.contains(id) {
-                        let msg = format!(
-                            "workset '{}' already assigned to another tab; skipped in '{}'",
-                            id, tab.id
-                        );
-                        eprintln!("warning: {msg}");
-                        warnings.push(msg);
-                        continue;
-                    }
-                    if seen.insert(id.clone()) {
-                        indices.push(*idx);
-                        labels.insert(id.clone(), tab.label.clone());
-                        assigned.insert(id.clone());
-                    }
-                } else {
-                    let msg = format!("tab '{}' references missing workset '{}'", tab.id, id);
-                    eprintln!("warning: {msg}");
-                    warnings.push(msg);
-                }
+                self.workset_tab_labels
+                    .insert(id.clone(), tab.label.clone());
             }
-            if tab.include_unassigned {
-                let mut extras: Vec<_> = index_map
+            if let Some(resolved) = self.tabs.iter_mut().find(|t| t.id == tab.id) {
+                resolved.indices = tab
+                    .worksets
                     .iter()
-                    .filter(|(id, _)| {
-                        !seen.contains::<str>(id.as_str()) && !assigned.contains::<str>(id.as_str())
-                    })
-                    .map(|(id, idx)| (id.clone(), *idx))
+                    .filter_map(|id| index_map.get(id.as_str()).copied())
                     .collect();
-                extras.sort_by(|a, b| a.0.cmp(&b.0));
-                for (id, idx) in extras {
-                    if seen.insert(id.clone()) {
-                        indices.push(idx);
-                        labels.insert(id.clone(), tab.label.clone());
-                        assigned.insert(id);
-                    }
+            }
+        }
+        self.reselect_after_patch(workset_id, visible_rows);
+    }
+
+    /// Mirrors swapping `cfg.worksets[a]` and `[b]` (a move in the All tab) in every resolved
+    /// tab, falling back to `apply_tabs` like `update_tabs_in_place`.
+    pub(super) fn update_tabs_after_swap(
+        &mut self,
+        a: usize,
+        b: usize,
+        workset_id: &str,
+        visible_rows: usize,
+    ) {
+        if !self.tabs_patchable() {
+            let tab = self.current_tab_id().map(str::to_string);
+            self.apply_tabs(tab, Some(workset_id.to_string()));
+            return;
+        }
+        // The All tab lists positions, which a swap leaves as they are.
+        for tab in self.tabs.iter_mut().filter(|tab| tab.id != ALL_TAB_ID) {
+            for idx in tab.indices.iter_mut() {
+                if *idx == a {
+                    *idx = b;
+                } else if *idx == b {
+                    *idx = a;
                 }
             }
-            tabs.push(ResolvedTab {
-                id: tab.id.clone(),
-                label: tab.label.clone(),
-                indices,
-            });
         }
+        self.reselect_after_patch(workset_id, visible_rows);
+    }
 
-        (tabs, warnings, labels)
+    fn reselect_after_patch(&mut self, workset_id: &str, visible_rows: usize) {
+        if self.unassigned_view.is_some() {
+            self.unassigned_view = Some(self.build_unassigned_view());
+        }
+        let idx = self
+            .index_in_current_tab(workset_id)
+            .or(self.table_state.selected())
+            .unwrap_or(0);
+        self.select_index(idx, visible_rows);
+    }
+
+    /// Worksets whose id is not listed in any `[[tab]]`, in config order. Unlike
+    /// `include_unassigned`, this ignores tabs that merely absorb unassigned worksets.
+    pub(super) fn build_unassigned_view(&self) -> ResolvedTab {
+        let listed: HashSet<&str> = self
+            .cfg
+            .tabs
+            .iter()
+            .flat_map(|tab| tab.worksets.iter().map(String::as_str))
+            .collect();
+        ResolvedTab {
+            id: UNASSIGNED_VIEW_ID.to_string(),
+            label: "Unassigned".to_string(),
+            indices: self
+                .cfg
+                .worksets
+                .iter()
+                .enumerate()
+                .filter(|(_, ws)| !listed.contains(ws.id.as_str()))
+                .map(|(idx, _)| idx)
+                .collect(),
+        }
+    }
+
+    pub(super) fn toggle_unassigned_view(&mut self) {
+        let preferred = self.current_id();
+        if self.unassigned_view.take().is_some() {
+            self.message = None;
+        } else {
+            let view = self.build_unassigned_view();
+            self.message = Some(format!(
+                "{} unassigned workset(s); a: assign, u: back",
+                view.indices.len()
+            ));
+            self.unassigned_view = Some(view);
+        }
+        self.apply_selection_for_active_tab(preferred);
     }
 
     pub(super) fn resolve_tab_index(&self, preferred: Option<&str>) -> usize {
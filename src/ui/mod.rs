@@ -1,2 +1,8 @@
+mod clock;
 pub mod editor;
 pub mod home;
+mod selection;
+mod selection_preview;
+mod status;
+mod terminal;
+mod window_counts;
@@ -0,0 +1,63 @@
+use ratatui::style::{Color, Modifier, Style};
+use unicode_width::UnicodeWidthStr;
+
+use crate::config::{AppConfig, DEFAULT_HIGHLIGHT_SYMBOL};
+
+/// Selection marker and colors from the config, shared by the home list and the editor.
+#[derive(Debug, Clone)]
+pub(crate) struct Selection {
+    pub(crate) symbol: String,
+    pub(crate) style: Style,
+}
+
+impl Selection {
+    pub(crate) fn from_config(cfg: &AppConfig) -> Self {
+        let (fg, bg) = cfg.selection_colors();
+        Self {
+            symbol: cfg.highlight_symbol().to_string(),
+            style: Style::default().fg(fg).bg(bg).add_modifier(Modifier::BOLD),
+        }
+    }
+
+    /// Blank prefix as wide as the symbol, so unselected lines stay aligned with the
+    /// selected one even when the symbol is several cells wide.
+    pub(crate) fn padding(&self) -> String {
+        " ".repeat(self.symbol.width())
+    }
+
+    /// `symbol` when selected, matching padding otherwise.
+    pub(crate) fn prefix(&self, selected: bool) -> String {
+        if selected {
+            self.symbol.clone()
+        } else {
+            self.padding()
+        }
+    }
+}
+
+impl Default for Selection {
+    fn default() -> Self {
+        Self {
+            symbol: DEFAULT_HIGHLIGHT_SYMBOL.to_string(),
+            style: Style::default()
+                .fg(Color::Black)
+                .bg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn padding_matches_wide_symbols() {
+        let selection = Selection {
+            symbol: "👉".into(),
+            ..Selection::default()
+        };
+        assert_eq!(selection.prefix(true), "👉");
+        assert_eq!(selection.prefix(false), "  ");
+    }
+}
@@ -0,0 +1,91 @@
+use std::time::{Duration, Instant};
+
+use crate::{config::AppConfig, run::WorkspacePreview};
+
+/// How long the selection has to rest on a workset before its workspace is previewed, so
+/// scrolling through the list doesn't switch workspaces on every row.
+const PREVIEW_SETTLE: Duration = Duration::from_millis(300);
+
+/// Debounced `preview_workspace_on_select`: the home list reports its selection after every
+/// event and the idle tick previews it once it has settled.
+#[derive(Debug, Default)]
+pub(crate) struct SelectionPreview {
+    /// Selected workset id and when it became selected, until it has been previewed.
+    pending: Option<(String, Instant)>,
+    previewed: Option<String>,
+    workspace: WorkspacePreview,
+}
+
+impl SelectionPreview {
+    pub(crate) fn track(&mut self, selected: Option<String>) {
+        self.track_at(selected, Instant::now());
+    }
+
+    fn track_at(&mut self, selected: Option<String>, now: Instant) {
+        let Some(id) = selected else {
+            self.pending = None;
+            return;
+        };
+        let unchanged = match &self.pending {
+            Some((pending, _)) => *pending == id,
+            None => self.previewed.as_deref() == Some(id.as_str()),
+        };
+        if !unchanged {
+            self.pending = Some((id, now));
+        }
+    }
+
+    /// The pending workset once it has stayed selected for [`PREVIEW_SETTLE`].
+    fn settled_at(&mut self, now: Instant) -> Option<String> {
+        let (_, since) = self.pending.as_ref()?;
+        if now.duration_since(*since) < PREVIEW_SETTLE {
+            return None;
+        }
+        let (id, _) = self.pending.take()?;
+        self.previewed = Some(id.clone());
+        Some(id)
+    }
+
+    /// Previews the settled selection's workspace. Returns whether it switched.
+    pub(crate) fn tick(&mut self, cfg: &AppConfig) -> anyhow::Result<bool> {
+        let Some(id) = self.settled_at(Instant::now()) else {
+            return Ok(false);
+        };
+        match cfg.worksets.iter().find(|ws| ws.id == id) {
+            Some(ws) => self.workspace.show(ws),
+            None => Ok(false),
+        }
+    }
+
+    /// Returns to the workspace that was active before the first preview.
+    pub(crate) fn restore(&mut self) -> anyhow::Result<()> {
+        self.pending = None;
+        self.previewed = None;
+        self.workspace.restore()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selection_is_previewed_once_it_settles() {
+        let start = Instant::now();
+        let at = |ms| start + Duration::from_millis(ms);
+        let mut preview = SelectionPreview::default();
+
+        preview.track_at(Some("a".into()), at(0));
+        preview.track_at(Some("b".into()), at(100));
+        assert_eq!(preview.settled_at(at(350)), None, "b moved in at 100ms");
+        preview.track_at(Some("b".into()), at(380));
+        assert_eq!(preview.settled_at(at(400)).as_deref(), Some("b"));
+        assert_eq!(preview.settled_at(at(900)), None, "previewed only once");
+
+        preview.track_at(Some("b".into()), at(1000));
+        assert_eq!(preview.settled_at(at(2000)), None);
+        preview.track_at(None, at(2000));
+        preview.track_at(Some("c".into()), at(2100));
+        assert_eq!(preview.settled_at(at(2400)).as_deref(), Some("c"));
+    }
+}
@@ -0,0 +1,70 @@
+use std::time::{Duration, Instant};
+
+/// Tracks how long the current status message has been on screen so idle ticks can clear it.
+/// Messages are observed rather than timestamped where they are set, so every existing
+/// `message = Some(..)` keeps working unchanged.
+#[derive(Debug, Default)]
+pub(crate) struct MessageExpiry {
+    shown: Option<(String, Instant)>,
+}
+
+impl MessageExpiry {
+    /// Clears `message` once it has been visible for `timeout` (`None` keeps it forever).
+    /// Returns whether the message was cleared.
+    pub(crate) fn tick(&mut self, message: &mut Option<String>, timeout: Option<Duration>) -> bool {
+        self.tick_at(message, timeout, Instant::now())
+    }
+
+    fn tick_at(
+        &mut self,
+        message: &mut Option<String>,
+        timeout: Option<Duration>,
+        now: Instant,
+    ) -> bool {
+        let Some(text) = message.as_deref() else {
+            self.shown = None;
+            return false;
+        };
+        match &self.shown {
+            Some((seen, since)) if seen == text => {
+                if timeout.is_some_and(|limit| now.duration_since(*since) >= limit) {
+                    *message = None;
+                    self.shown = None;
+                    return true;
+                }
+                false
+            }
+            _ => {
+                self.shown = Some((text.to_string(), now));
+                false
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn message_expires_after_timeout_and_restarts_on_change() {
+        let timeout = Some(Duration::from_secs(4));
+        let start = Instant::now();
+        let mut expiry = MessageExpiry::default();
+        let mut message = Some("Deletion cancelled".to_string());
+
+        assert!(!expiry.tick_at(&mut message, timeout, start));
+        assert!(!expiry.tick_at(&mut message, timeout, start + Duration::from_secs(3)));
+
+        message = Some("Tab saved".into());
+        assert!(!expiry.tick_at(&mut message, timeout, start + Duration::from_secs(5)));
+        assert!(message.is_some());
+        assert!(expiry.tick_at(&mut message, timeout, start + Duration::from_secs(9)));
+        assert!(message.is_none());
+
+        let mut sticky = Some("kept".to_string());
+        expiry.tick_at(&mut sticky, None, start);
+        assert!(!expiry.tick_at(&mut sticky, None, start + Duration::from_secs(60)));
+        assert!(sticky.is_some());
+    }
+}
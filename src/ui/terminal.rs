@@ -0,0 +1,271 @@
+use std::{
+    io::{self, Stdout, Write},
+    panic,
+    sync::{
+        Once,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+use anyhow::Result;
+use crossterm::{
+    cursor::{Hide, Show},
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyModifiers,
+    },
+    execute,
+    terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
+};
+use ratatui::{
+    Frame, Terminal,
+    backend::{Backend, CrosstermBackend},
+    layout::Alignment,
+    widgets::{Paragraph, Wrap},
+};
+
+static PANIC_HOOK: Once = Once::new();
+/// Sessions that still own the terminal; the panic hook leaves the TUI modes only while one does.
+static ACTIVE_SESSIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// How long the event loops wait for input before running time-based updates. Short enough
+/// for timers to feel live, long enough that an idle TUI barely uses the CPU.
+pub(crate) const TICK_RATE: Duration = Duration::from_millis(250);
+
+/// Backends that can switch the terminal into and out of the TUI modes
+/// (raw mode, alternate screen, mouse capture, cursor visibility).
+pub(crate) trait SessionBackend: Backend {
+    fn enter_session(&mut self, hide_cursor: bool) -> io::Result<()>;
+    fn leave_session(&mut self) -> io::Result<()>;
+}
+
+impl<W: Write> SessionBackend for CrosstermBackend<W> {
+    fn enter_session(&mut self, hide_cursor: bool) -> io::Result<()> {
+        enable_raw_mode()?;
+        execute!(self, EnterAlternateScreen, EnableMouseCapture)?;
+        if hide_cursor {
+            execute!(self, Hide)
+        } else {
+            execute!(self, Show)
+        }
+    }
+
+    fn leave_session(&mut self) -> io::Result<()> {
+        // Always attempt both steps; report the first failure.
+        let raw = disable_raw_mode();
+        execute!(self, LeaveAlternateScreen, DisableMouseCapture, Show)?;
+        raw
+    }
+}
+
+/// Owns the `Terminal` for one TUI screen. Setup happens in the constructor and teardown in
+/// [`TerminalSession::restore`] or `Drop`, so early returns, errors and panics all leave the
+/// terminal usable.
+pub(crate) struct TerminalSession<B: SessionBackend> {
+    terminal: Terminal<B>,
+    active: bool,
+}
+
+impl TerminalSession<CrosstermBackend<Stdout>> {
+    pub(crate) fn new(hide_cursor: bool) -> Result<Self> {
+        Self::with_backend(CrosstermBackend::new(io::stdout()), hide_cursor)
+    }
+}
+
+impl<B: SessionBackend> TerminalSession<B> {
+    pub(crate) fn with_backend(backend: B, hide_cursor: bool) -> Result<Self> {
+        install_panic_hook();
+        let mut session = Self {
+            terminal: Terminal::new(backend)?,
+            active: true,
+        };
+        // The session owns teardown from here on, even if the remaining setup fails.
+        ACTIVE_SESSIONS.fetch_add(1, Ordering::SeqCst);
+        session.terminal.backend_mut().enter_session(hide_cursor)?;
+        Ok(session)
+    }
+
+    pub(crate) fn terminal(&mut self) -> &mut Terminal<B> {
+        &mut self.terminal
+    }
+
+    /// Leave the TUI modes. Safe to call more than once; only the first call does anything.
+    pub(crate) fn restore(&mut self) -> Result<()> {
+        if !std::mem::replace(&mut self.active, false) {
+            return Ok(());
+        }
+        ACTIVE_SESSIONS.fetch_sub(1, Ordering::SeqCst);
+        self.terminal.backend_mut().leave_session()?;
+        Ok(())
+    }
+}
+
+impl<B: SessionBackend> Drop for TerminalSession<B> {
+    fn drop(&mut self) {
+        let _ = self.restore();
+    }
+}
+
+/// Restore the terminal before the default hook prints, so the panic message lands on the
+/// normal screen instead of the discarded alternate one. Without a live session (CLI paths, or
+/// after the TUI closed) the terminal is left untouched.
+fn install_panic_hook() {
+    PANIC_HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if ACTIVE_SESSIONS.load(Ordering::SeqCst) > 0 {
+                let _ = CrosstermBackend::new(io::stdout()).leave_session();
+            }
+            previous(info);
+        }));
+    });
+}
+
+/// Waits up to [`TICK_RATE`] for input; `None` means the tick elapsed without an event.
+pub(crate) fn next_event() -> Result<Option<Event>> {
+    if event::poll(TICK_RATE)? {
+        Ok(Some(event::read()?))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Raw mode swallows SIGINT, so Ctrl-C arrives as a key event instead.
+pub(crate) fn is_interrupt(key: &KeyEvent) -> bool {
+    key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL)
+}
+
+/// Below `min_width`x`min_height` the fixed rows of a screen no longer fit and ratatui squeezes
+/// them into garbage, so this draws a one-line notice instead. Returns whether it did; the
+/// caller skips its normal drawing then and picks up again on the next resize.
+pub(crate) fn draw_too_small(f: &mut Frame, min_width: u16, min_height: u16) -> bool {
+    let area = f.size();
+    if area.width >= min_width && area.height >= min_height {
+        return false;
+    }
+    let notice = Paragraph::new(format!(
+        "terminal too small (need ≥ {min_width}x{min_height})"
+    ))
+    .alignment(Alignment::Center)
+    .wrap(Wrap { trim: true });
+    f.render_widget(notice, area);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{
+        backend::{ClearType, TestBackend, WindowSize},
+        buffer::Cell,
+        layout::Rect,
+    };
+    use std::{cell::Cell as Counter, rc::Rc};
+
+    struct MockBackend {
+        inner: TestBackend,
+        enters: Rc<Counter<usize>>,
+        leaves: Rc<Counter<usize>>,
+    }
+
+    impl Backend for MockBackend {
+        fn draw<'a, I>(&mut self, content: I) -> io::Result<()>
+        where
+            I: Iterator<Item = (u16, u16, &'a Cell)>,
+        {
+            self.inner.draw(content)
+        }
+        fn hide_cursor(&mut self) -> io::Result<()> {
+            self.inner.hide_cursor()
+        }
+        fn show_cursor(&mut self) -> io::Result<()> {
+            self.inner.show_cursor()
+        }
+        fn get_cursor(&mut self) -> io::Result<(u16, u16)> {
+            self.inner.get_cursor()
+        }
+        fn set_cursor(&mut self, x: u16, y: u16) -> io::Result<()> {
+            self.inner.set_cursor(x, y)
+        }
+        fn clear(&mut self) -> io::Result<()> {
+            self.inner.clear()
+        }
+        fn clear_region(&mut self, clear_type: ClearType) -> io::Result<()> {
+            self.inner.clear_region(clear_type)
+        }
+        fn size(&self) -> io::Result<Rect> {
+            self.inner.size()
+        }
+        fn window_size(&mut self) -> io::Result<WindowSize> {
+            self.inner.window_size()
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            self.inner.flush()
+        }
+    }
+
+    impl SessionBackend for MockBackend {
+        fn enter_session(&mut self, _hide_cursor: bool) -> io::Result<()> {
+            self.enters.set(self.enters.get() + 1);
+            Ok(())
+        }
+        fn leave_session(&mut self) -> io::Result<()> {
+            self.leaves.set(self.leaves.get() + 1);
+            Ok(())
+        }
+    }
+
+    fn mock() -> (MockBackend, Rc<Counter<usize>>, Rc<Counter<usize>>) {
+        let enters = Rc::new(Counter::new(0));
+        let leaves = Rc::new(Counter::new(0));
+        let backend = MockBackend {
+            inner: TestBackend::new(20, 5),
+            enters: enters.clone(),
+            leaves: leaves.clone(),
+        };
+        (backend, enters, leaves)
+    }
+
+    #[test]
+    fn dropping_session_restores_terminal_once() {
+        let (backend, enters, leaves) = mock();
+        let session = TerminalSession::with_backend(backend, true).unwrap();
+        assert_eq!((enters.get(), leaves.get()), (1, 0));
+        drop(session);
+        assert_eq!((enters.get(), leaves.get()), (1, 1));
+    }
+
+    #[test]
+    fn explicit_restore_is_idempotent_with_drop() {
+        let (backend, _, leaves) = mock();
+        let mut session = TerminalSession::with_backend(backend, false).unwrap();
+        session.restore().unwrap();
+        session.restore().unwrap();
+        drop(session);
+        assert_eq!(leaves.get(), 1);
+    }
+
+    #[test]
+    fn too_small_notice_replaces_the_ui_below_the_minimum() {
+        let mut terminal = Terminal::new(TestBackend::new(20, 5)).unwrap();
+        let mut drawn = false;
+        terminal
+            .draw(|f| drawn = draw_too_small(f, 40, 13))
+            .unwrap();
+        assert!(drawn);
+        let text: String = terminal
+            .backend()
+            .buffer()
+            .content
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(text.contains("terminal too small"));
+
+        terminal.backend_mut().resize(40, 13);
+        terminal
+            .draw(|f| drawn = draw_too_small(f, 40, 13))
+            .unwrap();
+        assert!(!drawn);
+    }
+}
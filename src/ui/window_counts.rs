@@ -0,0 +1,63 @@
+use std::time::{Duration, Instant};
+
+use crate::{config::Workset, run::LiveWindowCounts};
+
+/// How long one client list is reused before the home list asks Hyprland again.
+const WINDOW_COUNT_TTL: Duration = Duration::from_secs(3);
+
+/// Cached window counts for the Workspace column. A failed fetch is cached too, so an
+/// unreachable Hyprland is asked at most once per TTL and the counts simply stay blank.
+#[derive(Debug, Default)]
+pub(crate) struct WindowCounts {
+    fetched: Option<(Instant, Option<LiveWindowCounts>)>,
+}
+
+impl WindowCounts {
+    /// Refetches once the cached list is older than the TTL. Returns whether it refetched,
+    /// i.e. whether the list should be redrawn.
+    pub(crate) fn refresh(&mut self) -> bool {
+        self.refresh_at(Instant::now(), LiveWindowCounts::fetch)
+    }
+
+    fn refresh_at(
+        &mut self,
+        now: Instant,
+        fetch: impl FnOnce() -> Option<LiveWindowCounts>,
+    ) -> bool {
+        if let Some((at, _)) = &self.fetched
+            && now.duration_since(*at) < WINDOW_COUNT_TTL
+        {
+            return false;
+        }
+        self.fetched = Some((now, fetch()));
+        true
+    }
+
+    pub(crate) fn for_workset(&self, ws: &Workset) -> Option<usize> {
+        self.fetched
+            .as_ref()
+            .and_then(|(_, counts)| counts.as_ref())
+            .and_then(|counts| counts.for_workset(ws))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_are_fetched_once_per_ttl() {
+        let start = Instant::now();
+        let mut counts = WindowCounts::default();
+        let mut fetches = 0;
+        let mut fetch = || {
+            fetches += 1;
+            None
+        };
+
+        assert!(counts.refresh_at(start, &mut fetch));
+        assert!(!counts.refresh_at(start + Duration::from_secs(1), &mut fetch));
+        assert!(counts.refresh_at(start + WINDOW_COUNT_TTL, &mut fetch));
+        assert_eq!(fetches, 2);
+    }
+}